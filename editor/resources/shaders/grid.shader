@@ -14,6 +14,22 @@
             name: "zAxisColor",
             kind: Color(r: 0, g: 0, b: 255, a: 255),
         ),
+        (
+            name: "majorLineColor",
+            kind: Color(r: 60, g: 60, b: 60, a: 255),
+        ),
+        (
+            name: "cellSize",
+            kind: Float(1.0),
+        ),
+        (
+            name: "majorLineEvery",
+            kind: Int(10),
+        ),
+        (
+            name: "fadeDistance",
+            kind: Float(100.0),
+        ),
     ],
 
     passes: [
@@ -81,6 +97,13 @@
                 uniform vec4 diffuseColor;
                 uniform vec4 xAxisColor;
                 uniform vec4 zAxisColor;
+                uniform vec4 majorLineColor;
+                // Distance between adjacent lines in world units.
+                uniform float cellSize;
+                // Every N-th line is a major (thicker) line.
+                uniform int majorLineEvery;
+                // Distance from the camera at which the grid fades out completely.
+                uniform float fadeDistance;
 
                 uniform mat4 fyrox_viewProjectionMatrix;
                 uniform float fyrox_zNear;
@@ -92,16 +115,23 @@
                 in vec3 nearPoint;
                 in vec3 farPoint;
 
-                vec4 grid(vec3 fragPos3D, float scale) {
-                    vec2 coord = fragPos3D.xz * scale;
-                    vec2 derivative = fwidth(coord);
-                    vec2 grid = abs(fract(coord - 0.5) - 0.5) / derivative;
-                    float line = min(grid.x, grid.y);
+                // Returns coverage of the lines of the grid with the given coordinates, thickness is in pixels.
+                float lines(vec2 coord, float thickness) {
+                    vec2 grid = abs(fract(coord - 0.5) - 0.5) / (fwidth(coord) * thickness);
+                    return 1.0 - min(min(grid.x, grid.y), 1.0);
+                }
+
+                vec4 grid(vec3 fragPos3D) {
+                    vec2 coord = fragPos3D.xz / max(cellSize, 0.0001);
+                    vec2 derivative = fwidth(fragPos3D.xz);
                     float minZ = 0.5 * min(derivative.y, 1);
                     float minX = 0.5 * min(derivative.x, 1);
 
-                    vec4 color = diffuseColor;
-                    float alpha = 1.0 - min(line, 1.0);
+                    float minorAlpha = lines(coord, 1.0);
+                    float majorAlpha = lines(coord / float(max(majorLineEvery, 1)), 2.0);
+
+                    vec4 color = majorAlpha >= 0.5 ? majorLineColor : diffuseColor;
+                    float alpha = max(minorAlpha, majorAlpha);
                     // Sharpen lines a bit.
                     color.a = alpha >= 0.5 ? 1.0 : 0.0;
 
@@ -118,6 +148,11 @@
                         color.a *= cosAngle;
                     }
 
+                    if (fadeDistance > 0.0) {
+                        float distance = length(fragPos3D - fyrox_cameraPosition);
+                        color.a *= 1.0 - smoothstep(0.5 * fadeDistance, fadeDistance, distance);
+                    }
+
                     return color;
                 }
 
@@ -135,7 +170,7 @@
                     float depth = computeDepth(fragPos3D);
                     gl_FragDepth = ((gl_DepthRange.diff * depth) + gl_DepthRange.near + gl_DepthRange.far) / 2.0;
 
-                    FragColor = grid(fragPos3D, 1.0);
+                    FragColor = grid(fragPos3D);
                     FragColor.a *= float(t > 0);
 
                     // Alpha test to prevent blending issues.
@@ -1,5 +1,6 @@
 use crate::fyrox::{
     core::{
+        log::Log,
         reflect::{is_path_to_array_element, Reflect, ResolvePath, SetFieldByPathError},
         ComponentProvider,
     },
@@ -190,7 +191,7 @@ impl CommandStack {
             ) {
                 for mut dropped_command in commands.drain(range) {
                     if debug {
-                        println!("Finalizing command {:?}", dropped_command);
+                        Log::info(format!("Finalizing command {:?}", dropped_command));
                     }
                     dropped_command.finalize(context);
                 }
@@ -215,7 +216,7 @@ impl CommandStack {
         }
 
         if self.debug {
-            println!("Executing command {:?}", command);
+            Log::info(format!("Executing command {:?}", command));
         }
 
         command.execute(context);
@@ -228,7 +229,7 @@ impl CommandStack {
             if let Some(top) = self.top.as_mut() {
                 if let Some(command) = self.commands.get_mut(*top) {
                     if self.debug {
-                        println!("Undo command {:?}", command);
+                        Log::info(format!("Undo command {:?}", command));
                     }
                     command.revert(context)
                 }
@@ -261,7 +262,7 @@ impl CommandStack {
 
             if let Some(command) = command {
                 if self.debug {
-                    println!("Redo command {:?}", command);
+                    Log::info(format!("Redo command {:?}", command));
                 }
                 command.execute(context)
             }
@@ -271,13 +272,17 @@ impl CommandStack {
     pub fn clear(&mut self, context: &mut dyn CommandContext) {
         for mut dropped_command in self.commands.drain(..) {
             if self.debug {
-                println!("Finalizing command {:?}", dropped_command);
+                Log::info(format!("Finalizing command {:?}", dropped_command));
             }
             dropped_command.finalize(context);
         }
     }
 }
 
+/// Creates a command, that applies the given property change to an entity, that is provided by `entity_getter`.
+/// Properties are addressed by their reflection paths (for example, `emitters[0].position`), so there is no
+/// need to have a separate command for each property of each entity - a single call of this function is
+/// enough to handle every [`PropertyChanged`] message of an inspector.
 pub fn make_command<F>(property_changed: &PropertyChanged, entity_getter: F) -> Option<Command>
 where
     F: 'static + FnMut(&mut dyn CommandContext) -> &mut dyn Reflect,
@@ -309,13 +314,15 @@ where
     let mut func = Some(func);
     entity.resolve_path_mut(path, &mut |result| match result {
         Ok(field) => func.take().unwrap()(field),
-        Err(e) => fyrox::core::log::Log::err(format!(
+        Err(e) => Log::warn(format!(
             "There is no such property {}! Reason: {:?}",
             path, e
         )),
     })
 }
 
+/// A generic command, that sets a value of a property at the given reflection path. The previous value is
+/// stored in the command, so the command could be reverted.
 pub struct SetPropertyCommand<F>
 where
     F: FnMut(&mut dyn CommandContext) -> &mut dyn Reflect,
@@ -350,7 +357,7 @@ where
         if is_path_to_array_element(&self.path) {
             (self.entity_getter)(ctx).resolve_path_mut(&self.path, &mut |result| match result {
                 Err(reason) => {
-                    fyrox::core::log::Log::err(format!(
+                    Log::warn(format!(
                         "Failed to set property {}! Invalid path {:?}!",
                         self.path, reason
                     ));
@@ -360,7 +367,7 @@ where
                         self.value = Some(old_value);
                     }
                     Err(current_value) => {
-                        fyrox::core::log::Log::err(format!(
+                        Log::err(format!(
                             "Failed to set property {}! Incompatible types {}!",
                             self.path,
                             current_value.type_name()
@@ -380,7 +387,7 @@ where
                     Err(result) => {
                         let value = match result {
                             SetFieldByPathError::InvalidPath { value, reason } => {
-                                fyrox::core::log::Log::err(format!(
+                                Log::warn(format!(
                                     "Failed to set property {}! Invalid path {:?}!",
                                     self.path, reason
                                 ));
@@ -388,7 +395,7 @@ where
                                 value
                             }
                             SetFieldByPathError::InvalidValue(value) => {
-                                fyrox::core::log::Log::err(format!(
+                                Log::err(format!(
                                     "Failed to set property {}! Incompatible types {}!",
                                     self.path,
                                     value.type_name()
@@ -466,7 +473,7 @@ where
             field.as_list_mut(&mut |result| {
                 if let Some(list) = result {
                     if let Err(item) = list.reflect_push(self.item.take().unwrap()) {
-                        fyrox::core::log::Log::err(format!(
+                        Log::err(format!(
                             "Failed to push item to {} collection. Type mismatch {} and {}!",
                             self.path,
                             item.type_name(),
@@ -475,10 +482,7 @@ where
                         self.item = Some(item);
                     }
                 } else {
-                    fyrox::core::log::Log::err(format!(
-                        "Property {} is not a collection!",
-                        self.path
-                    ))
+                    Log::err(format!("Property {} is not a collection!", self.path))
                 }
             });
         })
@@ -491,16 +495,10 @@ where
                     if let Some(item) = list.reflect_pop() {
                         self.item = Some(item);
                     } else {
-                        fyrox::core::log::Log::err(format!(
-                            "Failed to pop item from {} collection!",
-                            self.path
-                        ))
+                        Log::err(format!("Failed to pop item from {} collection!", self.path))
                     }
                 } else {
-                    fyrox::core::log::Log::err(format!(
-                        "Property {} is not a collection!",
-                        self.path
-                    ))
+                    Log::err(format!("Property {} is not a collection!", self.path))
                 }
             });
         })
@@ -554,33 +552,172 @@ where
                 if let Some(list) = result {
                     self.value = list.reflect_remove(self.index);
                 } else {
-                    fyrox::core::log::Log::err(format!(
-                        "Property {} is not a collection!",
-                        self.path
-                    ))
+                    Log::err(format!("Property {} is not a collection!", self.path))
                 }
             })
         })
     }
 
     fn revert(&mut self, ctx: &mut dyn CommandContext) {
+        // Nothing was removed (for example, the index was out of bounds), so there is nothing to restore.
+        if self.value.is_none() {
+            return;
+        }
+
         try_modify_property((self.entity_getter)(ctx), &self.path, |field| {
             field.as_list_mut(&mut |result| {
                 if let Some(list) = result {
                     if let Err(item) = list.reflect_insert(self.index, self.value.take().unwrap()) {
                         self.value = Some(item);
-                        fyrox::core::log::Log::err(format!(
+                        Log::err(format!(
                             "Failed to insert item to {} collection. Type mismatch!",
                             self.path
                         ))
                     }
                 } else {
-                    fyrox::core::log::Log::err(format!(
-                        "Property {} is not a collection!",
-                        self.path
-                    ))
+                    Log::err(format!("Property {} is not a collection!", self.path))
                 }
             });
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::{CommandContext, CommandTrait, RemoveCollectionItemCommand, SetPropertyCommand},
+        fyrox::{
+            core::{
+                algebra::Vector3,
+                log::{Log, MessageKind},
+                reflect::Reflect,
+                type_traits::prelude::*,
+            },
+            scene::particle_system::{
+                emitter::{base::BaseEmitterBuilder, sphere::SphereEmitterBuilder},
+                ParticleSystem,
+            },
+        },
+    };
+
+    #[derive(Debug, ComponentProvider)]
+    struct ParticleSystemContext {
+        particle_system: ParticleSystem,
+    }
+
+    impl CommandContext for ParticleSystemContext {}
+
+    fn particle_system_context() -> ParticleSystemContext {
+        ParticleSystemContext {
+            particle_system: ParticleSystem::default(),
+        }
+    }
+
+    fn particle_system(ctx: &mut dyn CommandContext) -> &mut dyn Reflect {
+        &mut ctx.get_mut::<ParticleSystemContext>().particle_system
+    }
+
+    fn emitter_positions(particle_system: &ParticleSystem) -> Vec<Vector3<f32>> {
+        particle_system
+            .emitters
+            .iter()
+            .map(|emitter| emitter.position())
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_emitter_undo() {
+        let positions = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+        ];
+
+        let mut context = particle_system_context();
+        context
+            .particle_system
+            .emitters
+            .set_value_and_mark_modified(
+                positions
+                    .iter()
+                    .map(|position| {
+                        SphereEmitterBuilder::new(
+                            BaseEmitterBuilder::new().with_position(*position),
+                        )
+                        .build()
+                    })
+                    .collect(),
+            );
+
+        let mut command =
+            RemoveCollectionItemCommand::new("emitters".to_string(), 1, particle_system);
+
+        command.execute(&mut context);
+        assert_eq!(
+            emitter_positions(&context.particle_system),
+            vec![positions[0], positions[2]]
+        );
+
+        command.revert(&mut context);
+        assert_eq!(
+            emitter_positions(&context.particle_system),
+            positions.to_vec()
+        );
+
+        // Redo must remove the same emitter again.
+        command.execute(&mut context);
+        assert_eq!(
+            emitter_positions(&context.particle_system),
+            vec![positions[0], positions[2]]
+        );
+
+        // Removal of an item, that does not exist, must not panic and must keep the collection.
+        let mut command =
+            RemoveCollectionItemCommand::new("emitters".to_string(), 5, particle_system);
+        command.execute(&mut context);
+        command.revert(&mut context);
+        assert_eq!(
+            emitter_positions(&context.particle_system),
+            vec![positions[0], positions[2]]
+        );
+    }
+
+    #[test]
+    fn test_set_property_undo() {
+        let mut context = particle_system_context();
+        let initial_acceleration = context.particle_system.acceleration();
+        let new_acceleration = Vector3::new(1.0, 2.0, 3.0);
+
+        let mut command = SetPropertyCommand::new(
+            "acceleration".to_string(),
+            Box::new(new_acceleration),
+            particle_system,
+        );
+
+        command.execute(&mut context);
+        assert_eq!(context.particle_system.acceleration(), new_acceleration);
+
+        command.revert(&mut context);
+        assert_eq!(context.particle_system.acceleration(), initial_acceleration);
+
+        command.execute(&mut context);
+        assert_eq!(context.particle_system.acceleration(), new_acceleration);
+    }
+
+    #[test]
+    fn test_unknown_property_warning() {
+        let mut context = particle_system_context();
+
+        let mut command = SetPropertyCommand::new(
+            "unknown_parent.unknown_particle_system_property".to_string(),
+            Box::new(123.0f32),
+            particle_system,
+        );
+        let ((), messages) = Log::capture(|| command.execute(&mut context));
+
+        assert!(messages
+            .iter()
+            .any(|message| message.kind == MessageKind::Warning
+                && message.content.contains("unknown_particle_system_property")));
+    }
+}
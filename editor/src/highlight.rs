@@ -207,6 +207,9 @@ impl SceneRenderPass for HighlightRenderPass {
                 storage: &mut render_bundle_storage,
                 graph: &ctx.scene.graph,
                 render_pass_name: &render_pass_name,
+                particle_transparency: Default::default(),
+                particle_budget: None,
+                culling_statistics: Default::default(),
             };
 
             for &root_node_handle in self.nodes_to_highlight.iter() {
@@ -290,6 +293,7 @@ impl SceneRenderPass for HighlightRenderPass {
                                 wvp_matrix: &(view_projection * instance.world_transform),
                                 bone_matrices: &instance.bone_matrices,
                                 use_skeletal_animation: bundle.is_skinned,
+                                instance_matrices: &[],
                                 camera_position: &ctx.camera.global_position(),
                                 camera_up_vector: &camera_up,
                                 camera_side_vector: &camera_side,
@@ -301,6 +305,7 @@ impl SceneRenderPass for HighlightRenderPass {
                                 blend_shapes_weights: &instance.blend_shapes_weights,
                                 normal_dummy: &ctx.normal_dummy,
                                 white_dummy: &ctx.white_dummy,
+                                diffuse_dummy: &ctx.white_dummy,
                                 black_dummy: &ctx.black_dummy,
                                 volume_dummy: &ctx.volume_dummy,
                                 matrix_storage: ctx.matrix_storage,
@@ -2,12 +2,13 @@ use crate::{
     fyrox::{
         asset::{untyped::UntypedResource, Resource},
         core::{
+            math::curve::Curve,
             parking_lot::Mutex,
             pool::{ErasedHandle, Handle},
         },
         gui::{
             self,
-            font::FontResource,
+            font::{FontRenderMode, FontResource},
             inspector::editors::{
                 bit::BitFieldPropertyEditorDefinition,
                 collection::VecCollectionPropertyEditorDefinition,
@@ -20,15 +21,16 @@ use crate::{
         },
         material::{
             shader::{Shader, ShaderResource},
-            MaterialResource,
+            CullMode, MaterialResource,
         },
         renderer::framework::state::PolygonFillMode,
         resource::{
             curve::{CurveResource, CurveResourceState},
             model::{MaterialSearchOptions, Model, ModelResource},
             texture::{
-                CompressionOptions, MipFilter, TextureMagnificationFilter,
-                TextureMinificationFilter, TextureResource, TextureWrapMode,
+                CompressionOptions, MipFilter, MipMapMode, TextureColorSpace,
+                TextureMagnificationFilter, TextureMinificationFilter, TextureResource,
+                TextureWrapMode,
             },
         },
         scene::{
@@ -38,7 +40,7 @@ use crate::{
             },
             camera::{
                 ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection,
-                Projection, SkyBox,
+                Projection, SkyBox, ToneMapping,
             },
             collider::{
                 BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
@@ -50,7 +52,7 @@ use crate::{
             joint::*,
             light::{
                 directional::{CsmOptions, FrustumSplitOptions},
-                BaseLight,
+                BaseLight, LightAttenuation,
             },
             mesh::{
                 surface::{BlendShape, Surface, SurfaceResource},
@@ -59,10 +61,15 @@ use crate::{
             node::Node,
             particle_system::{
                 emitter::{
-                    base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
-                    sphere::SphereEmitter, Emitter,
+                    base::{BaseEmitter, EmissionMode},
+                    cone::ConeEmitter,
+                    cuboid::CuboidEmitter,
+                    cylinder::CylinderEmitter,
+                    sphere::SphereEmitter,
+                    Emitter,
                 },
-                ParticleSystemRng,
+                force_field::ForceFieldKind,
+                ParticleBlendMode, ParticleSystemRng, SimulationSpace,
             },
             ragdoll::Limb,
             rigidbody::RigidBodyType,
@@ -202,6 +209,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(TexturePropertyEditorDefinition { untyped: true });
     container.insert(FontPropertyEditorDefinition);
     container.insert(InheritablePropertyEditorDefinition::<FontResource>::new());
+    container.insert(EnumPropertyEditorDefinition::<FontRenderMode>::new());
     container.insert(InheritablePropertyEditorDefinition::<Option<TextureResource>>::new());
     container.insert(InheritablePropertyEditorDefinition::<Option<UntypedResource>>::new());
     container.register_inheritable_vec_collection::<Option<TextureResource>>();
@@ -348,8 +356,10 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<Biquad>();
     container.register_inheritable_inspectable::<AudioBus>();
     container.register_inheritable_inspectable::<BaseEmitter>();
+    container.insert(EnumPropertyEditorDefinition::<Curve>::new_optional());
     container.register_inheritable_inspectable::<SphereEmitter>();
     container.register_inheritable_inspectable::<CylinderEmitter>();
+    container.register_inheritable_inspectable::<ConeEmitter>();
     container.register_inheritable_inspectable::<CuboidEmitter>();
     container.register_inheritable_inspectable::<PerspectiveProjection>();
     container.register_inheritable_inspectable::<OrthographicProjection>();
@@ -372,6 +382,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<CoefficientCombineRule, _>();
     container.register_inheritable_enum::<CompressionOptions, _>();
     container.register_inheritable_enum::<TextureWrapMode, _>();
+    container.register_inheritable_enum::<TextureColorSpace, _>();
+    container.register_inheritable_enum::<MipMapMode, _>();
     container.register_inheritable_enum::<TextureMagnificationFilter, _>();
     container.register_inheritable_enum::<TextureMinificationFilter, _>();
     container.register_inheritable_enum::<Projection, _>();
@@ -380,6 +392,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<Mobility, _>();
     container.register_inheritable_enum::<RigidBodyType, _>();
     container.register_inheritable_enum::<Exposure, _>();
+    container.register_inheritable_enum::<ToneMapping, _>();
+    container.register_inheritable_enum::<LightAttenuation, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
     container.register_inheritable_enum::<DistanceModel, _>();
@@ -450,7 +464,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     >::new());
 
     container.insert(InspectablePropertyEditorDefinition::<ParticleSystemRng>::new());
+    container.register_inheritable_enum::<ParticleBlendMode, _>();
+    container.register_inheritable_enum::<SimulationSpace, _>();
+    container.register_inheritable_enum::<ForceFieldKind, _>();
+    container.insert(EnumPropertyEditorDefinition::<EmissionMode>::new());
     container.insert(EnumPropertyEditorDefinition::<PolygonFillMode>::new());
+    container.insert(EnumPropertyEditorDefinition::<CullMode>::new());
+    container.insert(EnumPropertyEditorDefinition::<CullMode>::new_optional());
 
     container.insert(EnumPropertyEditorDefinition::<MipFilter>::new());
 
@@ -1280,6 +1280,7 @@ impl Editor {
                     &current_scene_entry.selection,
                     game_scene,
                     engine,
+                    &self.message_sender,
                 );
                 self.camera_control_panel.handle_ui_message(
                     message,
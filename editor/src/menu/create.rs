@@ -30,6 +30,7 @@ use crate::{
             node::Node,
             particle_system::{
                 emitter::{base::BaseEmitterBuilder, sphere::SphereEmitterBuilder},
+                force_field::ForceFieldBuilder,
                 ParticleSystemBuilder,
             },
             pivot::PivotBuilder,
@@ -129,6 +130,7 @@ pub struct CreateEntityMenu {
     create_camera: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
     create_particle_system: Handle<UiNode>,
+    create_force_field: Handle<UiNode>,
     create_listener: Handle<UiNode>,
     create_sound_source: Handle<UiNode>,
     physics_menu: PhysicsMenu,
@@ -163,6 +165,7 @@ impl CreateEntityMenu {
         let create_decal;
         let create_navmesh;
         let create_particle_system;
+        let create_force_field;
         let create_terrain;
         let create_pivot;
         let create_sound_source;
@@ -267,6 +270,10 @@ impl CreateEntityMenu {
                 create_particle_system = create_menu_item("Particle System", vec![], ctx);
                 create_particle_system
             },
+            {
+                create_force_field = create_menu_item("Force Field", vec![], ctx);
+                create_force_field
+            },
             {
                 create_terrain = create_menu_item("Terrain", vec![], ctx);
                 create_terrain
@@ -294,6 +301,7 @@ impl CreateEntityMenu {
                 create_camera,
                 create_sprite,
                 create_particle_system,
+                create_force_field,
                 create_pivot,
                 create_terrain,
                 create_sound_source,
@@ -328,6 +336,7 @@ impl CreateEntityMenu {
             self.create_camera,
             self.create_sprite,
             self.create_particle_system,
+            self.create_force_field,
             self.create_pivot,
             self.create_terrain,
             self.sound_menu,
@@ -492,6 +501,11 @@ impl CreateEntityMenu {
                             .build()])
                             .build_node(),
                         )
+                    } else if message.destination() == self.create_force_field {
+                        Some(
+                            ForceFieldBuilder::new(BaseBuilder::new().with_name("ForceField"))
+                                .build_node(),
+                        )
                     } else if message.destination() == self.create_terrain {
                         Some(
                             TerrainBuilder::new(BaseBuilder::new().with_name("Terrain"))
@@ -1,7 +1,8 @@
-use crate::fyrox::graph::SceneGraph;
+use crate::command::{Command, CommandGroup, SetPropertyCommand};
+use crate::fyrox::graph::{BaseSceneGraph, SceneGraph};
 use crate::fyrox::gui::HorizontalAlignment;
 use crate::fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector3, pool::Handle, reflect::Reflect},
     engine::Engine,
     gui::{
         button::{ButtonBuilder, ButtonMessage},
@@ -10,6 +11,7 @@ use crate::fyrox::{
         message::{MessageDirection, UiMessage},
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         text::TextBuilder,
+        vec::{Vec3EditorBuilder, Vec3EditorMessage},
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Thickness, UiNode, VerticalAlignment,
@@ -17,10 +19,14 @@ use crate::fyrox::{
     scene::{node::Node, particle_system::ParticleSystem},
 };
 use crate::{
-    scene::{GameScene, Selection},
+    message::MessageSender,
+    scene::{commands::GameSceneContext, GameScene, Selection},
     send_sync_message, Message, FIXED_TIMESTEP,
 };
 
+/// Standard gravity of Earth (m/s²).
+const EARTH_GRAVITY: f32 = 9.81;
+
 pub struct ParticleSystemPreviewControlPanel {
     pub window: Handle<UiNode>,
     preview: Handle<UiNode>,
@@ -30,6 +36,11 @@ pub struct ParticleSystemPreviewControlPanel {
     rewind: Handle<UiNode>,
     time: Handle<UiNode>,
     set_time: Handle<UiNode>,
+    zero_gravity: Handle<UiNode>,
+    earth_gravity: Handle<UiNode>,
+    custom_gravity: Handle<UiNode>,
+    custom_acceleration: Handle<UiNode>,
+    custom_acceleration_value: Vector3<f32>,
     particle_systems_state: Vec<(Handle<Node>, Node)>,
     desired_playback_time: f32,
     scene_viewer_frame: Handle<UiNode>,
@@ -118,66 +129,136 @@ impl ParticleSystemPreviewControlPanel {
         .add_column(Column::stretch())
         .build(ctx);
 
+        let zero_gravity;
+        let earth_gravity;
+        let custom_gravity;
+        let custom_acceleration;
+        let gravity_presets = GridBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .on_column(0)
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Gravity")
+                    .build(ctx),
+                )
+                .with_child({
+                    zero_gravity = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(1)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Zero")
+                    .build(ctx);
+                    zero_gravity
+                })
+                .with_child({
+                    earth_gravity = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(2)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Earth Down")
+                    .build(ctx);
+                    earth_gravity
+                })
+                .with_child({
+                    custom_acceleration = Vec3EditorBuilder::<f32>::new(
+                        WidgetBuilder::new()
+                            .on_column(3)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .build(ctx);
+                    custom_acceleration
+                })
+                .with_child({
+                    custom_gravity = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(4)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Custom")
+                    .build(ctx);
+                    custom_gravity
+                }),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::auto())
+        .add_column(Column::auto())
+        .add_column(Column::auto())
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .build(ctx);
+
         let time;
         let set_time;
         let window = WindowBuilder::new(
             WidgetBuilder::new()
                 .with_name("ParticleSystemPanel")
-                .with_width(300.0)
-                .with_height(70.0),
+                .with_width(360.0)
+                .with_height(95.0),
         )
         .open(false)
         .with_title(WindowTitle::text("Particle System"))
         .with_content(
             GridBuilder::new(
-                WidgetBuilder::new().with_child(grid).with_child(
-                    GridBuilder::new(
-                        WidgetBuilder::new()
-                            .on_row(1)
-                            .on_column(0)
-                            .with_child(
-                                TextBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(0)
-                                        .with_vertical_alignment(VerticalAlignment::Center)
-                                        .with_margin(Thickness::uniform(1.0)),
-                                )
-                                .with_text("Playback Time")
-                                .build(ctx),
-                            )
-                            .with_child({
-                                time = NumericUpDownBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(1)
-                                        .with_margin(Thickness::uniform(1.0)),
-                                )
-                                .with_min_value(0.0f32)
-                                .with_max_value(10.0 * 60.0) // 10 Minutes
-                                .with_value(0.0f32)
-                                .build(ctx);
-                                time
-                            })
-                            .with_child({
-                                set_time = ButtonBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(2)
-                                        .with_width(33.0)
-                                        .with_margin(Thickness::uniform(1.0)),
+                WidgetBuilder::new()
+                    .with_child(grid)
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .on_column(0)
+                                .with_child(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(0)
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Playback Time")
+                                    .build(ctx),
                                 )
-                                .with_text("Set")
-                                .build(ctx);
-                                set_time
-                            }),
+                                .with_child({
+                                    time = NumericUpDownBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_min_value(0.0f32)
+                                    .with_max_value(10.0 * 60.0) // 10 Minutes
+                                    .with_value(0.0f32)
+                                    .build(ctx);
+                                    time
+                                })
+                                .with_child({
+                                    set_time = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(2)
+                                            .with_width(33.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Set")
+                                    .build(ctx);
+                                    set_time
+                                }),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::auto())
+                        .add_column(Column::stretch())
+                        .add_column(Column::auto())
+                        .build(ctx),
                     )
-                    .add_row(Row::stretch())
-                    .add_column(Column::auto())
-                    .add_column(Column::stretch())
-                    .add_column(Column::auto())
-                    .build(ctx),
-                ),
+                    .with_child(gravity_presets),
             )
             .add_row(Row::stretch())
             .add_row(Row::stretch())
+            .add_row(Row::stretch())
             .add_column(Column::stretch())
             .build(ctx),
         )
@@ -193,6 +274,11 @@ impl ParticleSystemPreviewControlPanel {
             preview,
             particle_systems_state: Default::default(),
             set_time,
+            zero_gravity,
+            earth_gravity,
+            custom_gravity,
+            custom_acceleration,
+            custom_acceleration_value: Default::default(),
             desired_playback_time: 0.0,
             scene_viewer_frame,
         }
@@ -215,11 +301,21 @@ impl ParticleSystemPreviewControlPanel {
         if let Message::SelectionChanged { .. } = message {
             let scene = &engine.scenes[game_scene.scene];
             if let Some(selection) = editor_selection.as_graph() {
-                let any_particle_system_selected = selection
+                let first_particle_system = selection
                     .nodes
                     .iter()
-                    .any(|n| scene.graph.try_get_of_type::<ParticleSystem>(*n).is_some());
-                if any_particle_system_selected {
+                    .find_map(|n| scene.graph.try_get_of_type::<ParticleSystem>(*n));
+                if let Some(particle_system) = first_particle_system {
+                    // Start custom acceleration from the current one, so it could be tweaked.
+                    self.custom_acceleration_value = particle_system.acceleration();
+                    engine
+                        .user_interfaces
+                        .first_mut()
+                        .send_message(Vec3EditorMessage::value(
+                            self.custom_acceleration,
+                            MessageDirection::ToWidget,
+                            self.custom_acceleration_value,
+                        ));
                     engine
                         .user_interfaces
                         .first_mut()
@@ -294,15 +390,67 @@ impl ParticleSystemPreviewControlPanel {
         !self.particle_systems_state.is_empty()
     }
 
+    fn set_acceleration(
+        &self,
+        acceleration: Vector3<f32>,
+        nodes: &[Handle<Node>],
+        game_scene: &GameScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        let scene = &engine.scenes[game_scene.scene];
+
+        let commands = nodes
+            .iter()
+            .filter(|n| scene.graph.try_get_of_type::<ParticleSystem>(**n).is_some())
+            .map(|&node| {
+                Command::new(SetPropertyCommand::new(
+                    "acceleration".into(),
+                    Box::new(acceleration) as Box<dyn Reflect>,
+                    move |ctx| ctx.get_mut::<GameSceneContext>().scene.graph.node_mut(node),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if !commands.is_empty() {
+            sender.do_command(CommandGroup::from(commands));
+        }
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         editor_selection: &Selection,
         game_scene: &mut GameScene,
         engine: &mut Engine,
+        sender: &MessageSender,
     ) {
         if let Some(selection) = editor_selection.as_graph() {
             if let Some(ButtonMessage::Click) = message.data() {
+                let preset = if message.destination() == self.zero_gravity {
+                    Some(Vector3::default())
+                } else if message.destination() == self.earth_gravity {
+                    Some(Vector3::new(0.0, -EARTH_GRAVITY, 0.0))
+                } else if message.destination() == self.custom_gravity {
+                    Some(self.custom_acceleration_value)
+                } else {
+                    None
+                };
+
+                if let Some(acceleration) = preset {
+                    // Preview works on a snapshot of the particle systems, that is restored when
+                    // preview ends, so leave it first, otherwise the new acceleration will be lost.
+                    self.leave_preview_mode(game_scene, engine);
+                    self.set_acceleration(
+                        acceleration,
+                        &selection.nodes,
+                        game_scene,
+                        engine,
+                        sender,
+                    );
+                    return;
+                }
+
                 let scene = &mut engine.scenes[game_scene.scene];
 
                 for &node in &selection.nodes {
@@ -340,6 +488,12 @@ impl ParticleSystemPreviewControlPanel {
                 {
                     self.desired_playback_time = *desired_playback_time;
                 }
+            } else if let Some(Vec3EditorMessage::<f32>::Value(value)) = message.data() {
+                if message.destination() == self.custom_acceleration
+                    && message.direction() == MessageDirection::FromWidget
+                {
+                    self.custom_acceleration_value = *value;
+                }
             }
         }
     }
@@ -19,6 +19,7 @@ use crate::{
             math::{aabb::AxisAlignedBoundingBox, plane::Plane, Rect},
             pool::{ErasedHandle, Handle},
             reflect::Reflect,
+            sstorage::ImmutableString,
             visitor::Visitor,
         },
         engine::{Engine, SerializationContext},
@@ -31,6 +32,7 @@ use crate::{
         },
         material::{
             shader::ShaderResource, shader::ShaderResourceExtension, Material, MaterialResource,
+            PropertyValue,
         },
         resource::{
             model::{Model, ModelResourceExtension},
@@ -72,7 +74,7 @@ use crate::{
         controller::SceneController,
         selector::HierarchyNode,
     },
-    settings::{keys::KeyBindings, SettingsMessage},
+    settings::{graphics::GridSettings, keys::KeyBindings, SettingsMessage},
     ui_scene::selection::UiSelection,
     world::graph::selection::GraphSelection,
     Message, Settings,
@@ -138,11 +140,31 @@ lazy_static! {
     };
 }
 
-fn make_grid_material() -> MaterialResource {
-    let material = Material::from_shader(GRID_SHADER.clone(), None);
+fn make_grid_material(settings: &GridSettings) -> MaterialResource {
+    let mut material = Material::from_shader(GRID_SHADER.clone(), None);
+    apply_grid_settings(&mut material, settings);
     MaterialResource::new_ok(Default::default(), material)
 }
 
+fn apply_grid_settings(material: &mut Material, settings: &GridSettings) {
+    for (name, value) in [
+        ("diffuseColor", PropertyValue::Color(settings.color)),
+        (
+            "majorLineColor",
+            PropertyValue::Color(settings.major_line_color),
+        ),
+        ("cellSize", PropertyValue::Float(settings.cell_size)),
+        // Settings file could be edited by hand, make sure that there is at least one cell between major lines.
+        (
+            "majorLineEvery",
+            PropertyValue::Int(settings.major_line_every.max(1) as i32),
+        ),
+        ("fadeDistance", PropertyValue::Float(settings.fade_distance)),
+    ] {
+        Log::verify(material.set_property(&ImmutableString::new(name), value));
+    }
+}
+
 impl GameScene {
     pub fn from_native_scene(
         mut scene: Scene,
@@ -171,7 +193,7 @@ impl GameScene {
             ResourceKind::Embedded,
             SurfaceData::make_quad(&Matrix4::new_scaling(2.0)),
         ))
-        .with_material(make_grid_material())
+        .with_material(make_grid_material(&settings.graphics.grid))
         .build()])
         .with_render_path(RenderPath::Forward)
         .build(&mut scene.graph);
@@ -862,7 +884,16 @@ impl SceneController for GameScene {
         for message in self.settings_receiver.try_iter() {
             match message {
                 SettingsMessage::Changed => {
-                    scene.graph[self.grid].set_visibility(settings.graphics.draw_grid);
+                    let grid = &mut scene.graph[self.grid];
+                    grid.set_visibility(settings.graphics.draw_grid);
+                    if let Some(grid) = grid.cast::<Mesh>() {
+                        for surface in grid.surfaces() {
+                            apply_grid_settings(
+                                &mut surface.material().data_ref(),
+                                &settings.graphics.grid,
+                            );
+                        }
+                    }
                 }
             }
         }
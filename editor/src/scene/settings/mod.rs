@@ -4,7 +4,9 @@ use crate::fyrox::{
     gui::{
         inspector::{
             editors::{
-                enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
             },
             InspectorBuilder, InspectorContext, InspectorMessage, PropertyFilter,
         },
@@ -21,7 +23,7 @@ use crate::fyrox::{
             physics::{IntegrationParameters, PhysicsWorld},
             Graph, NodePool,
         },
-        SceneRenderingOptions,
+        FogMode, FogSettings, SceneRenderingOptions,
     },
     utils::lightmap::Lightmap,
 };
@@ -66,6 +68,9 @@ impl SceneSettingsWindow {
         container.register_inheritable_inspectable::<dim2::physics::PhysicsWorld>();
         container.register_inheritable_inspectable::<SceneRenderingOptions>();
         container.insert(EnumPropertyEditorDefinition::<Color>::new_optional());
+        container.insert(EnumPropertyEditorDefinition::<FogSettings>::new_optional());
+        container.insert(InspectablePropertyEditorDefinition::<FogSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<FogMode>::new());
 
         Self {
             window,
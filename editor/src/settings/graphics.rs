@@ -1,4 +1,7 @@
-use crate::fyrox::{core::reflect::prelude::*, renderer::QualitySettings};
+use crate::fyrox::{
+    core::{color::Color, reflect::prelude::*},
+    renderer::QualitySettings,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
@@ -8,6 +11,8 @@ pub struct GraphicsSettings {
     pub z_far: f32,
     #[serde(default = "default_draw_grid")]
     pub draw_grid: bool,
+    #[serde(default)]
+    pub grid: GridSettings,
 }
 
 fn default_draw_grid() -> bool {
@@ -21,6 +26,34 @@ impl Default for GraphicsSettings {
             z_near: 0.025,
             z_far: 128.0,
             draw_grid: default_draw_grid(),
+            grid: Default::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
+pub struct GridSettings {
+    /// Distance between two adjacent lines of the grid (in world units).
+    #[reflect(min_value = 0.01, step = 0.1)]
+    pub cell_size: f32,
+    /// Every N-th line of the grid is drawn thicker.
+    #[reflect(min_value = 1.0)]
+    pub major_line_every: u32,
+    pub color: Color,
+    pub major_line_color: Color,
+    /// Distance from the camera at which the grid fades out completely.
+    #[reflect(min_value = 0.0, step = 1.0)]
+    pub fade_distance: f32,
+}
+
+impl Default for GridSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 1.0,
+            major_line_every: 10,
+            color: Color::opaque(40, 40, 40),
+            major_line_color: Color::opaque(60, 60, 60),
+            fade_distance: 100.0,
         }
     }
 }
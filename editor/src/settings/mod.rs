@@ -21,7 +21,7 @@ use crate::{
             window::{WindowBuilder, WindowMessage, WindowTitle},
             HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
         },
-        renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
+        renderer::{BloomSettings, CsmSettings, QualitySettings, ShadowMapPrecision},
     },
     inspector::editors::make_property_editors_container,
     message::MessageSender,
@@ -30,7 +30,7 @@ use crate::{
         camera::CameraSettings,
         debugging::DebuggingSettings,
         general::{GeneralSettings, ScriptEditor},
-        graphics::GraphicsSettings,
+        graphics::{GraphicsSettings, GridSettings},
         keys::{KeyBindings, TerrainKeyBindings},
         model::ModelSettings,
         move_mode::MoveInteractionModeSettings,
@@ -208,11 +208,13 @@ impl SettingsData {
 
         container.insert(InspectablePropertyEditorDefinition::<GeneralSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<GridSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
         container.insert(EnumPropertyEditorDefinition::<ScriptEditor>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<BloomSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CameraSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<
@@ -6,9 +6,23 @@ use crate::{
 };
 use bytemuck::{Pod, Zeroable};
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
-#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Visit, Reflect, Pod, Zeroable)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    Pod,
+    Zeroable,
+    Serialize,
+    Deserialize,
+)]
 #[repr(C)]
 pub struct Color {
     // Do not change order! OpenGL requires this order!
@@ -661,8 +675,10 @@ impl Color {
         }
     }
 
+    /// Linearly interpolates between `self` and `other`. `t` is clamped to `[0; 1]` range.
     #[inline]
     pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
         let dr = (t * (i32::from(other.r) - i32::from(self.r)) as f32) as i32;
         let dg = (t * (i32::from(other.g) - i32::from(self.g)) as f32) as i32;
         let db = (t * (i32::from(other.b) - i32::from(self.b)) as f32) as i32;
@@ -681,6 +697,19 @@ impl Color {
         }
     }
 
+    /// Creates opaque color from hue (`[0; 360]` range), saturation (`[0; 100]` range) and
+    /// brightness (`[0; 100]` range). See [`Hsv`] for more info.
+    #[inline]
+    pub fn from_hsv(hue: f32, saturation: f32, brightness: f32) -> Self {
+        Self::from(Hsv::new(hue, saturation, brightness))
+    }
+
+    /// Converts the color to HSV representation. Alpha channel is ignored.
+    #[inline]
+    pub fn to_hsv(self) -> Hsv {
+        Hsv::from(self)
+    }
+
     #[inline]
     pub fn with_new_alpha(self, a: u8) -> Self {
         Self {
@@ -1013,6 +1042,22 @@ mod test {
         assert_eq!(color, Color::opaque(127, 127, 127));
     }
 
+    #[test]
+    fn test_color_lerp_clamped() {
+        assert_eq!(Color::BLACK.lerp(Color::WHITE, -1.0), Color::BLACK);
+        assert_eq!(Color::BLACK.lerp(Color::WHITE, 2.0), Color::WHITE);
+    }
+
+    #[test]
+    fn test_color_hsv_helpers() {
+        assert_eq!(Color::from_hsv(120.0, 100.0, 100.0), Color::GREEN);
+        assert_eq!(Color::BLUE.to_hsv(), Hsv::new(240.0, 100.0, 100.0));
+        assert_eq!(
+            Color::from_hsv(300.0, 100.0, 100.0).to_hsv(),
+            Hsv::new(300.0, 100.0, 100.0)
+        );
+    }
+
     #[test]
     fn test_color_with_new_alpha() {
         let color = Color::BLACK;
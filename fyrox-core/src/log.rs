@@ -2,7 +2,10 @@
 
 use crate::lazy_static::lazy_static;
 use crate::parking_lot::Mutex;
-use std::fmt::{Debug, Display};
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display},
+};
 
 use crate::instant::Instant;
 #[cfg(not(target_arch = "wasm32"))]
@@ -43,6 +46,11 @@ lazy_static! {
     });
 }
 
+thread_local! {
+    // Messages captured by `Log::capture` on the current thread, if any.
+    static CAPTURED: RefCell<Option<Vec<LogMessage>>> = const { RefCell::new(None) };
+}
+
 /// A kind of message.
 #[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
 #[repr(u32)]
@@ -80,6 +88,18 @@ impl Log {
         S: AsRef<str>,
     {
         let mut msg = message.as_ref().to_owned();
+
+        // Captures receive messages regardless of the verbosity.
+        CAPTURED.with(|captured| {
+            if let Some(captured) = captured.borrow_mut().as_mut() {
+                captured.push(LogMessage {
+                    kind,
+                    content: msg.clone(),
+                    time: Instant::now() - self.time_origin,
+                });
+            }
+        });
+
         if kind as u32 >= self.verbosity as u32 {
             // Notify listeners about the message and remove all disconnected listeners.
             self.listeners.retain(|listener| {
@@ -171,6 +191,17 @@ impl Log {
         LOG.lock().listeners.push(listener)
     }
 
+    /// Calls the given function and returns its result together with every message, that was
+    /// written in the log by the function. Unlike listeners, only the messages written on the
+    /// current thread are captured (regardless of the verbosity), so it is suitable for tests, that
+    /// run in parallel. Captures could be nested, the innermost one receives the messages.
+    pub fn capture<R>(func: impl FnOnce() -> R) -> (R, Vec<LogMessage>) {
+        let outer = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+        let result = func();
+        let captured = CAPTURED.with(|captured| captured.replace(outer));
+        (result, captured.unwrap_or_default())
+    }
+
     /// Allows you to verify that the result of operation is Ok, or print the error in the log.
     ///
     /// # Use cases
@@ -12,6 +12,7 @@ use crate::{
     math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, plane::Plane},
     num_traits::NumAssign,
     reflect::prelude::*,
+    uuid_provider,
     visitor::prelude::*,
 };
 use fyrox_core_derive::{impl_reflect, impl_visit};
@@ -155,3 +156,5 @@ impl_visit!(
         pub keys: Vec<CurveKey>,
     }
 );
+
+uuid_provider!(Curve = "17ef1f10-52c5-432e-8e36-62a02bfeed51");
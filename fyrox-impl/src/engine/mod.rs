@@ -34,7 +34,10 @@ use crate::{
         Material,
     },
     plugin::{Plugin, PluginContext, PluginRegistrationContext},
-    renderer::{framework::error::FrameworkError, framework::state::GlKind, Renderer},
+    renderer::{
+        framework::error::FrameworkError, framework::state::GlKind, HeadlessRenderer, Renderer,
+        Statistics,
+    },
     resource::{
         curve::{loader::CurveLoader, CurveResourceState},
         model::{loader::ModelLoader, Model, ModelResource},
@@ -64,7 +67,7 @@ use fyrox_sound::{
 };
 #[cfg(not(target_arch = "wasm32"))]
 use glutin::{
-    config::ConfigTemplateBuilder,
+    config::{ConfigTemplateBuilder, GlConfig},
     context::{
         ContextApi, ContextAttributesBuilder, GlProfile, NotCurrentGlContext,
         PossiblyCurrentContext, Version,
@@ -178,6 +181,33 @@ pub struct InitializedGraphicsContext {
     gl_surface: Surface<WindowSurface>,
 }
 
+impl InitializedGraphicsContext {
+    /// Enables or disables vertical synchronization. The new swap interval takes effect starting
+    /// from the next frame. Keep in mind, that some drivers could ignore this setting. On WebAssembly
+    /// synchronization is controlled by the browser, and the method only remembers the new value.
+    pub fn set_vsync(&mut self, enabled: bool) -> Result<(), FrameworkError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let interval = if enabled {
+                SwapInterval::Wait(NonZeroU32::new(1).unwrap())
+            } else {
+                SwapInterval::DontWait
+            };
+            self.gl_surface
+                .set_swap_interval(&self.gl_context, interval)?;
+        }
+
+        self.params.vsync = enabled;
+
+        Ok(())
+    }
+
+    /// Returns `true` if vertical synchronization is enabled, `false` - otherwise.
+    pub fn vsync(&self) -> bool {
+        self.params.vsync
+    }
+}
+
 /// Graphics context of the engine, it could be in two main states:
 ///
 /// - [`GraphicsContext::Initialized`] - active graphics context, that is fully initialized and ready for use.
@@ -435,6 +465,9 @@ pub struct Engine {
 
     performance_statistics: PerformanceStatistics,
 
+    // Renderer stub, that is used when there is no graphics context.
+    headless_renderer: HeadlessRenderer,
+
     model_events_receiver: Receiver<ResourceEvent>,
 
     #[allow(dead_code)] // Keep engine instance alive.
@@ -1011,6 +1044,8 @@ pub struct GraphicsContextParams {
 
     /// Amount of samples for MSAA. Must be a power of two (1, 2, 4, 8). `None` means disabled.
     /// MSAA works only for forward rendering and does not work for deferred rendering.
+    /// If the requested amount of samples is not supported, MSAA will be disabled and FXAA will be
+    /// used instead.
     pub msaa_sample_count: Option<u8>,
 }
 
@@ -1041,6 +1076,58 @@ pub struct EngineInitParams {
     pub task_pool: Arc<TaskPool>,
 }
 
+/// Returns refresh rate (in Hz) of the monitor, that shows the window.
+fn monitor_refresh_rate(window: &Window) -> Option<f32> {
+    window
+        .current_monitor()?
+        .refresh_rate_millihertz()
+        .map(|millihertz| millihertz as f32 / 1000.0)
+}
+
+/// Selects a config with exactly the requested amount of MSAA samples. If there's no such config,
+/// a config without multisampling will be used instead and a warning will be written to the log. The
+/// caller is responsible for enabling FXAA in this case (see [`msaa_fallback_required`]).
+#[cfg(not(target_arch = "wasm32"))]
+fn select_gl_config(
+    configs: Box<dyn Iterator<Item = glutin::config::Config> + '_>,
+    msaa_sample_count: Option<u8>,
+) -> glutin::config::Config {
+    let requested = msaa_sample_count.unwrap_or(0);
+
+    let mut best: Option<glutin::config::Config> = None;
+    for config in configs {
+        let samples = config.num_samples();
+        if samples == requested {
+            return config;
+        }
+
+        // Prefer a config with the least amount of samples, ideally without multisampling at all.
+        if best
+            .as_ref()
+            .map_or(true, |best| samples < best.num_samples())
+        {
+            best = Some(config);
+        }
+    }
+
+    let config = best.expect("There must be at least one suitable config!");
+
+    if msaa_sample_count.is_some() {
+        Log::warn(format!(
+            "Requested MSAA sample count {requested} is not supported, falling back to FXAA."
+        ));
+    }
+
+    config
+}
+
+/// Returns `true` if the selected config does not provide the requested amount of MSAA samples and
+/// FXAA must be used instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn msaa_fallback_required(config: &glutin::config::Config, msaa_sample_count: Option<u8>) -> bool {
+    msaa_sample_count.map_or(false, |requested| config.num_samples() != requested)
+}
+
 fn process_node_script<T, C>(index: usize, context: &mut C, func: &mut T) -> bool
 where
     T: FnMut(&mut Script, &mut C),
@@ -1362,6 +1449,7 @@ impl Engine {
             sound_engine,
             user_interfaces,
             performance_statistics: Default::default(),
+            headless_renderer: Default::default(),
             plugins: Default::default(),
             serialization_context,
             widget_constructors,
@@ -1419,23 +1507,29 @@ impl Engine {
                 .with_window_level(params.window_attributes.window_level)
                 .with_active(params.window_attributes.active);
 
+            // Set to `true` if the requested MSAA sample count is not supported.
+            #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+            let mut fxaa_fallback = false;
+
             #[cfg(not(target_arch = "wasm32"))]
             let (window, gl_context, gl_surface, glow_context, gl_kind) = {
-                let mut template = ConfigTemplateBuilder::new()
+                // Multisampling is not requested in the template, because it would filter out every
+                // config if the requested sample count is not supported. Instead, the most suitable
+                // config is selected manually.
+                let template = ConfigTemplateBuilder::new()
                     .prefer_hardware_accelerated(Some(true))
                     .with_stencil_size(8)
                     .with_depth_size(24);
 
-                if let Some(sample_count) = params.msaa_sample_count {
-                    template = template.with_multisampling(sample_count);
-                }
-
+                let msaa_sample_count = params.msaa_sample_count;
                 let (opt_window, gl_config) = DisplayBuilder::new()
                     .with_window_builder(Some(window_builder))
-                    .build(window_target, template, |mut configs| {
-                        configs.next().unwrap()
+                    .build(window_target, template, |configs| {
+                        select_gl_config(configs, msaa_sample_count)
                     })?;
 
+                fxaa_fallback = msaa_fallback_required(&gl_config, msaa_sample_count);
+
                 let window = opt_window.unwrap();
 
                 let raw_window_handle = window.raw_window_handle();
@@ -1599,12 +1693,21 @@ impl Engine {
                 gl_context,
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_surface,
-                renderer: Renderer::new(
-                    glow_context,
-                    (window.inner_size().width, window.inner_size().height),
-                    &self.resource_manager,
-                    gl_kind,
-                )?,
+                renderer: {
+                    let mut renderer = Renderer::new(
+                        glow_context,
+                        (window.inner_size().width, window.inner_size().height),
+                        &self.resource_manager,
+                        gl_kind,
+                    )?;
+                    renderer.set_monitor_refresh_rate(monitor_refresh_rate(&window));
+                    if fxaa_fallback {
+                        let mut quality_settings = renderer.get_quality_settings();
+                        quality_settings.fxaa = true;
+                        renderer.set_quality_settings(&quality_settings)?;
+                    }
+                    renderer
+                },
                 window,
                 params: params.clone(),
             });
@@ -1673,6 +1776,9 @@ impl Engine {
     pub fn set_frame_size(&mut self, new_size: (u32, u32)) -> Result<(), FrameworkError> {
         if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             ctx.renderer.set_frame_size(new_size)?;
+            // The window could be moved to another monitor.
+            ctx.renderer
+                .set_monitor_refresh_rate(monitor_refresh_rate(&ctx.window));
 
             #[cfg(not(target_arch = "wasm32"))]
             ctx.gl_surface.resize(
@@ -1680,6 +1786,8 @@ impl Engine {
                 NonZeroU32::new(new_size.0).unwrap_or_else(|| NonZeroU32::new(1).unwrap()),
                 NonZeroU32::new(new_size.1).unwrap_or_else(|| NonZeroU32::new(1).unwrap()),
             );
+        } else {
+            self.headless_renderer.set_frame_size(new_size)?;
         }
 
         Ok(())
@@ -1948,6 +2056,7 @@ impl Engine {
             ctx.renderer.update_caches(dt);
             window_size
         } else {
+            self.headless_renderer.update_caches(dt);
             Vector2::new(1.0, 1.0)
         };
 
@@ -2378,7 +2487,9 @@ impl Engine {
     }
 
     /// Performs rendering of single frame, must be called from your game loop, otherwise you won't
-    /// see anything.
+    /// see anything. If there is no graphics context (for example, on a game server or on a CI
+    /// machine without a GPU), the method does not render anything, but still updates frame
+    /// timings (see [`Self::render_statistics`]), which are based on time deltas of the updates.
     #[inline]
     pub fn render(&mut self) -> Result<(), FrameworkError> {
         for ui in self.user_interfaces.iter_mut() {
@@ -2407,11 +2518,22 @@ impl Engine {
                         .map(|ui| ui.get_drawing_context()),
                 )?;
             }
+        } else {
+            self.headless_renderer.render()?;
         }
 
         Ok(())
     }
 
+    /// Returns statistics of the last rendered frame. If there is no graphics context, only frame
+    /// timings of the statistics are valid.
+    pub fn render_statistics(&self) -> Statistics {
+        match self.graphics_context {
+            GraphicsContext::Initialized(ref ctx) => ctx.renderer.get_statistics(),
+            GraphicsContext::Uninitialized(_) => self.headless_renderer.get_statistics(),
+        }
+    }
+
     /// Enables or disables registered plugins.
     pub(crate) fn enable_plugins(
         &mut self,
@@ -2929,7 +3051,10 @@ mod test {
             pool::Handle, reflect::prelude::*, task::TaskPool, type_traits::prelude::*,
             visitor::prelude::*,
         },
-        engine::{task::TaskPoolHandler, GraphicsContext, ScriptProcessor},
+        engine::{
+            task::TaskPoolHandler, Engine, EngineInitParams, GraphicsContext, ScriptProcessor,
+            SerializationContext,
+        },
         graph::BaseSceneGraph,
         scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
         script::{
@@ -3640,4 +3765,43 @@ mod test {
             }
         }
     }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))] // Uses the same event loop hack as `test_async_script_tasks`.
+    fn test_headless_render() {
+        use std::mem::{ManuallyDrop, MaybeUninit};
+        use winit::event_loop::EventLoop;
+        // See `test_async_script_tasks` for the explanation.
+        #[allow(invalid_value)]
+        #[allow(clippy::uninit_assumed_init)]
+        let event_loop =
+            unsafe { ManuallyDrop::new(MaybeUninit::<EventLoop<()>>::uninit().assume_init()) };
+
+        let task_pool = Arc::new(TaskPool::new());
+        let mut engine = Engine::new(EngineInitParams {
+            graphics_context_params: Default::default(),
+            serialization_context: Arc::new(SerializationContext::new()),
+            widget_constructors: Arc::new(Default::default()),
+            resource_manager: ResourceManager::new(task_pool.clone()),
+            task_pool,
+        })
+        .unwrap();
+
+        assert!(matches!(
+            engine.graphics_context,
+            GraphicsContext::Uninitialized(_)
+        ));
+
+        engine.set_frame_size((640, 480)).unwrap();
+
+        let dt = 1.0 / 60.0;
+        engine.update(dt, &event_loop, &mut 0.0, Default::default());
+        engine.update(dt, &event_loop, &mut 0.0, Default::default());
+        engine.render().unwrap();
+        assert_eq!(engine.render_statistics().frame_delta, 2.0 * dt);
+
+        engine.update(dt, &event_loop, &mut 0.0, Default::default());
+        engine.render().unwrap();
+        assert_eq!(engine.render_statistics().frame_delta, dt);
+    }
 }
@@ -15,10 +15,12 @@ use crate::{
         reflect::prelude::*,
         sstorage::ImmutableString,
         uuid::{uuid, Uuid},
+        uuid_provider,
         visitor::{prelude::*, RegionGuard},
         TypeUuidProvider,
     },
     material::shader::{PropertyKind, SamplerFallback, ShaderResource, ShaderResourceExtension},
+    renderer::framework::framebuffer::{CullFace, DrawParameters},
     resource::texture::{Texture, TextureResource},
 };
 use fxhash::FxHashMap;
@@ -28,11 +30,13 @@ use lazy_static::lazy_static;
 use std::error::Error;
 use std::{
     any::Any,
+    borrow::Cow,
     fmt::{Display, Formatter},
     ops::Deref,
     path::Path,
     sync::Arc,
 };
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub mod loader;
 pub mod shader;
@@ -400,6 +404,46 @@ impl Default for PropertyValue {
 pub struct Material {
     shader: ShaderResource,
     properties: FxHashMap<ImmutableString, PropertyValue>,
+    cull_mode: Option<CullMode>,
+}
+
+/// Defines which faces of a surface with a material will be culled (not rendered).
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum CullMode {
+    /// Back faces are culled. This is the most common mode for closed meshes.
+    #[default]
+    Back,
+    /// Front faces are culled.
+    Front,
+    /// Nothing is culled, both sides of faces are rendered. Useful for plants, cloth, etc. The
+    /// standard shaders flip normals of back faces, so both sides are lit correctly.
+    None,
+}
+
+uuid_provider!(CullMode = "fb160fab-c410-444b-920d-22def803b274");
+
+impl CullMode {
+    /// Returns a face, that should be culled in this mode, if any.
+    pub fn cull_face(self) -> Option<CullFace> {
+        match self {
+            CullMode::Back => Some(CullFace::Back),
+            CullMode::Front => Some(CullFace::Front),
+            CullMode::None => None,
+        }
+    }
 }
 
 impl Visit for Material {
@@ -418,6 +462,7 @@ impl Visit for Material {
         shader.visit("Shader", &mut region)?;
         self.shader = shader;
         self.properties.visit("Properties", &mut region)?;
+        let _ = self.cull_mode.visit("CullMode", &mut region); // Backward compatibility.
 
         Ok(())
     }
@@ -679,6 +724,7 @@ impl Material {
         Self {
             shader,
             properties: property_values,
+            cull_mode: None,
         }
     }
 
@@ -695,6 +741,7 @@ impl Material {
         let mut material = Material {
             shader: Default::default(),
             properties: Default::default(),
+            cull_mode: None,
         };
         let mut visitor = Visitor::load_from_memory(&content)?;
         visitor.blackboard.register(Arc::new(resource_manager));
@@ -836,6 +883,32 @@ impl Material {
         }
     }
 
+    /// Same as [`Self::set_property`], but consumes the material and returns it back. It is useful to
+    /// configure a material with a custom shader in declarative manner, before attaching it to a surface.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fyrox_impl::material::{Material, PropertyValue};
+    /// # use fyrox_impl::core::color::Color;
+    /// # use fyrox_impl::core::sstorage::ImmutableString;
+    ///
+    /// let material = Material::standard()
+    ///     .with_property(
+    ///         &ImmutableString::new("diffuseColor"),
+    ///         PropertyValue::Color(Color::RED),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn with_property(
+        mut self,
+        name: &ImmutableString,
+        value: PropertyValue,
+    ) -> Result<Self, MaterialError> {
+        self.set_property(name, value)?;
+        Ok(self)
+    }
+
     /// Sets a value for sampler at the given name. It is a shortcut for [`Self::set_property`]
     /// method with [`PropertyValue::Sampler`] and [`SamplerFallback::White`].
     pub fn set_texture(
@@ -905,6 +978,41 @@ impl Material {
         false
     }
 
+    /// Sets face culling mode of the material, it overrides culling mode defined by the passes of
+    /// the shader. [`None`] means that the culling mode of the shader is used.
+    pub fn set_cull_mode(&mut self, cull_mode: Option<CullMode>) {
+        self.cull_mode = cull_mode;
+    }
+
+    /// Sets face culling mode of the material and returns the material. See [`Self::set_cull_mode`]
+    /// for more info.
+    pub fn with_cull_mode(mut self, cull_mode: Option<CullMode>) -> Self {
+        self.set_cull_mode(cull_mode);
+        self
+    }
+
+    /// Returns face culling mode of the material, [`None`] means that the culling mode of the shader
+    /// is used.
+    pub fn cull_mode(&self) -> Option<CullMode> {
+        self.cull_mode
+    }
+
+    /// Returns draw parameters of a shader pass with the culling mode of the material applied.
+    pub fn draw_parameters<'a>(
+        &self,
+        pass_draw_parameters: &'a DrawParameters,
+    ) -> Cow<'a, DrawParameters> {
+        match self.cull_mode {
+            Some(cull_mode) if cull_mode.cull_face() != pass_draw_parameters.cull_face => {
+                Cow::Owned(DrawParameters {
+                    cull_face: cull_mode.cull_face(),
+                    ..pass_draw_parameters.clone()
+                })
+            }
+            _ => Cow::Borrowed(pass_draw_parameters),
+        }
+    }
+
     /// Returns a reference to current shader.
     pub fn shader(&self) -> &ShaderResource {
         &self.shader
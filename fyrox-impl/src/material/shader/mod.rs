@@ -134,7 +134,10 @@
 //!
 //! - GBuffer - A pass that fills a set of render target sized textures with various data
 //! about each rendered object. These textures then are used for physically-based lighting.
-//! Use this pass when you want the standard lighting to work with your objects.
+//! Use this pass when you want the standard lighting to work with your objects. Fragment shader of
+//! this pass must write every output of the G-Buffer (`layout(location = 0)` to `layout(location = 5)`),
+//! values of outputs, that are not written, are undefined. Write `vec4(0.0)` to the emission output
+//! (`layout(location = 5)`) if your object does not emit light.
 //!
 //! - Forward - A pass that draws an object directly in render target. This pass is very
 //! limiting, it does not support lighting, shadows, etc. It should be only used to render
@@ -151,18 +154,28 @@
 //! There are number of built-in properties, that Fyrox will try to assign automatically if they're defined
 //! in your shader:
 //!
-//! | Name                       | Type         | Description                                                                                                       |
-//! |----------------------------|--------------|-------------------------------------------------------------------------------------------------------------------|
-//! | fyrox_worldMatrix          | `mat4`       | Local-to-world transformation.                                                                                    |
-//! | fyrox_worldViewProjection  | `mat4`       | Local-to-clip-space transform.                                                                                    |
-//! | fyrox_boneMatrices         | `sampler2D`  | Array of bone matrices packed into a texture. Use `S_FetchMatrix` built-in method to fetch a matrix by its index. |
-//! | fyrox_useSkeletalAnimation | `bool`       | Whether skinned meshes is rendering or not.                                                                       |
-//! | fyrox_cameraPosition       | `vec3`       | Position of the camera.                                                                                           |
-//! | fyrox_usePOM               | `bool`       | Whether to use parallax mapping or not.                                                                           |
-//! | fyrox_lightPosition        | `vec3`       | Light position.                                                                                                   |
-//! | fyrox_blendShapesStorage   | `sampler3D`  | 3D texture of layered blend shape storage. Use `S_FetchBlendShapeOffsets` built-in method to fetch info.          |
-//! | fyrox_blendShapesWeights   | `float[128]` | Weights of all available blend shapes.                                                                            |
-//! | fyrox_blendShapesCount     | `int`        | Total amount of blend shapes.                                                                                     |
+//! | Name                                 | Type         | Description                                                                                                       |
+//! |--------------------------------------|--------------|-------------------------------------------------------------------------------------------------------------------|
+//! | fyrox_worldMatrix                    | `mat4`       | Local-to-world transformation.                                                                                    |
+//! | fyrox_worldViewProjection            | `mat4`       | Local-to-clip-space transform.                                                                                    |
+//! | fyrox_boneMatrices                   | `sampler2D`  | Array of bone matrices packed into a texture. Use `S_FetchMatrix` built-in method to fetch a matrix by its index. |
+//! | fyrox_useSkeletalAnimation           | `bool`       | Whether skinned meshes is rendering or not.                                                                       |
+//! | fyrox_cameraPosition                 | `vec3`       | Position of the camera.                                                                                           |
+//! | fyrox_usePOM                         | `bool`       | Whether to use parallax mapping or not.                                                                           |
+//! | fyrox_lightPosition                  | `vec3`       | Light position.                                                                                                   |
+//! | fyrox_blendShapesStorage             | `sampler3D`  | 3D texture of layered blend shape storage. Use `S_FetchBlendShapeOffsets` built-in method to fetch info.          |
+//! | fyrox_blendShapesWeights             | `float[128]` | Weights of all available blend shapes.                                                                            |
+//! | fyrox_blendShapesCount               | `int`        | Total amount of blend shapes.                                                                                     |
+//! | fyrox_diffuseTextureSrgb             | `bool`       | Whether `diffuseTexture` has sRGB color space. Use `S_DiffuseToSRGB`/`S_DiffuseToLinear` to get correct color.    |
+//! | fyrox_instanceMatrices               | `sampler2D`  | World matrices of instances packed into a texture. Use `S_FetchMatrix` with `gl_InstanceID` to fetch a matrix.    |
+//! | fyrox_useInstancing                  | `bool`       | Whether instanced meshes is rendering or not.                                                                     |
+//! | fyrox_softParticleScale              | `float`      | Global scale of soft particles boundaries, see `Renderer::set_soft_particle_scale`.                               |
+//! | fyrox_lightsInverseSquareAttenuation | `bool[16]`   | Attenuation of every light in forward passes: `true` - inverse square, `false` - legacy, see `LightAttenuation`.  |
+//!
+//! Instanced meshes (see [`crate::scene::mesh::Mesh::set_instanced`]) are drawn in a single draw call
+//! only with shaders, that define both `fyrox_useInstancing` and `fyrox_instanceMatrices` and take the
+//! world matrix of an instance from `fyrox_instanceMatrices` when `fyrox_useInstancing` is set. With
+//! any other shader, such meshes are drawn one by one, as if instancing was disabled.
 //!
 //! To use any of the properties, just define a uniform with an appropriate name:
 //!
@@ -59,6 +59,27 @@
             name: "parallaxScale",
             kind: Float(0.08),
         ),
+        (
+            name: "normalScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "flipNormalGreen",
+            kind: Bool(false),
+        ),
+        (
+            // Fragments with alpha less than this value are discarded (alpha test) in the GBuffer
+            // and shadow passes, so cutouts cast shadows of the same shape. Use this for cutout
+            // materials (foliage, fences, etc.), zero disables the test.
+            name: "alphaTestThreshold",
+            kind: Float(0.5),
+        ),
+        (
+            // Set to false to make the surface ignore shadows of every light source. It does not
+            // affect casting of shadows, see `cast_shadows` flag of a mesh for that.
+            name: "receiveShadows",
+            kind: Bool(true),
+        ),
     ],
 
     passes: [
@@ -97,6 +118,9 @@
                 // required data to these uniforms.
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
@@ -162,15 +186,23 @@
                         localTangent = inputTangent;
                     }
 
-                    mat3 nm = mat3(fyrox_worldMatrix);
+                    mat4 worldMatrix = fyrox_worldMatrix;
+                    mat4 worldViewProjection = fyrox_worldViewProjection;
+                    if (fyrox_useInstancing)
+                    {
+                        worldMatrix = S_FetchMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        worldViewProjection = fyrox_viewProjectionMatrix * worldMatrix;
+                    }
+
+                    mat3 nm = mat3(worldMatrix);
                     normal = normalize(nm * localNormal);
                     tangent = normalize(nm * localTangent);
                     binormal = normalize(vertexTangent.w * cross(normal, tangent));
                     texCoord = vertexTexCoord;
-                    position = vec3(fyrox_worldMatrix * localPosition);
+                    position = vec3(worldMatrix * localPosition);
                     secondTexCoord = vertexSecondTexCoord;
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    gl_Position = worldViewProjection * localPosition;
                 }
                 "#,
             fragment_shader:
@@ -180,10 +212,15 @@
                 layout(location = 2) out vec4 outAmbient;
                 layout(location = 3) out vec4 outMaterial;
                 layout(location = 4) out uint outDecalMask;
+                layout(location = 5) out vec4 outEmission;
 
                 // Properties.
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
                 uniform sampler2D normalTexture;
+                uniform float normalScale;
+                // Set to true for normal maps in DirectX convention (Y axis points down).
+                uniform bool flipNormalGreen;
                 uniform sampler2D metallicTexture;
                 uniform sampler2D roughnessTexture;
                 uniform sampler2D heightTexture;
@@ -196,6 +233,8 @@
                 uniform vec4 diffuseColor;
                 uniform float parallaxCenter;
                 uniform float parallaxScale;
+                uniform float alphaTestThreshold;
+                uniform bool receiveShadows;
 
                 // Define uniforms with reserved names. Fyrox will automatically provide
                 // required data to these uniforms.
@@ -228,25 +267,39 @@
                         tc = texCoord * texCoordScale;
                     }
 
-                    outColor = diffuseColor * texture(diffuseTexture, tc);
+                    outColor = diffuseColor * S_DiffuseToSRGB(texture(diffuseTexture, tc), fyrox_diffuseTextureSrgb);
 
                     // Alpha test.
-                    if (outColor.a < 0.5) {
+                    if (outColor.a < alphaTestThreshold) {
                         discard;
                     }
                     outColor.a = 1.0;
 
-                    vec4 n = normalize(texture(normalTexture, tc) * 2.0 - 1.0);
-                    outNormal = vec4(normalize(tangentSpace * n.xyz) * 0.5 + 0.5, 1.0);
+                    vec3 n = texture(normalTexture, tc).xyz * 2.0 - 1.0;
+                    if (flipNormalGreen) {
+                        n.y = -n.y;
+                    }
+                    n.xy *= normalScale;
+                    vec3 worldNormal = normalize(tangentSpace * normalize(n));
+                    // Back faces are visible only if culling is disabled, flip their normals so
+                    // they are lit correctly.
+                    if (!gl_FrontFacing) {
+                        worldNormal = -worldNormal;
+                    }
+                    outNormal = vec4(worldNormal * 0.5 + 0.5, 1.0);
 
                     outMaterial.x = texture(metallicTexture, tc).r;
                     outMaterial.y = texture(roughnessTexture, tc).r;
                     outMaterial.z = texture(aoTexture, tc).r;
-                    outMaterial.a = 1.0;
+                    // Alpha channel is used as a flag, that tells lights whether to apply shadows.
+                    outMaterial.a = receiveShadows ? 1.0 : 0.0;
 
-                    outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb + texture(lightmapTexture, secondTexCoord).rgb;
+                    outAmbient.xyz = texture(lightmapTexture, secondTexCoord).rgb;
                     outAmbient.a = 1.0;
 
+                    outEmission.xyz = emissionStrength * texture(emissionTexture, tc).rgb;
+                    outEmission.a = 1.0;
+
                     outDecalMask = layerIndex;
                 }
                 "#,
@@ -341,6 +394,7 @@
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
                 uniform vec4 diffuseColor;
 
                 out vec4 FragColor;
@@ -349,7 +403,7 @@
 
                 void main()
                 {
-                    FragColor = diffuseColor * texture(diffuseTexture, texCoord);
+                    FragColor = diffuseColor * S_DiffuseToSRGB(texture(diffuseTexture, texCoord), fyrox_diffuseTextureSrgb);
                 }
                "#,
         ),
@@ -431,12 +485,13 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform float alphaTestThreshold;
 
                 in vec2 texCoord;
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (texture(diffuseTexture, texCoord).a < alphaTestThreshold) discard;
                 }
                 "#,
         ),
@@ -518,12 +573,13 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform float alphaTestThreshold;
 
                 in vec2 texCoord;
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (texture(diffuseTexture, texCoord).a < alphaTestThreshold) discard;
                 }
                 "#,
         ),
@@ -608,6 +664,7 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform float alphaTestThreshold;
 
                 uniform vec3 fyrox_lightPosition;
 
@@ -618,7 +675,7 @@
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (texture(diffuseTexture, texCoord).a < alphaTestThreshold) discard;
                     depth = length(fyrox_lightPosition - worldPosition);
                 }
                 "#,
@@ -66,12 +66,14 @@
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
 
                 uniform int fyrox_lightCount;
                 uniform vec4 fyrox_lightsColorRadius[16]; // xyz - color, w = radius
                 uniform vec3 fyrox_lightsPosition[16];
                 uniform vec3 fyrox_lightsDirection[16];
                 uniform vec2 fyrox_lightsParameters[16]; // x = hotspot angle, y - full cone angle delta
+                uniform bool fyrox_lightsInverseSquareAttenuation[16];
                 uniform vec4 fyrox_ambientLightColor;
 
                 out vec4 FragColor;
@@ -96,13 +98,13 @@
                         vec3 toFragment = fragmentPosition - lightPosition;
                         float distance = length(toFragment);
                         vec3 toFragmentNormalized = toFragment / distance;
-                        float distanceAttenuation = S_LightDistanceAttenuation(distance, radius);
+                        float distanceAttenuation = S_LightAttenuation(fyrox_lightsInverseSquareAttenuation[i], distance, radius);
                         float spotAngleCos = dot(toFragmentNormalized, direction);
                         float directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
                         lighting += lightColor * (distanceAttenuation * directionalAttenuation);
                     }
 
-                    FragColor = vec4(lighting, 1.0) * color * S_SRGBToLinear(texture(diffuseTexture, texCoord));
+                    FragColor = vec4(lighting, 1.0) * color * S_DiffuseToLinear(texture(diffuseTexture, texCoord), fyrox_diffuseTextureSrgb);
                 }
                "#,
         )
@@ -81,6 +81,7 @@
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
 
                 out vec4 FragColor;
 
@@ -89,7 +90,7 @@
 
                 void main()
                 {
-                    FragColor = color * S_SRGBToLinear(texture(diffuseTexture, texCoord));
+                    FragColor = color * S_DiffuseToLinear(texture(diffuseTexture, texCoord), fyrox_diffuseTextureSrgb);
                 }
                "#,
         )
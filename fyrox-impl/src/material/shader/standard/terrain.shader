@@ -71,6 +71,14 @@
             name: "parallaxScale",
             kind: Float(0.08),
         ),
+        (
+            name: "normalScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "flipNormalGreen",
+            kind: Bool(false),
+        ),
     ],
 
     passes: [
@@ -155,10 +163,15 @@
                 layout(location = 2) out vec4 outAmbient;
                 layout(location = 3) out vec4 outMaterial;
                 layout(location = 4) out uint outDecalMask;
+                layout(location = 5) out vec4 outEmission;
 
                 // Properties.
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
                 uniform sampler2D normalTexture;
+                uniform float normalScale;
+                // Set to true for normal maps in DirectX convention (Y axis points down).
+                uniform bool flipNormalGreen;
                 uniform sampler2D metallicTexture;
                 uniform sampler2D roughnessTexture;
                 uniform sampler2D heightTexture;
@@ -204,25 +217,33 @@
                         tc = texCoord * texCoordScale;
                     }
 
-                    outColor = diffuseColor * texture(diffuseTexture, tc);
+                    outColor = diffuseColor * S_DiffuseToSRGB(texture(diffuseTexture, tc), fyrox_diffuseTextureSrgb);
 
-                    vec4 n = normalize(texture(normalTexture, tc) * 2.0 - 1.0);
-                    outNormal = vec4(normalize(tangentSpace * n.xyz) * 0.5 + 0.5, 1.0);
+                    vec3 n = texture(normalTexture, tc).xyz * 2.0 - 1.0;
+                    if (flipNormalGreen) {
+                        n.y = -n.y;
+                    }
+                    n.xy *= normalScale;
+                    outNormal = vec4(normalize(tangentSpace * normalize(n)) * 0.5 + 0.5, 1.0);
 
                     outMaterial.x = texture(metallicTexture, tc).r;
                     outMaterial.y = texture(roughnessTexture, tc).r;
                     outMaterial.z = texture(aoTexture, tc).r;
                     outMaterial.a = 1.0;
 
-                    outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb + texture(lightmapTexture, secondTexCoord).rgb;
+                    outAmbient.xyz = texture(lightmapTexture, secondTexCoord).rgb;
                     outAmbient.a = 1.0;
 
+                    outEmission.xyz = emissionStrength * texture(emissionTexture, tc).rgb;
+                    outEmission.a = 1.0;
+
                     outDecalMask = layerIndex;
 
                     float mask = texture(maskTexture, texCoord).r;
 
                     outColor.a = mask;
                     outAmbient.a = mask;
+                    outEmission.a = mask;
                     outNormal.a = mask;
                     outMaterial.a = mask;
                 }
@@ -288,6 +309,7 @@
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool fyrox_diffuseTextureSrgb;
                 uniform vec4 diffuseColor;
 
                 out vec4 FragColor;
@@ -296,7 +318,7 @@
 
                 void main()
                 {
-                    FragColor = diffuseColor * texture(diffuseTexture, texCoord);
+                    FragColor = diffuseColor * S_DiffuseToSRGB(texture(diffuseTexture, texCoord), fyrox_diffuseTextureSrgb);
                 }
                "#,
         ),
@@ -103,6 +103,19 @@ impl GaussianBlur {
         })
     }
 
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        self.h_framebuffer.resize(state, width, height)?;
+        self.v_framebuffer.resize(state, width, height)?;
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
     fn h_blurred(&self) -> Rc<RefCell<GpuTexture>> {
         self.h_framebuffer.color_attachments()[0].texture.clone()
     }
@@ -15,7 +15,7 @@ use crate::{
             },
             state::PipelineState,
         },
-        make_viewport_matrix, RenderPassStatistics,
+        make_viewport_matrix, BloomSettings, RenderPassStatistics,
     },
 };
 use std::{cell::RefCell, rc::Rc};
@@ -26,6 +26,8 @@ struct Shader {
     program: GpuProgram,
     world_view_projection_matrix: UniformLocation,
     hdr_sampler: UniformLocation,
+    threshold: UniformLocation,
+    intensity: UniformLocation,
 }
 
 impl Shader {
@@ -39,6 +41,8 @@ impl Shader {
             world_view_projection_matrix: program
                 .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             hdr_sampler: program.uniform_location(state, &ImmutableString::new("hdrSampler"))?,
+            threshold: program.uniform_location(state, &ImmutableString::new("threshold"))?,
+            intensity: program.uniform_location(state, &ImmutableString::new("intensity"))?,
             program,
         })
     }
@@ -88,6 +92,19 @@ impl BloomRenderer {
         })
     }
 
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        self.framebuffer.resize(state, width, height)?;
+        self.blur.resize(state, width, height)?;
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
     fn glow_texture(&self) -> Rc<RefCell<GpuTexture>> {
         self.framebuffer.color_attachments()[0].texture.clone()
     }
@@ -101,6 +118,7 @@ impl BloomRenderer {
         state: &PipelineState,
         quad: &GeometryBuffer,
         hdr_scene_frame: Rc<RefCell<GpuTexture>>,
+        settings: &BloomSettings,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
@@ -130,11 +148,17 @@ impl BloomRenderer {
                         &shader.world_view_projection_matrix,
                         &(make_viewport_matrix(viewport)),
                     )
+                    .set_f32(&shader.threshold, settings.threshold)
+                    .set_f32(&shader.intensity, settings.intensity)
                     .set_texture(&shader.hdr_sampler, &hdr_scene_frame);
             },
         )?;
 
+        // Every next blur pass takes the result of the previous one, making the glow wider.
         stats += self.blur.render(state, quad, self.glow_texture())?;
+        for _ in 1..settings.blur_iterations {
+            stats += self.blur.render(state, quad, self.blur.result())?;
+        }
 
         Ok(stats)
     }
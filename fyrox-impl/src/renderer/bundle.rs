@@ -4,13 +4,17 @@ use crate::scene::node::RdcControlFlow;
 use crate::{
     core::{
         algebra::{Matrix4, Vector3},
-        math::frustum::Frustum,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum},
         pool::Handle,
         sstorage::ImmutableString,
     },
     graph::BaseSceneGraph,
     material::MaterialResource,
-    renderer::{cache::TimeToLive, framework::geometry_buffer::ElementRange},
+    renderer::{
+        cache::TimeToLive,
+        framework::{geometry_buffer::ElementRange, state::BlendFunc},
+        CullingStatistics, RenderFlags, TransparencyMode,
+    },
     scene::{
         graph::Graph,
         mesh::{
@@ -22,6 +26,7 @@ use crate::{
             RenderPath,
         },
         node::Node,
+        particle_system::ParticleSystem,
     },
 };
 use fxhash::{FxBuildHasher, FxHashMap, FxHasher};
@@ -30,6 +35,7 @@ use std::{
     collections::hash_map::DefaultHasher,
     fmt::{Debug, Formatter},
     hash::{Hash, Hasher},
+    ops::Range,
 };
 
 /// Observer info contains all the data, that describes an observer. It could be a real camera, light source's
@@ -45,6 +51,75 @@ pub struct ObserverInfo {
     pub view_matrix: Matrix4<f32>,
     /// Projection matrix of the observer.
     pub projection_matrix: Matrix4<f32>,
+    /// Whether frustum culling is enabled or not. When disabled, every visible node will be asked to give its
+    /// render data, regardless of its position relative to the observer.
+    pub frustum_culling: bool,
+    /// Defines how alpha-blended particles will be rendered.
+    pub particle_transparency: TransparencyMode,
+    /// Parts of a frame, that should be rendered. Nodes of disabled parts will not be asked to give
+    /// their render data.
+    pub render_flags: RenderFlags,
+    /// Whether the observer should use hysteresis of LOD groups (see [`crate::scene::base::LodGroup::hysteresis`]) or
+    /// not. State of the hysteresis is shared, so it should be used only by the main camera of a
+    /// scene.
+    pub lod_hysteresis: bool,
+}
+
+/// Per-particle-system limits of rendered particles, calculated from the remaining part of the global
+/// particle budget. Particle systems closer to the observer get their share of the budget first.
+#[derive(Default, Debug)]
+pub struct ParticleBudget {
+    limits: FxHashMap<Handle<Node>, usize>,
+}
+
+impl ParticleBudget {
+    fn new(
+        graph: &Graph,
+        observer_position: &Vector3<f32>,
+        frustum: Option<&Frustum>,
+        budget: &mut usize,
+    ) -> (Self, usize) {
+        let mut particle_systems = graph
+            .pair_iter()
+            .filter(|(_, node)| node.global_visibility() && node.is_globally_enabled())
+            .filter_map(|(handle, node)| {
+                let particle_system = node.cast::<ParticleSystem>()?;
+                if let Some(frustum) = frustum {
+                    if particle_system.frustum_culling()
+                        && !frustum.is_intersects_aabb(&particle_system.world_bounding_box())
+                    {
+                        return None;
+                    }
+                }
+                Some((
+                    handle,
+                    particle_system
+                        .global_position()
+                        .metric_distance(observer_position),
+                    particle_system.alive_particle_count(),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        particle_systems.sort_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+
+        let mut culled = 0;
+        let mut limits = FxHashMap::default();
+        for (handle, _, count) in particle_systems {
+            let limit = count.min(*budget);
+            *budget -= limit;
+            culled += count - limit;
+            limits.insert(handle, limit);
+        }
+
+        (Self { limits }, culled)
+    }
+
+    /// Returns maximum amount of particles, that could be rendered by the given particle system.
+    /// `None` means that there is no limit.
+    pub fn limit(&self, particle_system: Handle<Node>) -> Option<usize> {
+        self.limits.get(&particle_system).cloned()
+    }
 }
 
 /// Render context is used to collect render data from the scene nodes. It provides all required information about
@@ -71,20 +146,49 @@ pub struct RenderContext<'a> {
     pub graph: &'a Graph,
     /// A name of the render pass for which the context was created for.
     pub render_pass_name: &'a ImmutableString,
+    /// Defines how alpha-blended particles will be rendered. Particles does not need to be sorted
+    /// if it is [`TransparencyMode::WeightedBlended`].
+    pub particle_transparency: TransparencyMode,
+    /// Limits of rendered particles per particle system. `None` means that there are no limits.
+    pub particle_budget: Option<&'a ParticleBudget>,
+    /// Frustum culling statistics, use [`Self::is_inside_frustum`] to do frustum culling and to
+    /// update the statistics at the same time.
+    pub culling_statistics: CullingStatistics,
 }
 
 impl<'a> RenderContext<'a> {
+    /// Checks whether the given world-space bounding box is (at least partially) inside the frustum
+    /// of the observer and updates culling statistics. Always returns `true` if `frustum_culling`
+    /// is `false` or if there's no frustum.
+    pub fn is_inside_frustum(
+        &mut self,
+        frustum_culling: bool,
+        world_bounding_box: &AxisAlignedBoundingBox,
+    ) -> bool {
+        let Some(frustum) = self.frustum.filter(|_| frustum_culling) else {
+            return true;
+        };
+
+        if frustum.is_intersects_aabb(world_bounding_box) {
+            self.culling_statistics.passed_nodes += 1;
+            true
+        } else {
+            self.culling_statistics.culled_nodes += 1;
+            false
+        }
+    }
+
     /// Calculates sorting index using of the given point by transforming it in the view space and
-    /// using Z coordinate. This index could be used for back-to-front sorting to prevent blending
-    /// issues.
+    /// using its distance along the view direction. Farther points have smaller indices, so this
+    /// index could be used for back-to-front sorting to prevent blending issues.
     pub fn calculate_sorting_index(&self, global_position: Vector3<f32>) -> u64 {
         let granularity = 1000.0;
-        u64::MAX
-            - (self
-                .view_matrix
-                .transform_point(&(global_position.into()))
-                .z
-                * granularity) as u64
+        // Observer looks along -Z axis in the view space, so visible points have negative Z.
+        let distance = -self
+            .view_matrix
+            .transform_point(&(global_position.into()))
+            .z;
+        u64::MAX - (distance * granularity) as u64
     }
 }
 
@@ -106,6 +210,16 @@ impl PersistentIdentifier {
         hasher.write_usize(index);
         Self(hasher.finish())
     }
+
+    /// Creates a new persistent identifier for a set of instances, that are drawn in a single instanced
+    /// draw call. The identifier is the same only for the same set of instances.
+    pub fn new_instanced(instances: impl Iterator<Item = PersistentIdentifier>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        for instance in instances {
+            instance.hash(&mut hasher);
+        }
+        Self(hasher.finish())
+    }
 }
 
 /// A set of data of a surface for rendering.  
@@ -126,6 +240,10 @@ pub struct SurfaceInstanceData {
     pub persistent_identifier: PersistentIdentifier,
     /// A handle of a node that emitted this surface data. Could be none, if there's no info about scene node.
     pub node_handle: Handle<Node>,
+    /// A flag, that defines whether the instance can be drawn in a single instanced draw call together with
+    /// other instances of the bundle with the same flag. Instances with bone matrices, blend shapes, depth
+    /// offset or partial element range are always drawn separately.
+    pub instanced: bool,
 }
 
 /// A set of surface instances that share the same vertex/index data and a material.
@@ -145,7 +263,46 @@ pub struct RenderDataBundle {
     pub render_path: RenderPath,
     /// A decal layer index of the bundle.
     pub decal_layer_index: u8,
+    /// An optional blending function, that overrides the one defined in the render pass of the material's
+    /// shader.
+    pub blend_func: Option<BlendFunc>,
     sort_index: u64,
+    // Sort indices and ranges of triangles of every batch, that was added by `push_triangles`.
+    triangle_chunks: Vec<(u64, Range<usize>)>,
+}
+
+impl RenderDataBundle {
+    // Reorders batched triangles back-to-front, so transparent objects (sprites, for instance) that
+    // share the same bundle are blended correctly against each other.
+    fn sort_triangle_chunks(&mut self) {
+        if self.render_path != RenderPath::Forward || self.triangle_chunks.len() < 2 {
+            return;
+        }
+
+        self.triangle_chunks
+            .sort_by_key(|(sort_index, _)| *sort_index);
+
+        let mut data = self.data.data_ref();
+        let triangle_buffer = &mut data.geometry_buffer;
+        let triangles = triangle_buffer.triangles_ref();
+        let sorted = self
+            .triangle_chunks
+            .iter()
+            .flat_map(|(_, range)| triangles[range.clone()].iter().cloned())
+            .collect::<Vec<_>>();
+        triangle_buffer.set_triangles(sorted);
+
+        // Triangles were moved, update the ranges so the next sort won't mix them up.
+        let mut start = 0;
+        for (_, range) in self.triangle_chunks.iter_mut() {
+            let len = range.len();
+            *range = start..start + len;
+            start += len;
+        }
+
+        // The farthest batch defines the order of the whole bundle.
+        self.sort_index = self.triangle_chunks[0].0;
+    }
 }
 
 impl Debug for RenderDataBundle {
@@ -169,10 +326,12 @@ pub trait RenderDataBundleStorageTrait {
     /// - Render Path
     /// - Skinning
     /// - Decal Layer Index
+    /// - Blending Function
     ///
     /// If one of these parameters is different, then a new bundle will be created and used to store
     /// the given vertices and indices. If an appropriate bundle exists, the the method will store
-    /// the given vertices and the triangles in it.
+    /// the given vertices and the triangles in it. `blend_func` could be used to override blending
+    /// function of the material's shader, `None` means that the function of the shader will be used.
     ///
     /// ## When to use
     ///
@@ -191,6 +350,7 @@ pub trait RenderDataBundleStorageTrait {
         decal_layer_index: u8,
         sort_index: u64,
         is_skinned: bool,
+        blend_func: Option<BlendFunc>,
         node_handle: Handle<Node>,
         func: &mut dyn FnMut(VertexBufferRefMut, TriangleBufferRefMut),
     );
@@ -217,28 +377,34 @@ pub struct RenderDataBundleStorage {
     bundle_map: FxHashMap<u64, usize>,
     /// A sorted list of bundles.
     pub bundles: Vec<RenderDataBundle>,
+    /// Frustum culling statistics, that was gathered during bundle generation.
+    pub culling_statistics: CullingStatistics,
 }
 
 impl RenderDataBundleStorage {
     /// Creates a new render bundle storage from the given graph and observer info. It "asks" every node in the
     /// graph one-by-one to give render data which is then put in the storage, sorted and ready for rendering.
     /// Frustum culling is done on scene node side ([`crate::scene::node::NodeTrait::collect_render_data`]).
+    /// Visible particle systems take their particles from the given remaining particle budget (if any), so
+    /// the budget could be shared across multiple observers. `None` means that there is no limit.
     pub fn from_graph(
         graph: &Graph,
         observer_info: ObserverInfo,
         render_pass_name: ImmutableString,
+        particle_budget: Option<&mut usize>,
     ) -> Self {
         // Aim for the worst-case scenario when every node has unique render data.
         let capacity = graph.node_count() as usize;
         let mut storage = Self {
             bundle_map: FxHashMap::with_capacity_and_hasher(capacity, FxBuildHasher::default()),
             bundles: Vec::with_capacity(capacity),
+            culling_statistics: Default::default(),
         };
 
         let mut lod_filter = vec![true; graph.capacity() as usize];
         for node in graph.linear_iter() {
             if let Some(lod_group) = node.lod_group() {
-                for level in lod_group.levels.iter() {
+                for (level_index, level) in lod_group.levels.iter().enumerate() {
                     for &object in level.objects.iter() {
                         if let Some(object_ref) = graph.try_get(object) {
                             let distance = observer_info
@@ -246,9 +412,12 @@ impl RenderDataBundleStorage {
                                 .metric_distance(&object_ref.global_position());
                             let z_range = observer_info.z_far - observer_info.z_near;
                             let normalized_distance = (distance - observer_info.z_near) / z_range;
-                            let visible = normalized_distance >= level.begin()
-                                && normalized_distance <= level.end();
-                            lod_filter[object.index() as usize] = visible;
+                            lod_filter[object.index() as usize] = lod_group.is_object_visible(
+                                level_index,
+                                object,
+                                normalized_distance,
+                                observer_info.lod_hysteresis,
+                            );
                         }
                     }
                 }
@@ -260,16 +429,36 @@ impl RenderDataBundleStorage {
         )
         .unwrap_or_default();
 
+        let mut culling_statistics = CullingStatistics::default();
+
+        let particle_budget = particle_budget.map(|budget| {
+            let (particle_budget, culled_particles) = ParticleBudget::new(
+                graph,
+                &observer_info.observer_position,
+                observer_info.frustum_culling.then_some(&frustum),
+                budget,
+            );
+            culling_statistics.culled_particles += culled_particles;
+            particle_budget
+        });
+
         let mut ctx = RenderContext {
             observer_position: &observer_info.observer_position,
             z_near: observer_info.z_near,
             z_far: observer_info.z_far,
             view_matrix: &observer_info.view_matrix,
             projection_matrix: &observer_info.projection_matrix,
-            frustum: Some(&frustum),
+            frustum: if observer_info.frustum_culling {
+                Some(&frustum)
+            } else {
+                None
+            },
             storage: &mut storage,
             graph,
             render_pass_name: &render_pass_name,
+            particle_transparency: observer_info.particle_transparency,
+            particle_budget: particle_budget.as_ref(),
+            culling_statistics,
         };
 
         let mut stack = Vec::with_capacity(capacity / 4);
@@ -277,19 +466,29 @@ impl RenderDataBundleStorage {
         while let Some(handle) = stack.pop() {
             if lod_filter[handle.index() as usize] {
                 let node = graph.node(handle);
-                if let RdcControlFlow::Continue = node.collect_render_data(&mut ctx) {
+
+                if !observer_info.render_flags.allows_node(node) {
+                    // Descendants of a disabled node still could be rendered.
+                    stack.extend_from_slice(node.children());
+                } else if let RdcControlFlow::Continue = node.collect_render_data(&mut ctx) {
                     stack.extend_from_slice(node.children());
                 }
             }
         }
 
+        let culling_statistics = ctx.culling_statistics;
+        storage.culling_statistics = culling_statistics;
         storage.sort();
 
         storage
     }
 
-    /// Sorts the bundles by their respective sort index.
+    /// Sorts the bundles by their respective sort index. Triangles of forward bundles, that were
+    /// produced by dynamic batching, are sorted too.
     pub fn sort(&mut self) {
+        for bundle in self.bundles.iter_mut() {
+            bundle.sort_triangle_chunks();
+        }
         self.bundles.sort_unstable_by_key(|b| b.sort_index);
     }
 }
@@ -325,6 +524,7 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
         decal_layer_index: u8,
         sort_index: u64,
         is_skinned: bool,
+        blend_func: Option<BlendFunc>,
         node_handle: Handle<Node>,
         func: &mut dyn FnMut(VertexBufferRefMut, TriangleBufferRefMut),
     ) {
@@ -334,6 +534,7 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
         hasher.write_u8(if is_skinned { 1 } else { 0 });
         hasher.write_u8(decal_layer_index);
         hasher.write_u32(render_path as u32);
+        blend_func.hash(&mut hasher);
         let key = hasher.finish();
 
         let bundle = if let Some(&bundle_index) = self.bundle_map.get(&key) {
@@ -373,14 +574,17 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
                         element_range: Default::default(),
                         persistent_identifier,
                         node_handle,
+                        instanced: false,
                     },
                 ],
                 material: material.clone(),
                 is_skinned,
                 render_path,
                 decal_layer_index,
+                blend_func,
                 // Temporary buffer lives one frame.
                 time_to_live: TimeToLive(0.0),
+                triangle_chunks: Default::default(),
             });
             self.bundles.last_mut().unwrap()
         };
@@ -388,10 +592,16 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
         let mut data = bundle.data.data_ref();
         let data = &mut *data;
 
+        let first_triangle = data.geometry_buffer.len();
+
         let vertex_buffer = data.vertex_buffer.modify();
         let triangle_buffer = data.geometry_buffer.modify();
 
         func(vertex_buffer, triangle_buffer);
+
+        bundle
+            .triangle_chunks
+            .push((sort_index, first_triangle..data.geometry_buffer.len()));
     }
 
     /// Adds a new surface instance to the storage. The method will automatically put the instance in the appropriate
@@ -428,7 +638,9 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
                 is_skinned,
                 render_path,
                 decal_layer_index,
+                blend_func: None,
                 time_to_live: Default::default(),
+                triangle_chunks: Default::default(),
             });
             self.bundles.last_mut().unwrap()
         };
@@ -436,3 +648,80 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
         bundle.instances.push(instance_data)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Vector3},
+            sstorage::ImmutableString,
+        },
+        material::{Material, MaterialResource},
+        renderer::bundle::{ObserverInfo, RenderDataBundleStorage},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::buffer::{VertexAttributeUsage, VertexReadTrait},
+            sprite::SpriteBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_sprites_sorted_back_to_front() {
+        let mut graph = Graph::new();
+
+        // Every sprite shares the same material, so they're batched into a single bundle.
+        let material = MaterialResource::new_ok(Default::default(), Material::standard_sprite());
+        for z in [-5.0, -2.0, -10.0] {
+            SpriteBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, z))
+                        .build(),
+                ),
+            )
+            .with_material(material.clone())
+            .build(&mut graph);
+        }
+
+        graph.update_hierarchical_data();
+
+        let mut storage = RenderDataBundleStorage::from_graph(
+            &graph,
+            ObserverInfo {
+                observer_position: Default::default(),
+                z_near: 0.025,
+                z_far: 100.0,
+                // The observer is at the origin and looks along -Z axis.
+                view_matrix: Matrix4::identity(),
+                projection_matrix: Matrix4::new_perspective(1.0, 1.0, 0.025, 100.0),
+                frustum_culling: false,
+                particle_transparency: Default::default(),
+                render_flags: Default::default(),
+                lod_hysteresis: false,
+            },
+            ImmutableString::new("Forward"),
+            None,
+        );
+        // `from_graph` sorts the storage already, sorting it again must keep the same order.
+        storage.sort();
+
+        assert_eq!(storage.bundles.len(), 1);
+        let data = storage.bundles[0].data.data_ref();
+        let depths = data
+            .geometry_buffer
+            .triangles_ref()
+            .iter()
+            .map(|triangle| {
+                data.vertex_buffer
+                    .get(triangle[0] as usize)
+                    .unwrap()
+                    .read_3_f32(VertexAttributeUsage::Position)
+                    .unwrap()
+                    .z
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(depths, [-10.0, -10.0, -5.0, -5.0, -2.0, -2.0]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     renderer::{
         cache::{TemporaryCache, TimeToLive},
         framework::{
+            backend::RenderBackend,
             error::FrameworkError,
             geometry_buffer::{GeometryBuffer, GeometryBufferKind},
             state::PipelineState,
@@ -18,6 +19,9 @@ struct SurfaceRenderData {
     layout_hash: u64,
 }
 
+/// GPU-side geometry buffers of surfaces. There is exactly one geometry buffer per surface data
+/// resource, so every surface, that uses the same [`SurfaceResource`], shares the buffer and the
+/// data is uploaded only once.
 #[derive(Default)]
 pub struct GeometryCache {
     buffer: TemporaryCache<SurfaceRenderData>,
@@ -27,8 +31,7 @@ fn create_geometry_buffer(
     data: &SurfaceData,
     state: &PipelineState,
 ) -> Result<SurfaceRenderData, FrameworkError> {
-    let geometry_buffer =
-        GeometryBuffer::from_surface_data(data, GeometryBufferKind::StaticDraw, state)?;
+    let geometry_buffer = state.create_geometry_buffer(data, GeometryBufferKind::StaticDraw)?;
 
     Ok(SurfaceRenderData {
         buffer: geometry_buffer,
@@ -95,4 +98,9 @@ impl GeometryCache {
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
+
+    /// Returns amount of unique geometry buffers, that are currently resident in GPU memory.
+    pub fn alive_count(&self) -> usize {
+        self.buffer.alive_count()
+    }
 }
@@ -183,4 +183,9 @@ impl<T> TemporaryCache<T> {
     pub fn remove(&mut self, index: &AtomicIndex) {
         self.buffer.free(index);
     }
+
+    /// Returns amount of alive entries in the cache.
+    pub fn alive_count(&self) -> usize {
+        self.buffer.filled()
+    }
 }
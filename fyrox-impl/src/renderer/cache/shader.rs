@@ -4,7 +4,8 @@ use crate::{
     core::sstorage::ImmutableString,
     material::shader::{Shader, ShaderResource},
     renderer::framework::{
-        framebuffer::DrawParameters, gpu_program::GpuProgram, state::PipelineState,
+        backend::RenderBackend, framebuffer::DrawParameters, gpu_program::GpuProgram,
+        state::PipelineState,
     },
 };
 use fxhash::FxHashMap;
@@ -24,8 +25,7 @@ impl ShaderSet {
         let mut map = FxHashMap::default();
         for render_pass in shader.definition.passes.iter() {
             let program_name = format!("{}_{}", shader.definition.name, render_pass.name);
-            match GpuProgram::from_source(
-                state,
+            match state.create_program(
                 &program_name,
                 &render_pass.vertex_shader,
                 &render_pass.fragment_shader,
@@ -41,7 +41,7 @@ impl ShaderSet {
                 }
                 Err(e) => {
                     return Err(FrameworkError::Custom(format!(
-                        "Failed to create {} shader' GPU program. Reason: {:?}",
+                        "Failed to create {} shader' GPU program. Reason: {}",
                         program_name, e
                     )));
                 }
@@ -50,11 +50,42 @@ impl ShaderSet {
 
         Ok(Self { render_passes: map })
     }
+
+    /// Creates a set of programs, that draw everything in bright magenta. It is used instead of a
+    /// shader, that failed to compile, so objects with broken shaders are still visible and the
+    /// shader could be fixed without restarting the application. Only scene passes (`GBuffer`
+    /// and `Forward`) are replaced, the rest of the passes (shadows, etc.) are skipped.
+    pub fn fallback(state: &PipelineState, shader: &Shader) -> Result<Self, FrameworkError> {
+        let mut map = FxHashMap::default();
+        for render_pass in shader.definition.passes.iter() {
+            if render_pass.name == "GBuffer" || render_pass.name == "Forward" {
+                map.insert(
+                    ImmutableString::new(&render_pass.name),
+                    RenderPassData {
+                        program: state.create_program(
+                            "FallbackShader",
+                            include_str!("../shaders/fallback_vs.glsl"),
+                            include_str!("../shaders/fallback_fs.glsl"),
+                        )?,
+                        draw_params: render_pass.draw_parameters.clone(),
+                    },
+                );
+            }
+        }
+
+        Ok(Self { render_passes: map })
+    }
+}
+
+struct ShaderCacheEntry {
+    // Source shader, it is needed to be able to recompile the programs.
+    shader: ShaderResource,
+    set: ShaderSet,
 }
 
 #[derive(Default)]
 pub struct ShaderCache {
-    pub(super) cache: TemporaryCache<ShaderSet>,
+    pub(super) cache: TemporaryCache<ShaderCacheEntry>,
 }
 
 impl ShaderCache {
@@ -76,9 +107,19 @@ impl ShaderCache {
             match self.cache.get_or_insert_with(
                 &shader_state.cache_index,
                 Default::default(),
-                || ShaderSet::new(pipeline_state, shader_state),
+                || {
+                    ShaderSet::new(pipeline_state, shader_state)
+                        .or_else(|error| {
+                            Log::err(format!("{}. Fallback shader will be used instead.", error));
+                            ShaderSet::fallback(pipeline_state, shader_state)
+                        })
+                        .map(|set| ShaderCacheEntry {
+                            shader: shader.clone(),
+                            set,
+                        })
+                },
             ) {
-                Ok(shader_set) => Some(shader_set),
+                Ok(entry) => Some(&entry.set),
                 Err(error) => {
                     Log::err(format!("{}", error));
                     None
@@ -89,6 +130,64 @@ impl ShaderCache {
         }
     }
 
+    /// Recompiles the programs of the given shader. Previous programs of the shader are kept if
+    /// the compilation fails, so a typo in a shader that is being edited does not break rendering.
+    pub fn reload(
+        &mut self,
+        pipeline_state: &PipelineState,
+        shader: &ShaderResource,
+    ) -> Result<(), FrameworkError> {
+        let mut shader_state = shader.state();
+        let Some(shader_data) = shader_state.data() else {
+            return Ok(());
+        };
+
+        match ShaderSet::new(pipeline_state, shader_data) {
+            Ok(set) => {
+                self.cache.remove(&shader_data.cache_index);
+                self.cache.spawn(
+                    ShaderCacheEntry {
+                        shader: shader.clone(),
+                        set,
+                    },
+                    shader_data.cache_index.clone(),
+                    Default::default(),
+                );
+                Ok(())
+            }
+            Err(error) => {
+                if self.cache.get_mut(&shader_data.cache_index).is_none() {
+                    // There is nothing to keep, use the fallback.
+                    drop(shader_state);
+                    let _ = self.get(pipeline_state, shader);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Recompiles the programs of every cached shader, see [`Self::reload`] for more info. Returns
+    /// the first error, if any, but tries to reload all the shaders.
+    pub fn reload_all(&mut self, pipeline_state: &PipelineState) -> Result<(), FrameworkError> {
+        let shaders = self
+            .cache
+            .buffer
+            .iter()
+            .map(|entry| entry.shader.clone())
+            .collect::<Vec<_>>();
+
+        let mut result = Ok(());
+        for shader in shaders.iter() {
+            if let Err(error) = self.reload(pipeline_state, shader) {
+                Log::err(format!("{}", error));
+                if result.is_ok() {
+                    result = Err(error);
+                }
+            }
+        }
+        result
+    }
+
     pub fn update(&mut self, dt: f32) {
         self.cache.update(dt)
     }
@@ -1,4 +1,4 @@
-use crate::resource::texture::Texture;
+use crate::resource::texture::{MipMapMode, Texture, TextureColorSpace};
 use crate::{
     core::{
         log::{Log, MessageKind},
@@ -7,6 +7,7 @@ use crate::{
     renderer::{
         cache::TemporaryCache,
         framework::{
+            backend::RenderBackend,
             error::FrameworkError,
             gpu_texture::{Coordinate, GpuTexture, PixelKind},
             state::PipelineState,
@@ -14,38 +15,93 @@ use crate::{
     },
     resource::texture::TextureResource,
 };
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 pub(crate) struct TextureRenderData {
     pub gpu_texture: Rc<RefCell<GpuTexture>>,
     pub modifications_counter: u64,
 }
 
-#[derive(Default)]
 pub struct TextureCache {
     pub(crate) map: TemporaryCache<TextureRenderData>,
+    max_anisotropy: f32,
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+            max_anisotropy: 16.0,
+        }
+    }
+}
+
+fn gpu_pixel_kind(texture: &Texture) -> PixelKind {
+    let pixel_kind = PixelKind::from(texture.pixel_kind());
+    match (texture.color_space(), pixel_kind) {
+        (TextureColorSpace::Srgb, PixelKind::RGB8) => PixelKind::SRGB8,
+        (TextureColorSpace::Srgb, PixelKind::RGBA8) => PixelKind::SRGBA8,
+        _ => pixel_kind,
+    }
+}
+
+/// Returns the amount of mip levels, that should be uploaded to GPU, and their data.
+fn mip_chain(texture: &Texture) -> (usize, &[u8]) {
+    let mip_count = texture.mip_map_mode().mip_count(texture.mip_count());
+    if mip_count == texture.mip_count() {
+        (mip_count as usize, texture.data())
+    } else {
+        (
+            mip_count as usize,
+            texture.mip_chain_data(mip_count as usize),
+        )
+    }
+}
+
+/// Checks whether the mip levels of the texture should be generated by GPU. It is done only for
+/// textures with a single level of data, if their minification filter uses mip mapping.
+fn is_gpu_mip_generation_needed(texture: &Texture) -> bool {
+    texture.mip_map_mode() == MipMapMode::Generate
+        && texture.mip_count() == 1
+        && texture.minification_filter().is_using_mip_mapping()
 }
 
 fn create_gpu_texture(
     state: &PipelineState,
     texture: &Texture,
 ) -> Result<TextureRenderData, FrameworkError> {
-    GpuTexture::new(
-        state,
+    let (mip_count, data) = mip_chain(texture);
+    let mut gpu_texture = state.create_texture(
         texture.kind().into(),
-        PixelKind::from(texture.pixel_kind()),
+        gpu_pixel_kind(texture),
         texture.minification_filter().into(),
         texture.magnification_filter().into(),
-        texture.mip_count() as usize,
-        Some(texture.data()),
-    )
-    .map(|gpu_texture| TextureRenderData {
+        mip_count,
+        Some(data),
+    )?;
+
+    if is_gpu_mip_generation_needed(texture) {
+        gpu_texture.bind_mut(state, 0).generate_mip_maps();
+    }
+
+    Ok(TextureRenderData {
         gpu_texture: Rc::new(RefCell::new(gpu_texture)),
         modifications_counter: texture.modifications_count(),
     })
 }
 
 impl TextureCache {
+    /// Sets the maximum anisotropy level that could be used by textures. Anisotropy level of
+    /// every texture will be limited by this value. Default value is 16.0.
+    pub fn set_max_anisotropy(&mut self, max_anisotropy: f32) {
+        self.max_anisotropy = max_anisotropy.max(1.0);
+    }
+
+    /// Returns the maximum anisotropy level that could be used by textures.
+    pub fn max_anisotropy(&self) -> f32 {
+        self.max_anisotropy
+    }
+
     /// Unconditionally uploads requested texture into GPU memory, previous GPU texture will be automatically
     /// destroyed.
     pub fn upload(
@@ -90,21 +146,26 @@ impl TextureCache {
                     let modifications_count = texture.modifications_count();
                     if entry.modifications_counter != modifications_count {
                         let mut gpu_texture = entry.gpu_texture.borrow_mut();
-                        if let Err(e) = gpu_texture.bind_mut(state, 0).set_data(
+                        let (mip_count, data) = mip_chain(texture);
+                        match gpu_texture.bind_mut(state, 0).set_data(
                             texture.kind().into(),
-                            texture.pixel_kind().into(),
-                            texture.mip_count() as usize,
-                            Some(texture.data()),
+                            gpu_pixel_kind(texture),
+                            mip_count,
+                            Some(data),
                         ) {
-                            Log::writeln(
+                            Ok(binding) => {
+                                if is_gpu_mip_generation_needed(texture) {
+                                    binding.generate_mip_maps();
+                                }
+                                entry.modifications_counter = modifications_count;
+                            }
+                            Err(e) => Log::writeln(
                                 MessageKind::Error,
                                 format!(
                                     "Unable to upload new texture data to GPU. Reason: {:?}",
                                     e
                                 ),
-                            )
-                        } else {
-                            entry.modifications_counter = modifications_count;
+                            ),
                         }
                     }
 
@@ -124,10 +185,11 @@ impl TextureCache {
                             .set_minification_filter(new_min_filter);
                     }
 
-                    if gpu_texture.anisotropy().ne(&texture.anisotropy_level()) {
+                    let new_anisotropy = texture.anisotropy_level().min(self.max_anisotropy);
+                    if gpu_texture.anisotropy().ne(&new_anisotropy) {
                         gpu_texture
                             .bind_mut(state, 0)
-                            .set_anisotropy(texture.anisotropy_level());
+                            .set_anisotropy(new_anisotropy);
                     }
 
                     let new_s_wrap_mode = texture.s_wrap_mode().into();
@@ -170,6 +232,23 @@ impl TextureCache {
         self.map.clear();
     }
 
+    /// Removes GPU textures, which source texture resources no longer exist. Returns estimated
+    /// amount of freed GPU memory in bytes.
+    pub fn evict_unused(&mut self) -> usize {
+        let mut freed_bytes = 0;
+        for i in 0..self.map.buffer.len() {
+            if let Some(entry) = self.map.buffer.get_raw(i) {
+                // The index is shared with the texture resource, so if the entry is the only owner
+                // of it, then the resource is gone.
+                if Arc::strong_count(&entry.self_index) == 1 {
+                    freed_bytes += entry.gpu_texture.borrow().size_bytes();
+                    self.map.buffer.free_raw(i);
+                }
+            }
+        }
+        freed_bytes
+    }
+
     pub fn unload(&mut self, texture: TextureResource) {
         if let Some(texture) = texture.state().data() {
             self.map.remove(&texture.cache_index);
@@ -17,7 +17,7 @@ use crate::{
         gpu_program::{GpuProgram, UniformLocation},
         state::PipelineState,
     },
-    renderer::RenderPassStatistics,
+    renderer::{camera_view_projection_matrix, RenderPassStatistics},
     scene::{camera::Camera, debug::SceneDrawingContext},
 };
 use bytemuck::{Pod, Zeroable};
@@ -91,6 +91,27 @@ impl DebugRenderer {
         framebuffer: &mut FrameBuffer,
         drawing_context: &SceneDrawingContext,
         camera: &Camera,
+        reverse_z: bool,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        self.render_contexts(
+            state,
+            viewport,
+            framebuffer,
+            &[drawing_context],
+            camera,
+            reverse_z,
+        )
+    }
+
+    /// Same as [`Self::render`], but draws lines of every given drawing context in a single draw call.
+    pub(crate) fn render_contexts(
+        &mut self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+        framebuffer: &mut FrameBuffer,
+        drawing_contexts: &[&SceneDrawingContext],
+        camera: &Camera,
+        reverse_z: bool,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
@@ -100,7 +121,10 @@ impl DebugRenderer {
         self.line_indices.clear();
 
         let mut i = 0;
-        for line in drawing_context.lines.iter() {
+        for line in drawing_contexts
+            .iter()
+            .flat_map(|drawing_context| drawing_context.lines.iter())
+        {
             let color = line.color.into();
             self.vertices.push(Vertex {
                 position: line.begin,
@@ -132,8 +156,10 @@ impl DebugRenderer {
             },
             ElementRange::Full,
             |mut program_binding| {
-                program_binding
-                    .set_matrix4(&self.shader.wvp_matrix, &camera.view_projection_matrix());
+                program_binding.set_matrix4(
+                    &self.shader.wvp_matrix,
+                    &camera_view_projection_matrix(camera, reverse_z),
+                );
             },
         )?;
 
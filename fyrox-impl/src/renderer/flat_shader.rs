@@ -26,3 +26,70 @@ impl FlatShader {
         })
     }
 }
+
+/// A variant of the flat shader, that visualizes a channel of the GBuffer.
+pub struct GBufferDebugShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub diffuse_texture: UniformLocation,
+    pub depth_texture: UniformLocation,
+    pub inv_proj_matrix: UniformLocation,
+    pub inv_view_proj_matrix: UniformLocation,
+    pub z_far: UniformLocation,
+    pub far_depth: UniformLocation,
+    pub mode: UniformLocation,
+}
+
+impl GBufferDebugShader {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/gbuffer_debug_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+
+        let program =
+            GpuProgram::from_source(state, "GBufferDebugShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            inv_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjectionMatrix"))?,
+            z_far: program.uniform_location(state, &ImmutableString::new("zFar"))?,
+            far_depth: program.uniform_location(state, &ImmutableString::new("farDepth"))?,
+            mode: program.uniform_location(state, &ImmutableString::new("mode"))?,
+            program,
+        })
+    }
+}
+
+/// A variant of the flat shader, that fills every pixel with non-empty depth with a flat color.
+pub struct WireframeShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub depth_texture: UniformLocation,
+    pub wire_color: UniformLocation,
+    pub far_depth: UniformLocation,
+}
+
+impl WireframeShader {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/wireframe_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+
+        let program =
+            GpuProgram::from_source(state, "WireframeShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            wire_color: program.uniform_location(state, &ImmutableString::new("wireColor"))?,
+            far_depth: program.uniform_location(state, &ImmutableString::new("farDepth"))?,
+            program,
+        })
+    }
+}
@@ -5,6 +5,9 @@
 //! This renderer eventually will replace deferred renderer, because deferred renderer is too restrictive.
 //! For now it is used **only** to render transparent meshes (or any other mesh that has Forward render
 //! path).
+//!
+//! Alpha-blended meshes, which shaders have `OitForward` render pass, could be rendered using
+//! order-independent transparency (see [`OitContext`]).
 
 use crate::{
     core::{
@@ -18,25 +21,38 @@ use crate::{
         apply_material,
         bundle::RenderDataBundleStorage,
         cache::{shader::ShaderCache, texture::TextureCache},
+        camera_view_projection_matrix, depth_range_projection,
         framework::{
-            error::FrameworkError, framebuffer::FrameBuffer, gpu_texture::GpuTexture,
-            state::PipelineState,
+            error::FrameworkError,
+            framebuffer::{BlendParameters, FrameBuffer},
+            gpu_texture::GpuTexture,
+            state::{BlendFactor, BlendFunc, PipelineState},
         },
+        oit::OitRenderer,
         storage::MatrixStorageCache,
         GeometryCache, LightData, MaterialContext, QualitySettings, RenderPassStatistics,
     },
     scene::{
         camera::Camera,
         graph::Graph,
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            directional::DirectionalLight, point::PointLight, spot::SpotLight, LightAttenuation,
+        },
         mesh::RenderPath,
     },
 };
 use fyrox_core::math::Matrix4Ext;
-use std::{cell::RefCell, rc::Rc};
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
 
 pub(crate) struct ForwardRenderer {
     render_pass_name: ImmutableString,
+    oit_render_pass_name: ImmutableString,
+}
+
+/// Everything that is needed to render alpha-blended meshes using order-independent transparency.
+pub(crate) struct OitContext<'a> {
+    pub renderer: &'a OitRenderer,
+    pub framebuffer: &'a mut FrameBuffer,
 }
 
 pub(crate) struct ForwardRenderContext<'a, 'b> {
@@ -51,18 +67,24 @@ pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub viewport: Rect<i32>,
     pub quality_settings: &'a QualitySettings,
     pub white_dummy: Rc<RefCell<GpuTexture>>,
+    pub diffuse_dummy: Rc<RefCell<GpuTexture>>,
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub scene_depth: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
     pub ambient_light: Color,
+    pub soft_particle_scale: f32,
+    pub reverse_z: bool,
+    /// Order-independent transparency is used only if this is set.
+    pub oit: Option<OitContext<'a>>,
 }
 
 impl ForwardRenderer {
     pub(crate) fn new() -> Self {
         Self {
             render_pass_name: ImmutableString::new("Forward"),
+            oit_render_pass_name: ImmutableString::new("OitForward"),
         }
     }
 
@@ -86,15 +108,27 @@ impl ForwardRenderer {
             viewport,
             quality_settings,
             white_dummy,
+            diffuse_dummy,
             normal_dummy,
             black_dummy,
             volume_dummy,
             scene_depth,
             matrix_storage,
             ambient_light,
+            soft_particle_scale,
+            reverse_z,
+            mut oit,
         } = args;
 
-        let initial_view_projection = camera.view_projection_matrix();
+        let initial_view_projection = camera_view_projection_matrix(camera, reverse_z);
+
+        // Shaders linearize depth using the range of the camera, reversed depth range is the same
+        // as the range with swapped planes.
+        let (z_near, z_far) = if reverse_z {
+            (camera.projection().z_far(), camera.projection().z_near())
+        } else {
+            (camera.projection().z_near(), camera.projection().z_far())
+        };
 
         let frustum = Frustum::from_view_projection_matrix(camera.view_projection_matrix())
             .unwrap_or_default();
@@ -110,13 +144,14 @@ impl ForwardRenderer {
                 continue;
             }
 
-            let (radius, half_cone_angle_cos, half_hotspot_angle_cos, color) =
+            let (radius, half_cone_angle_cos, half_hotspot_angle_cos, color, inverse_square) =
                 if let Some(point) = light.cast::<PointLight>() {
                     (
                         point.radius(),
                         std::f32::consts::PI.cos(),
                         std::f32::consts::PI.cos(),
                         point.base_light_ref().color().as_frgb(),
+                        point.base_light_ref().attenuation() == LightAttenuation::InverseSquare,
                     )
                 } else if let Some(spot) = light.cast::<SpotLight>() {
                     (
@@ -124,6 +159,7 @@ impl ForwardRenderer {
                         (spot.hotspot_cone_angle() * 0.5).cos(),
                         (spot.full_cone_angle() * 0.5).cos(),
                         spot.base_light_ref().color().as_frgb(),
+                        spot.base_light_ref().attenuation() == LightAttenuation::InverseSquare,
                     )
                 } else if let Some(directional) = light.cast::<DirectionalLight>() {
                     (
@@ -131,6 +167,8 @@ impl ForwardRenderer {
                         std::f32::consts::PI.cos(),
                         std::f32::consts::PI.cos(),
                         directional.base_light_ref().color().as_frgb(),
+                        // Directional lights are not affected by attenuation.
+                        false,
                     )
                 } else {
                     continue;
@@ -145,87 +183,161 @@ impl ForwardRenderer {
                     Vector4::new(color.x, color.y, color.z, radius);
                 light_data.parameters[light_num] =
                     Vector2::new(half_cone_angle_cos, half_hotspot_angle_cos);
+                light_data.inverse_square_attenuation[light_num] = inverse_square as i32;
 
                 light_data.count += 1;
             }
         }
 
-        for bundle in bundle_storage
-            .bundles
-            .iter()
-            .filter(|b| b.render_path == RenderPath::Forward)
-        {
-            let mut material_state = bundle.material.state();
+        // Order-independent transparency is resolved before any other forward bundle is rendered,
+        // this way sorted transparent meshes are blended over the result of the composition and
+        // not covered by it.
+        let oit_passes: &[bool] = if oit.is_some() {
+            &[true, false]
+        } else {
+            &[false]
+        };
 
-            let Some(material) = material_state.data() else {
-                continue;
-            };
+        for &is_oit_pass in oit_passes {
+            let mut oit_used = false;
 
-            let Some(geometry) = geom_cache.get(state, &bundle.data, bundle.time_to_live) else {
-                continue;
-            };
-
-            let blend_shapes_storage = bundle
-                .data
-                .data_ref()
-                .blend_shapes_container
-                .as_ref()
-                .and_then(|c| c.blend_shape_storage.clone());
-
-            let Some(render_pass) = shader_cache
-                .get(state, material.shader())
-                .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
-            else {
-                continue;
-            };
+            for bundle in bundle_storage
+                .bundles
+                .iter()
+                .filter(|b| b.render_path == RenderPath::Forward)
+            {
+                let mut material_state = bundle.material.state();
 
-            for instance in bundle.instances.iter() {
-                let view_projection = if instance.depth_offset != 0.0 {
-                    let mut projection = camera.projection_matrix();
-                    projection[14] -= instance.depth_offset;
-                    projection * camera.view_matrix()
-                } else {
-                    initial_view_projection
+                let Some(material) = material_state.data() else {
+                    continue;
+                };
+
+                let Some(geometry) = geom_cache.get(state, &bundle.data, bundle.time_to_live)
+                else {
+                    continue;
                 };
 
-                statistics += framebuffer.draw(
-                    geometry,
-                    state,
-                    viewport,
-                    &render_pass.program,
-                    &render_pass.draw_params,
-                    instance.element_range,
-                    |mut program_binding| {
-                        apply_material(MaterialContext {
-                            material,
-                            program_binding: &mut program_binding,
-                            texture_cache,
-                            world_matrix: &instance.world_transform,
-                            view_projection_matrix: &view_projection,
-                            wvp_matrix: &(view_projection * instance.world_transform),
-                            bone_matrices: &instance.bone_matrices,
-                            use_skeletal_animation: bundle.is_skinned,
-                            camera_position: &camera.global_position(),
-                            camera_up_vector: &camera_up,
-                            camera_side_vector: &camera_side,
-                            z_near: camera.projection().z_near(),
-                            z_far: camera.projection().z_far(),
-                            use_pom: quality_settings.use_parallax_mapping,
-                            light_position: &Default::default(),
-                            blend_shapes_storage: blend_shapes_storage.as_ref(),
-                            blend_shapes_weights: &instance.blend_shapes_weights,
-                            normal_dummy: &normal_dummy,
-                            white_dummy: &white_dummy,
-                            black_dummy: &black_dummy,
-                            volume_dummy: &volume_dummy,
-                            matrix_storage,
-                            persistent_identifier: instance.persistent_identifier,
-                            light_data: Some(&light_data),
-                            ambient_light,
-                            scene_depth: Some(&scene_depth),
+                let blend_shapes_storage = bundle
+                    .data
+                    .data_ref()
+                    .blend_shapes_container
+                    .as_ref()
+                    .and_then(|c| c.blend_shape_storage.clone());
+
+                let Some(shader_set) = shader_cache.get(state, material.shader()) else {
+                    continue;
+                };
+
+                // Alpha-blended meshes are rendered in the OIT frame buffer, if their shader supports it.
+                // Such meshes must use blending of the OIT render pass, so blending of the bundle is
+                // ignored.
+                let oit_render_pass = oit
+                    .as_ref()
+                    .filter(|_| {
+                        bundle.blend_func.map_or(true, |func| {
+                            func == BlendFunc::new(
+                                BlendFactor::SrcAlpha,
+                                BlendFactor::OneMinusSrcAlpha,
+                            )
+                        })
+                    })
+                    .and_then(|_| shader_set.render_passes.get(&self.oit_render_pass_name));
+
+                if oit_render_pass.is_some() != is_oit_pass {
+                    continue;
+                }
+
+                let Some(render_pass) = oit_render_pass
+                    .or_else(|| shader_set.render_passes.get(&self.render_pass_name))
+                else {
+                    continue;
+                };
+
+                let target_framebuffer = match oit.as_mut() {
+                    Some(oit) if oit_render_pass.is_some() => {
+                        if !oit_used {
+                            oit.renderer.clear(state, viewport, oit.framebuffer);
+                            oit_used = true;
+                        }
+                        &mut *oit.framebuffer
+                    }
+                    _ => &mut *framebuffer,
+                };
+
+                let draw_params = material.draw_parameters(&render_pass.draw_params);
+                let draw_params = match bundle.blend_func.filter(|_| oit_render_pass.is_none()) {
+                    Some(func) => {
+                        let mut draw_params = draw_params.into_owned();
+                        draw_params.blend = Some(BlendParameters {
+                            func,
+                            equation: draw_params
+                                .blend
+                                .as_ref()
+                                .map(|blend| blend.equation)
+                                .unwrap_or_default(),
                         });
-                    },
-                )?;
+                        Cow::Owned(draw_params)
+                    }
+                    None => draw_params,
+                };
+
+                for instance in bundle.instances.iter() {
+                    let view_projection = if instance.depth_offset != 0.0 {
+                        let mut projection = camera.projection_matrix();
+                        projection[14] -= instance.depth_offset;
+                        depth_range_projection(projection, reverse_z) * camera.view_matrix()
+                    } else {
+                        initial_view_projection
+                    };
+
+                    statistics += target_framebuffer.draw(
+                        geometry,
+                        state,
+                        viewport,
+                        &render_pass.program,
+                        &draw_params,
+                        instance.element_range,
+                        |mut program_binding| {
+                            apply_material(MaterialContext {
+                                material,
+                                program_binding: &mut program_binding,
+                                texture_cache,
+                                world_matrix: &instance.world_transform,
+                                view_projection_matrix: &view_projection,
+                                wvp_matrix: &(view_projection * instance.world_transform),
+                                bone_matrices: &instance.bone_matrices,
+                                use_skeletal_animation: bundle.is_skinned,
+                                instance_matrices: &[],
+                                camera_position: &camera.global_position(),
+                                camera_up_vector: &camera_up,
+                                camera_side_vector: &camera_side,
+                                z_near,
+                                z_far,
+                                use_pom: quality_settings.use_parallax_mapping,
+                                light_position: &Default::default(),
+                                blend_shapes_storage: blend_shapes_storage.as_ref(),
+                                blend_shapes_weights: &instance.blend_shapes_weights,
+                                normal_dummy: &normal_dummy,
+                                white_dummy: &white_dummy,
+                                diffuse_dummy: &diffuse_dummy,
+                                black_dummy: &black_dummy,
+                                volume_dummy: &volume_dummy,
+                                matrix_storage,
+                                persistent_identifier: instance.persistent_identifier,
+                                light_data: Some(&light_data),
+                                ambient_light,
+                                soft_particle_scale,
+                                scene_depth: Some(&scene_depth),
+                            });
+                        },
+                    )?;
+                }
+            }
+
+            if let Some(oit) = oit.as_ref().filter(|_| oit_used) {
+                statistics +=
+                    oit.renderer
+                        .composite(state, viewport, oit.framebuffer, framebuffer)?;
             }
         }
 
@@ -0,0 +1,239 @@
+//! Render backend is a set of operations, that the renderer needs from a graphics API. It is the
+//! first step to support graphics APIs other than OpenGL: the renderer creates its GPU resources,
+//! issues draw calls and presents frames through the trait, instead of calling OpenGL directly.
+//! GPU resources are opaque for the renderer, each backend defines its own types for them.
+//! [`PipelineState`] is the only implementation for now, that is why it is also available as
+//! [`GlBackend`].
+//!
+//! Only the top level of the renderer goes through the trait for now: resource caches (textures,
+//! geometry buffers and shaders), frame buffers and dummy textures of the renderer, final blitting
+//! of frames and presenting them. Render passes (G-Buffer, lighting, shadows, order-independent
+//! transparency, object picking, skybox, user interface, etc.) still use OpenGL types directly,
+//! moving them behind the trait is a subject of future work.
+//!
+//! The trait uses associated types for GPU resources and generic methods, so it cannot be used as
+//! a trait object. The code, that must not depend on a particular backend, should be generic over
+//! the trait instead.
+
+use crate::{
+    core::math::Rect,
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            geometry_buffer::{
+                DrawCallStatistics, ElementRange, GeometryBuffer, GeometryBufferKind,
+            },
+            gpu_program::{GpuProgram, GpuProgramBinding},
+            gpu_texture::{
+                GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+            },
+            state::PipelineState,
+        },
+        PipelineStatistics,
+    },
+    scene::mesh::surface::SurfaceData,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use glutin::{
+    context::PossiblyCurrentContext,
+    prelude::GlSurface,
+    surface::{Surface, WindowSurface},
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// OpenGL render backend.
+pub type GlBackend = PipelineState;
+
+/// A set of operations, that the renderer needs from a graphics API. See module docs for more info.
+pub trait RenderBackend {
+    /// A texture (or a render target) in GPU memory.
+    type Texture;
+    /// A set of textures, that could be used as the target of draw calls.
+    type FrameBuffer;
+    /// Vertices and indices of a mesh in GPU memory.
+    type GeometryBuffer;
+    /// Compiled and linked shader program.
+    type Program;
+    /// Interface to set uniforms of a program before a draw call.
+    type ProgramBinding<'a, 'b>;
+    /// Something, that shows rendered frames (a window surface, for example).
+    type PresentTarget<'a>;
+
+    /// Creates a new texture of the given kind and pixel format. `data` could be `None`, in this
+    /// case the content of the texture is undefined.
+    fn create_texture(
+        &self,
+        kind: GpuTextureKind,
+        pixel_kind: PixelKind,
+        min_filter: MinificationFilter,
+        mag_filter: MagnificationFilter,
+        mip_count: usize,
+        data: Option<&[u8]>,
+    ) -> Result<Self::Texture, FrameworkError>;
+
+    /// Creates a new frame buffer with the given depth (or depth-stencil) and color attachments.
+    fn create_frame_buffer(
+        &self,
+        depth_attachment: Option<(AttachmentKind, Rc<RefCell<Self::Texture>>)>,
+        color_attachments: Vec<Rc<RefCell<Self::Texture>>>,
+    ) -> Result<Self::FrameBuffer, FrameworkError>;
+
+    /// Creates a new geometry buffer and uploads the given surface data to it.
+    fn create_geometry_buffer(
+        &self,
+        data: &SurfaceData,
+        kind: GeometryBufferKind,
+    ) -> Result<Self::GeometryBuffer, FrameworkError>;
+
+    /// Compiles and links a new GPU program from the given sources.
+    fn create_program(
+        &self,
+        name: &str,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Result<Self::Program, FrameworkError>;
+
+    /// Draws the given range of elements of the geometry into the frame buffer using the program.
+    /// `apply_uniforms` is called right before the draw call to set uniforms of the program.
+    #[allow(clippy::too_many_arguments)]
+    fn draw<F: FnOnce(Self::ProgramBinding<'_, '_>)>(
+        &self,
+        framebuffer: &mut Self::FrameBuffer,
+        geometry: &Self::GeometryBuffer,
+        viewport: Rect<i32>,
+        program: &Self::Program,
+        params: &DrawParameters,
+        element_range: ElementRange,
+        apply_uniforms: F,
+    ) -> Result<DrawCallStatistics, FrameworkError>;
+
+    /// Shows the frame, that was rendered into the back buffer, on the given target.
+    fn swap_buffers(&self, target: Self::PresentTarget<'_>) -> Result<(), FrameworkError>;
+
+    /// Returns statistics of the pipeline state changes.
+    fn pipeline_statistics(&self) -> PipelineStatistics;
+
+    /// Returns estimated amount of GPU memory (in bytes) occupied by all textures.
+    fn texture_memory(&self) -> usize;
+
+    /// Returns estimated amount of GPU memory (in bytes) occupied by all geometry buffers.
+    fn geometry_memory(&self) -> usize;
+}
+
+/// Window surface and OpenGL context, that are used to present frames.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct GlPresentTarget<'a> {
+    /// Window surface.
+    pub surface: &'a Surface<WindowSurface>,
+    /// OpenGL context, that is current for the surface.
+    pub context: &'a PossiblyCurrentContext,
+}
+
+impl RenderBackend for PipelineState {
+    type Texture = GpuTexture;
+    type FrameBuffer = FrameBuffer;
+    type GeometryBuffer = GeometryBuffer;
+    type Program = GpuProgram;
+    type ProgramBinding<'a, 'b> = GpuProgramBinding<'a, 'b>;
+    #[cfg(not(target_arch = "wasm32"))]
+    type PresentTarget<'a> = GlPresentTarget<'a>;
+    // Browser presents frames on its own.
+    #[cfg(target_arch = "wasm32")]
+    type PresentTarget<'a> = ();
+
+    fn create_texture(
+        &self,
+        kind: GpuTextureKind,
+        pixel_kind: PixelKind,
+        min_filter: MinificationFilter,
+        mag_filter: MagnificationFilter,
+        mip_count: usize,
+        data: Option<&[u8]>,
+    ) -> Result<GpuTexture, FrameworkError> {
+        GpuTexture::new(
+            self, kind, pixel_kind, min_filter, mag_filter, mip_count, data,
+        )
+    }
+
+    fn create_frame_buffer(
+        &self,
+        depth_attachment: Option<(AttachmentKind, Rc<RefCell<GpuTexture>>)>,
+        color_attachments: Vec<Rc<RefCell<GpuTexture>>>,
+    ) -> Result<FrameBuffer, FrameworkError> {
+        FrameBuffer::new(
+            self,
+            depth_attachment.map(|(kind, texture)| Attachment { kind, texture }),
+            color_attachments
+                .into_iter()
+                .map(|texture| Attachment {
+                    kind: AttachmentKind::Color,
+                    texture,
+                })
+                .collect(),
+        )
+    }
+
+    fn create_geometry_buffer(
+        &self,
+        data: &SurfaceData,
+        kind: GeometryBufferKind,
+    ) -> Result<GeometryBuffer, FrameworkError> {
+        GeometryBuffer::from_surface_data(data, kind, self)
+    }
+
+    fn create_program(
+        &self,
+        name: &str,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Result<GpuProgram, FrameworkError> {
+        GpuProgram::from_source(self, name, vertex_source, fragment_source)
+    }
+
+    fn draw<F: FnOnce(GpuProgramBinding<'_, '_>)>(
+        &self,
+        framebuffer: &mut FrameBuffer,
+        geometry: &GeometryBuffer,
+        viewport: Rect<i32>,
+        program: &GpuProgram,
+        params: &DrawParameters,
+        element_range: ElementRange,
+        apply_uniforms: F,
+    ) -> Result<DrawCallStatistics, FrameworkError> {
+        framebuffer.draw(
+            geometry,
+            self,
+            viewport,
+            program,
+            params,
+            element_range,
+            apply_uniforms,
+        )
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn swap_buffers(&self, target: GlPresentTarget<'_>) -> Result<(), FrameworkError> {
+        target
+            .surface
+            .swap_buffers(target.context)
+            .map_err(|e| FrameworkError::SwapBuffersFailed(format!("{:?}", e)))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn swap_buffers(&self, _target: ()) -> Result<(), FrameworkError> {
+        Ok(())
+    }
+
+    fn pipeline_statistics(&self) -> PipelineStatistics {
+        PipelineState::pipeline_statistics(self)
+    }
+
+    fn texture_memory(&self) -> usize {
+        PipelineState::texture_memory(self)
+    }
+
+    fn geometry_memory(&self) -> usize {
+        PipelineState::geometry_memory(self)
+    }
+}
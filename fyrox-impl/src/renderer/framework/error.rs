@@ -54,6 +54,12 @@ pub enum FrameworkError {
     InvalidFrameBuffer,
     /// OpenGL failed to construct framebuffer.
     FailedToConstructFBO,
+    /// Unable to swap front and back buffers. Usually it means that the graphics context was lost
+    /// and it should be re-created.
+    SwapBuffersFailed(String),
+    /// OpenGL reported an error, contains the error code. Such errors are reported only when
+    /// strict error checking is enabled.
+    GlError(u32),
     /// Custom error. Usually used for internal errors.
     Custom(String),
 }
@@ -121,6 +127,12 @@ impl Display for FrameworkError {
             FrameworkError::FailedToConstructFBO => {
                 write!(f, "OpenGL failed to construct framebuffer.")
             }
+            FrameworkError::SwapBuffersFailed(v) => {
+                write!(f, "Failed to swap buffers: {v}")
+            }
+            FrameworkError::GlError(code) => {
+                write!(f, "OpenGL error: 0x{code:X}")
+            }
             FrameworkError::Custom(v) => {
                 write!(f, "Custom error: {v}")
             }
@@ -5,10 +5,12 @@ use crate::{
         geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
         gpu_program::{GpuProgram, GpuProgramBinding},
         gpu_texture::{CubeMapFace, GpuTexture, GpuTextureKind, PixelElementKind},
-        state::{BlendEquation, BlendFunc, ColorMask, PipelineState, StencilFunc, StencilOp},
+        state::{
+            BlendEquation, BlendFunc, ColorMask, GlKind, PipelineState, StencilFunc, StencilOp,
+        },
     },
 };
-use glow::HasContext;
+use glow::{HasContext, PixelPackData};
 use serde::{Deserialize, Serialize};
 use std::rc::Weak;
 use std::{cell::RefCell, rc::Rc};
@@ -222,6 +224,41 @@ impl FrameBuffer {
         self.fbo
     }
 
+    /// Re-allocates storage of every rectangle attachment of the frame buffer using new size. Textures
+    /// and the frame buffer object itself are kept, so every attachment binding stays valid. Attachments
+    /// that already have requested size are left untouched, so it is safe to resize frame buffers that
+    /// share some attachments.
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        let new_kind = GpuTextureKind::Rectangle { width, height };
+        for attachment in self
+            .depth_attachment
+            .iter()
+            .chain(self.color_attachments.iter())
+        {
+            let mut texture = attachment.texture.borrow_mut();
+            if let GpuTextureKind::Rectangle {
+                width: current_width,
+                height: current_height,
+            } = texture.kind()
+            {
+                if current_width == width && current_height == height {
+                    continue;
+                }
+
+                let pixel_kind = texture.pixel_kind();
+                texture
+                    .bind_mut(state, 0)
+                    .set_data(new_kind, pixel_kind, 1, None)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn clear(
         &mut self,
         state: &PipelineState,
@@ -337,6 +374,71 @@ impl FrameBuffer {
         }
     }
 
+    /// Reads the pixels of the first color attachment (or the back buffer) in the given rectangle and
+    /// returns them as tightly packed RGBA8 array. Rows of the returned array are ordered from top to
+    /// bottom.
+    pub fn read_pixels(&self, state: &PipelineState, rect: Rect<i32>) -> Vec<u8> {
+        let width = rect.w().max(0) as usize;
+        let height = rect.h().max(0) as usize;
+        let row_size = width * 4;
+
+        let mut pixels = vec![0u8; row_size * height];
+
+        state.set_read_framebuffer(self.fbo);
+        state.set_pack_alignment(4);
+
+        unsafe {
+            state.gl.read_pixels(
+                rect.x(),
+                rect.y(),
+                rect.w(),
+                rect.h(),
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                PixelPackData::Slice(pixels.as_mut_slice()),
+            );
+        }
+
+        // OpenGL has origin at the bottom left corner, flip the rows to make the image top-down.
+        let mut flipped = Vec::with_capacity(pixels.len());
+        for row in pixels.chunks_exact(row_size.max(1)).rev() {
+            flipped.extend_from_slice(row);
+        }
+        flipped
+    }
+
+    /// Reads a value of the depth attachment at the given pixel. Coordinates are in pixels with the
+    /// origin at the bottom left corner (as in OpenGL). Returned value is in `[0; 1]` range.
+    ///
+    /// # Platform-specific
+    ///
+    /// OpenGL ES does not support reading of depth values, so this method will always return `1.0`
+    /// there.
+    pub fn read_depth(&self, state: &PipelineState, x: i32, y: i32) -> f32 {
+        if state.gl_kind() == GlKind::OpenGLES {
+            return 1.0;
+        }
+
+        let mut depth = [0u8; 4];
+
+        state.set_read_framebuffer(self.fbo);
+        state.set_pack_alignment(4);
+
+        unsafe {
+            state.gl.read_pixels(
+                x,
+                y,
+                1,
+                1,
+                glow::DEPTH_COMPONENT,
+                glow::FLOAT,
+                PixelPackData::Slice(&mut depth),
+            );
+        }
+
+        f32::from_ne_bytes(depth)
+    }
+
     pub fn draw<F: FnOnce(GpuProgramBinding<'_, '_>)>(
         &mut self,
         geometry: &GeometryBuffer,
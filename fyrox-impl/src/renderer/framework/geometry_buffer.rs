@@ -15,6 +15,8 @@ struct NativeBuffer {
     kind: GeometryBufferKind,
     element_size: usize,
     size_bytes: usize,
+    // Size of the storage of the buffer, it could be larger than the size of actual data.
+    allocated_bytes: usize,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
 }
@@ -22,6 +24,7 @@ struct NativeBuffer {
 impl Drop for NativeBuffer {
     fn drop(&mut self) {
         if let Some(state) = self.state.upgrade() {
+            state.update_geometry_memory(self.allocated_bytes, 0);
             unsafe {
                 state.gl.delete_buffer(self.id);
             }
@@ -35,7 +38,9 @@ pub struct GeometryBuffer {
     buffers: Vec<NativeBuffer>,
     element_buffer_object: glow::Buffer,
     element_count: Cell<usize>,
+    element_buffer_size: Cell<usize>,
     element_kind: ElementKind,
+    index_kind: Cell<IndexKind>,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
 }
@@ -179,6 +184,31 @@ impl ElementKind {
     }
 }
 
+/// Type of indices in the element buffer. 16-bit indices are used automatically when every index
+/// fits into them (which is always true for meshes with less than 65536 vertices), it halves the
+/// size of the element buffer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum IndexKind {
+    U16,
+    U32,
+}
+
+impl IndexKind {
+    fn size(self) -> usize {
+        match self {
+            IndexKind::U16 => size_of::<u16>(),
+            IndexKind::U32 => size_of::<u32>(),
+        }
+    }
+
+    fn gl_type(self) -> u32 {
+        match self {
+            IndexKind::U16 => glow::UNSIGNED_SHORT,
+            IndexKind::U32 => glow::UNSIGNED_INT,
+        }
+    }
+}
+
 pub struct GeometryBufferBinding<'a> {
     state: &'a PipelineState,
     buffer: &'a GeometryBuffer,
@@ -196,7 +226,7 @@ impl<'a> GeometryBufferBinding<'a> {
         assert_eq!(self.buffer.element_kind, ElementKind::Triangle);
         self.buffer.element_count.set(triangles.len());
 
-        unsafe { self.set_elements(array_as_u8_slice(triangles)) }
+        unsafe { self.set_indices(bytemuck::cast_slice(triangles)) }
 
         self
     }
@@ -208,18 +238,37 @@ impl<'a> GeometryBufferBinding<'a> {
         self.buffer.element_count.set(lines.len());
 
         unsafe {
-            self.set_elements(array_as_u8_slice(lines));
+            self.set_indices(bytemuck::cast_slice(lines));
         }
 
         self
     }
 
+    // Uploads the given 32-bit indices, packing them into 16-bit indices if possible.
+    unsafe fn set_indices(&self, indices: &[u32]) {
+        if indices.iter().all(|&index| index <= u16::MAX as u32) {
+            let short_indices = indices
+                .iter()
+                .map(|&index| index as u16)
+                .collect::<Vec<_>>();
+            self.buffer.index_kind.set(IndexKind::U16);
+            self.set_elements(array_as_u8_slice(&short_indices));
+        } else {
+            self.buffer.index_kind.set(IndexKind::U32);
+            self.set_elements(array_as_u8_slice(indices));
+        }
+    }
+
     unsafe fn set_elements(&self, data: &[u8]) {
         scope_profile!();
 
         self.state
             .gl
             .buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, data, glow::DYNAMIC_DRAW);
+
+        self.state
+            .update_geometry_memory(self.buffer.element_buffer_size.get(), data.len());
+        self.buffer.element_buffer_size.set(data.len());
     }
 
     pub fn draw(&self, element_range: ElementRange) -> Result<DrawCallStatistics, FrameworkError> {
@@ -262,11 +311,12 @@ impl<'a> GeometryBufferBinding<'a> {
         scope_profile!();
 
         if index_count > 0 {
-            let indices = (start_index * size_of::<u32>()) as i32;
+            let index_kind = self.buffer.index_kind.get();
+            let indices = (start_index * index_kind.size()) as i32;
             self.state.gl.draw_elements(
                 self.mode(),
                 index_count as i32,
-                glow::UNSIGNED_INT,
+                index_kind.gl_type(),
                 indices,
             );
         }
@@ -280,7 +330,7 @@ impl<'a> GeometryBufferBinding<'a> {
                 self.state.gl.draw_elements_instanced(
                     self.mode(),
                     index_count as i32,
-                    glow::UNSIGNED_INT,
+                    self.buffer.index_kind.get().gl_type(),
                     0,
                     count as i32,
                 )
@@ -331,6 +381,8 @@ impl GeometryBuffer {
                 state
                     .gl
                     .buffer_data_u8_slice(glow::ARRAY_BUFFER, array_as_u8_slice(data), usage);
+                state.update_geometry_memory(buffer.allocated_bytes, size);
+                buffer.allocated_bytes = size;
             } else {
                 state
                     .gl
@@ -368,6 +420,7 @@ impl GeometryBuffer {
 impl Drop for GeometryBuffer {
     fn drop(&mut self) {
         if let Some(state) = self.state.upgrade() {
+            state.update_geometry_memory(self.element_buffer_size.get(), 0);
             unsafe {
                 self.buffers.clear();
 
@@ -458,6 +511,7 @@ impl BufferBuilder {
                     self.kind as u32,
                 );
             }
+            state.update_geometry_memory(0, self.data_size);
         }
 
         let native_buffer = NativeBuffer {
@@ -466,6 +520,7 @@ impl BufferBuilder {
             kind: self.kind,
             element_size: self.element_size,
             size_bytes: self.data_size,
+            allocated_bytes: self.data_size,
             thread_mark: Default::default(),
         };
 
@@ -535,7 +590,9 @@ impl GeometryBufferBuilder {
             buffers,
             element_buffer_object: ebo,
             element_count: Cell::new(0),
+            element_buffer_size: Cell::new(0),
             element_kind: self.element_kind,
+            index_kind: Cell::new(IndexKind::U32),
             thread_mark: PhantomData,
         })
     }
@@ -46,11 +46,70 @@ pub enum BuiltInUniform {
     LightsPosition,
     LightsDirection,
     LightsParameters,
+    LightsInverseSquareAttenuation,
     AmbientLight,
+    SoftParticleScale,
+    DiffuseTextureSrgb,
+    InstanceMatrices,
+    UseInstancing,
     // Must be last.
     Count,
 }
 
+/// Type of an active uniform of a GPU program.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UniformKind {
+    Bool,
+    Int,
+    UnsignedInt,
+    Float,
+    Vector2,
+    Vector3,
+    Vector4,
+    Matrix2,
+    Matrix3,
+    Matrix4,
+    Sampler,
+    /// Any other type, that does not have a specific variant. Contains raw OpenGL type.
+    Other(u32),
+}
+
+impl UniformKind {
+    fn from_gl(utype: u32) -> Self {
+        match utype {
+            glow::BOOL => Self::Bool,
+            glow::INT => Self::Int,
+            glow::UNSIGNED_INT => Self::UnsignedInt,
+            glow::FLOAT => Self::Float,
+            glow::FLOAT_VEC2 => Self::Vector2,
+            glow::FLOAT_VEC3 => Self::Vector3,
+            glow::FLOAT_VEC4 => Self::Vector4,
+            glow::FLOAT_MAT2 => Self::Matrix2,
+            glow::FLOAT_MAT3 => Self::Matrix3,
+            glow::FLOAT_MAT4 => Self::Matrix4,
+            glow::SAMPLER_1D
+            | glow::SAMPLER_2D
+            | glow::SAMPLER_3D
+            | glow::SAMPLER_CUBE
+            | glow::SAMPLER_2D_SHADOW
+            | glow::INT_SAMPLER_2D
+            | glow::UNSIGNED_INT_SAMPLER_2D => Self::Sampler,
+            _ => Self::Other(utype),
+        }
+    }
+}
+
+/// Description of an active uniform of a GPU program.
+#[derive(Clone, Debug)]
+pub struct ActiveUniform {
+    /// Name of the uniform, as it is written in the shader source. Array uniforms have `[0]` suffix.
+    pub name: String,
+    /// Type of the uniform.
+    pub kind: UniformKind,
+    /// Count of elements for array uniforms, `1` for non-array uniforms.
+    pub size: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct UniformLocation {
     id: glow::UniformLocation,
@@ -476,10 +535,20 @@ fn fetch_built_in_uniform_locations(
         fetch_uniform_location(state, program, "fyrox_lightsDirection");
     locations[BuiltInUniform::LightsParameters as usize] =
         fetch_uniform_location(state, program, "fyrox_lightsParameters");
+    locations[BuiltInUniform::LightsInverseSquareAttenuation as usize] =
+        fetch_uniform_location(state, program, "fyrox_lightsInverseSquareAttenuation");
     locations[BuiltInUniform::AmbientLight as usize] =
         fetch_uniform_location(state, program, "fyrox_ambientLightColor");
     locations[BuiltInUniform::LightPosition as usize] =
         fetch_uniform_location(state, program, "fyrox_lightPosition");
+    locations[BuiltInUniform::SoftParticleScale as usize] =
+        fetch_uniform_location(state, program, "fyrox_softParticleScale");
+    locations[BuiltInUniform::DiffuseTextureSrgb as usize] =
+        fetch_uniform_location(state, program, "fyrox_diffuseTextureSrgb");
+    locations[BuiltInUniform::InstanceMatrices as usize] =
+        fetch_uniform_location(state, program, "fyrox_instanceMatrices");
+    locations[BuiltInUniform::UseInstancing as usize] =
+        fetch_uniform_location(state, program, "fyrox_useInstancing");
 
     locations
 }
@@ -548,6 +617,16 @@ impl GpuProgram {
         }
     }
 
+    /// Returns `true` if the program reads instance matrices (`fyrox_useInstancing` and
+    /// `fyrox_instanceMatrices` built-in uniforms), and thus can be used for instanced draw calls.
+    pub fn supports_instancing(&self) -> bool {
+        self.built_in_uniform_locations[BuiltInUniform::UseInstancing as usize].is_some()
+            && self.built_in_uniform_locations[BuiltInUniform::InstanceMatrices as usize].is_some()
+    }
+
+    /// Tries to find a location of a uniform with the given name. Locations are cached, so there's
+    /// only one request to the driver per each unique name. Returns [`None`] if there's no such
+    /// uniform or if it was optimized out by the shader compiler.
     pub fn uniform_location_internal(
         &self,
         state: &PipelineState,
@@ -566,6 +645,7 @@ impl GpuProgram {
         }
     }
 
+    /// Same as [`Self::uniform_location_internal`], but returns an error if there's no such uniform.
     pub fn uniform_location(
         &self,
         state: &PipelineState,
@@ -575,6 +655,23 @@ impl GpuProgram {
             .ok_or_else(|| FrameworkError::UnableToFindShaderUniform(name.deref().to_owned()))
     }
 
+    /// Returns a list of every active uniform of the program. It could be used to enumerate custom
+    /// shader parameters and bind them by name, using [`Self::uniform_location`] and typed setters
+    /// of [`GpuProgramBinding`].
+    pub fn active_uniforms(&self, state: &PipelineState) -> Vec<ActiveUniform> {
+        unsafe {
+            let count = state.gl.get_active_uniforms(self.id);
+            (0..count)
+                .filter_map(|index| state.gl.get_active_uniform(self.id, index))
+                .map(|uniform| ActiveUniform {
+                    name: uniform.name,
+                    kind: UniformKind::from_gl(uniform.utype),
+                    size: uniform.size.max(0) as usize,
+                })
+                .collect()
+        }
+    }
+
     pub fn bind<'a, 'b>(&'b self, state: &'a PipelineState) -> GpuProgramBinding<'a, 'b> {
         state.set_program(Some(self.id));
         GpuProgramBinding {
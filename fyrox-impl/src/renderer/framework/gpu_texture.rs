@@ -385,6 +385,8 @@ pub struct GpuTexture {
     r_wrap_mode: WrapMode,
     anisotropy: f32,
     pixel_kind: PixelKind,
+    // Estimated amount of memory (in bytes) occupied by all mip levels of the texture.
+    size_bytes: usize,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
 }
@@ -626,6 +628,8 @@ impl CubeMapFace {
 }
 
 impl<'a> TextureBinding<'a> {
+    /// Sets new anisotropy level, the value will be clamped to `[1.0; max]` range, where `max` is
+    /// the maximum value supported by current GPU.
     pub fn set_anisotropy(self, anisotropy: f32) -> Self {
         unsafe {
             let max = self
@@ -633,9 +637,9 @@ impl<'a> TextureBinding<'a> {
                 .gl
                 .get_parameter_f32(glow::MAX_TEXTURE_MAX_ANISOTROPY_EXT);
             self.state.gl.tex_parameter_f32(
-                glow::TEXTURE_2D,
+                self.texture.kind.gl_texture_target(),
                 glow::TEXTURE_MAX_ANISOTROPY_EXT,
-                anisotropy.clamp(0.0, max),
+                anisotropy.clamp(1.0, max.max(1.0)),
             );
 
             // Set it to requested value, instead of hardware-limited. This will allow
@@ -660,6 +664,40 @@ impl<'a> TextureBinding<'a> {
         self
     }
 
+    /// Generates full mip chain on GPU, using current content of the first mip level. It is useful
+    /// for textures without precomputed mips, such as procedural textures. Compressed textures can't
+    /// have their mips generated, so this method does nothing for them.
+    pub fn generate_mip_maps(self) -> Self {
+        if self.texture.pixel_kind.is_compressed() {
+            return self;
+        }
+
+        let max_size = match self.texture.kind {
+            GpuTextureKind::Line { length } => length,
+            GpuTextureKind::Rectangle { width, height }
+            | GpuTextureKind::Cube { width, height } => width.max(height),
+            GpuTextureKind::Volume {
+                width,
+                height,
+                depth,
+            } => width.max(height).max(depth),
+        };
+        let max_level = max_size.max(1).ilog2();
+
+        unsafe {
+            let target = self.texture.kind.gl_texture_target();
+
+            self.state
+                .gl
+                .tex_parameter_i32(target, glow::TEXTURE_BASE_LEVEL, 0);
+            self.state
+                .gl
+                .tex_parameter_i32(target, glow::TEXTURE_MAX_LEVEL, max_level as i32);
+            self.state.gl.generate_mipmap(target);
+        }
+        self
+    }
+
     pub fn set_magnification_filter(self, mag_filter: MagnificationFilter) -> Self {
         unsafe {
             self.state.gl.tex_parameter_i32(
@@ -775,6 +813,9 @@ impl<'a> TextureBinding<'a> {
 
         self.texture.kind = kind;
         self.texture.pixel_kind = pixel_kind;
+        self.state
+            .update_texture_memory(self.texture.size_bytes, desired_byte_count);
+        self.texture.size_bytes = desired_byte_count;
 
         let target = kind.gl_texture_target();
 
@@ -1066,6 +1107,7 @@ impl GpuTexture {
                 r_wrap_mode: WrapMode::Repeat,
                 anisotropy: 1.0,
                 pixel_kind,
+                size_bytes: 0,
                 thread_mark: PhantomData,
             };
 
@@ -1160,11 +1202,18 @@ impl GpuTexture {
     pub fn pixel_kind(&self) -> PixelKind {
         self.pixel_kind
     }
+
+    /// Returns estimated amount of GPU memory (in bytes) occupied by the texture, including all its
+    /// mip levels. Actual amount of memory depends on the driver and could be larger.
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
 }
 
 impl Drop for GpuTexture {
     fn drop(&mut self) {
         if let Some(state) = self.state.upgrade() {
+            state.update_texture_memory(self.size_bytes, 0);
             unsafe {
                 state.gl.delete_texture(self.texture);
             }
@@ -1,8 +1,10 @@
 #![allow(missing_docs)] // TODO
 
+pub mod backend;
 pub mod error;
 pub mod framebuffer;
 pub mod geometry_buffer;
 pub mod gpu_program;
 pub mod gpu_texture;
+pub mod query;
 pub mod state;
@@ -0,0 +1,78 @@
+use crate::renderer::framework::{
+    error::FrameworkError,
+    state::{GlKind, PipelineState},
+};
+use glow::HasContext;
+use std::rc::Weak;
+
+/// Returns `true` if the current context supports timer queries (see [`TimerQuery`]). Timer queries
+/// are the part of OpenGL 3.3, but OpenGL ES and WebGL need a special extension.
+pub fn is_timer_query_supported(state: &PipelineState) -> bool {
+    if state.gl_kind() == GlKind::OpenGL {
+        return true;
+    }
+    let extensions = state.gl.supported_extensions();
+    extensions.contains("GL_EXT_disjoint_timer_query")
+        || extensions.contains("EXT_disjoint_timer_query_webgl2")
+}
+
+/// A query, that measures the time (on GPU side) taken by every command between [`TimerQuery::begin`]
+/// and [`TimerQuery::end`]. The result is not available immediately, GPU works asynchronously and
+/// usually lags behind CPU by a frame or two, use [`TimerQuery::try_get_result`] to fetch it without
+/// stalling. Only one timer query could be active at a time.
+pub struct TimerQuery {
+    state: Weak<PipelineState>,
+    id: glow::Query,
+}
+
+impl TimerQuery {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        unsafe {
+            Ok(Self {
+                state: state.weak(),
+                id: state.gl.create_query()?,
+            })
+        }
+    }
+
+    pub fn begin(&self, state: &PipelineState) {
+        unsafe {
+            state.gl.begin_query(glow::TIME_ELAPSED, self.id);
+        }
+    }
+
+    pub fn end(&self, state: &PipelineState) {
+        unsafe {
+            state.gl.end_query(glow::TIME_ELAPSED);
+        }
+    }
+
+    /// Returns elapsed time in nanoseconds, if the result is available.
+    pub fn try_get_result(&self, state: &PipelineState) -> Option<u32> {
+        unsafe {
+            if state
+                .gl
+                .get_query_parameter_u32(self.id, glow::QUERY_RESULT_AVAILABLE)
+                != 0
+            {
+                Some(
+                    state
+                        .gl
+                        .get_query_parameter_u32(self.id, glow::QUERY_RESULT),
+                )
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl Drop for TimerQuery {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.upgrade() {
+            unsafe {
+                state.gl.delete_query(self.id);
+            }
+        }
+    }
+}
@@ -35,6 +35,24 @@ float S_LightDistanceAttenuation(float distance, float radius)
     return attenuation;
 }
 
+// Inverse-square attenuation with smooth window, that makes light to fade out at given radius.
+// Karis 2013, "Real Shading in Unreal Engine 4".
+float S_InverseSquareLightAttenuation(float distance, float radius)
+{
+    float ratio = distance / radius;
+    float window = clamp(1.0 - ratio * ratio * ratio * ratio, 0.0, 1.0);
+    return window * window / max(distance * distance, 0.0001);
+}
+
+float S_LightAttenuation(bool inverseSquare, float distance, float radius)
+{
+    if (inverseSquare) {
+        return S_InverseSquareLightAttenuation(distance, radius);
+    } else {
+        return S_LightDistanceAttenuation(distance, radius);
+    }
+}
+
 // Projects world space position (typical use case) by given matrix.
 vec3 S_Project(vec3 worldPosition, mat4 matrix)
 {
@@ -59,6 +77,19 @@ vec3 S_UnProject(vec3 screenPos, mat4 matrix)
     return position.xyz / position.w;
 }
 
+// Returns normalized vector from a fragment with given screen position to the observer. It is
+// computed using a point on the near clipping plane, so it works for both perspective and
+// orthographic projections (for the latter it is the same for every fragment).
+vec3 S_ViewVector(vec3 screenPos, vec3 fragmentPosition, mat4 invViewProj)
+{
+    // Reversed depth range (reverse-Z) flips the handedness of the matrix, in this case the near
+    // plane is at the far end of the depth range.
+    float nearPlaneDepth = determinant(invViewProj) > 0.0 ? 1.0 : 0.0;
+    vec3 nearPlanePosition = S_UnProject(vec3(screenPos.xy, nearPlaneDepth), invViewProj);
+
+    return normalize(nearPlanePosition - fragmentPosition);
+}
+
 float S_DistributionGGX(vec3 N, vec3 H, float roughness)
 {
     float a = roughness * roughness;
@@ -329,6 +360,18 @@ vec4 S_SRGBToLinear(vec4 color) {
     return vec4(rgb, color.a);
 }
 
+// Returns sRGB color of a value fetched from a diffuse texture. Textures with sRGB color space are
+// converted into linear color space by the GPU when sampled, so the conversion has to be reverted.
+vec4 S_DiffuseToSRGB(vec4 color, bool isSrgbTexture) {
+    return isSrgbTexture ? S_LinearToSRGB(color) : color;
+}
+
+// Returns linear color of a value fetched from a diffuse texture. Textures with sRGB color space are
+// already converted into linear color space by the GPU when sampled.
+vec4 S_DiffuseToLinear(vec4 color, bool isSrgbTexture) {
+    return isSrgbTexture ? color : S_SRGBToLinear(color);
+}
+
 float S_Luminance(vec3 x) {
     return dot(x, vec3(0.299, 0.587, 0.114));
 }
@@ -1,3 +1,4 @@
+use crate::renderer::framework::error::FrameworkError;
 use crate::renderer::PipelineStatistics;
 use crate::{
     core::{color::Color, math::Rect, reflect::prelude::*, visitor::prelude::*},
@@ -217,6 +218,8 @@ struct InnerState {
     polygon_fill_mode: PolygonFillMode,
 
     framebuffer: Option<glow::Framebuffer>,
+    read_framebuffer: Option<glow::Framebuffer>,
+    pack_alignment: i32,
     viewport: Rect<i32>,
 
     blend_func: BlendFunc,
@@ -233,6 +236,9 @@ struct InnerState {
 
     frame_statistics: PipelineStatistics,
     gl_kind: GlKind,
+
+    texture_memory: usize,
+    geometry_memory: usize,
 }
 
 impl InnerState {
@@ -254,6 +260,8 @@ impl InnerState {
             polygon_face: Default::default(),
             polygon_fill_mode: Default::default(),
             framebuffer: None,
+            read_framebuffer: None,
+            pack_alignment: 4,
             blend_func: Default::default(),
             viewport: Rect::new(0, 0, 1, 1),
             program: Default::default(),
@@ -265,6 +273,8 @@ impl InnerState {
             frame_statistics: Default::default(),
             blend_equation: Default::default(),
             gl_kind,
+            texture_memory: 0,
+            geometry_memory: 0,
         }
     }
 }
@@ -593,6 +603,8 @@ impl PipelineState {
         let mut state = self.state.borrow_mut();
         if state.framebuffer != framebuffer {
             state.framebuffer = framebuffer;
+            // Binding to `GL_FRAMEBUFFER` changes both draw and read bindings.
+            state.read_framebuffer = framebuffer;
 
             state.frame_statistics.framebuffer_binding_changes += 1;
 
@@ -603,6 +615,35 @@ impl PipelineState {
         }
     }
 
+    /// Sets a frame buffer that will be used as a source for pixel read operations.
+    pub fn set_read_framebuffer(&self, framebuffer: Option<glow::Framebuffer>) {
+        let mut state = self.state.borrow_mut();
+        if state.read_framebuffer != framebuffer {
+            state.read_framebuffer = framebuffer;
+
+            state.frame_statistics.framebuffer_binding_changes += 1;
+
+            unsafe {
+                self.gl
+                    .bind_framebuffer(glow::READ_FRAMEBUFFER, state.read_framebuffer)
+            }
+        }
+    }
+
+    /// Sets the alignment requirements for the start of each pixel row in memory for pixel read
+    /// operations.
+    pub fn set_pack_alignment(&self, alignment: i32) {
+        let mut state = self.state.borrow_mut();
+        if state.pack_alignment != alignment {
+            state.pack_alignment = alignment;
+
+            unsafe {
+                self.gl
+                    .pixel_store_i32(glow::PACK_ALIGNMENT, state.pack_alignment)
+            }
+        }
+    }
+
     pub fn set_viewport(&self, viewport: Rect<i32>) {
         let mut state = self.state.borrow_mut();
         if state.viewport != viewport {
@@ -936,8 +977,9 @@ impl PipelineState {
             mask |= glow::STENCIL_BUFFER_BIT;
         }
 
+        self.set_read_framebuffer(source);
+
         unsafe {
-            self.gl.bind_framebuffer(glow::READ_FRAMEBUFFER, source);
             self.gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, dest);
             self.gl.blit_framebuffer(
                 src_x0,
@@ -999,4 +1041,49 @@ impl PipelineState {
     pub fn pipeline_statistics(&self) -> PipelineStatistics {
         self.state.borrow().frame_statistics
     }
+
+    /// Returns estimated amount of GPU memory (in bytes) occupied by all alive textures.
+    pub fn texture_memory(&self) -> usize {
+        self.state.borrow().texture_memory
+    }
+
+    /// Returns estimated amount of GPU memory (in bytes) occupied by all alive vertex and index
+    /// buffers.
+    pub fn geometry_memory(&self) -> usize {
+        self.state.borrow().geometry_memory
+    }
+
+    pub(crate) fn update_texture_memory(&self, old_size: usize, new_size: usize) {
+        let mut state = self.state.borrow_mut();
+        state.texture_memory = state.texture_memory.saturating_sub(old_size) + new_size;
+    }
+
+    pub(crate) fn update_geometry_memory(&self, old_size: usize, new_size: usize) {
+        let mut state = self.state.borrow_mut();
+        state.geometry_memory = state.geometry_memory.saturating_sub(old_size) + new_size;
+    }
+
+    /// Fetches all pending OpenGL errors and returns the first one (if any).
+    pub fn check_error(&self) -> Result<(), FrameworkError> {
+        let mut first_error = None;
+        // Limit the amount of iterations, because lost context could report errors infinitely.
+        for _ in 0..32 {
+            let error = unsafe { self.gl.get_error() };
+            if error == glow::NO_ERROR {
+                break;
+            }
+            first_error.get_or_insert(error);
+        }
+        match first_error {
+            Some(error) => Err(FrameworkError::GlError(error)),
+            None => Ok(()),
+        }
+    }
+
+    /// Blocks until all previously issued commands are fully executed by GPU.
+    pub fn finish(&self) {
+        unsafe {
+            self.gl.finish();
+        }
+    }
 }
@@ -11,7 +11,7 @@ use crate::{
             framebuffer::{DrawParameters, FrameBuffer},
             geometry_buffer::{GeometryBuffer, GeometryBufferKind},
             gpu_program::{GpuProgram, UniformLocation},
-            gpu_texture::GpuTexture,
+            gpu_texture::{GpuTexture, GpuTextureKind},
             state::PipelineState,
         },
         RenderPassStatistics,
@@ -84,6 +84,13 @@ impl FxaaRenderer {
             0.0,
         ));
 
+        let inverse_frame_size = match frame_texture.borrow().kind() {
+            GpuTextureKind::Rectangle { width, height } => {
+                Vector2::new(1.0 / width as f32, 1.0 / height as f32)
+            }
+            _ => Vector2::new(1.0 / viewport.w() as f32, 1.0 / viewport.h() as f32),
+        };
+
         statistics += frame_buffer.draw(
             &self.quad,
             state,
@@ -102,10 +109,7 @@ impl FxaaRenderer {
             |mut program_binding| {
                 program_binding
                     .set_matrix4(&self.shader.wvp_matrix, &frame_matrix)
-                    .set_vector2(
-                        &self.shader.inverse_screen_size,
-                        &Vector2::new(1.0 / viewport.w() as f32, 1.0 / viewport.h() as f32),
-                    )
+                    .set_vector2(&self.shader.inverse_screen_size, &inverse_frame_size)
                     .set_texture(&self.shader.screen_texture, &frame_texture);
             },
         )?;
@@ -2,9 +2,14 @@
 //!
 //! RT0: sRGBA8 - Diffuse color (xyz)
 //! RT1: RGBA8 - Normal (xyz)
-//! RT2: RGBA16F - Ambient light + emission (both in xyz)
+//! RT2: RGBA16F - Ambient light (xyz)
 //! RT3: RGBA8 - Metallic (x) + Roughness (y) + Ambient Occlusion (z)
 //! RT4: R8UI - Decal mask (x)
+//! RT5: RGBA16F - Emission (xyz)
+//!
+//! Emission is stored separately from ambient light, because it must not be affected by diffuse
+//! color and ambient occlusion. Shaders of the GBuffer pass must write every render target, including
+//! the emission, otherwise the content of the target is undefined for the rendered pixels.
 //!
 //! Every alpha channel is used for layer blending for terrains. This is inefficient, but for
 //! now I don't know better solution.
@@ -19,15 +24,16 @@ use crate::{
     },
     renderer::{
         apply_material,
-        bundle::RenderDataBundleStorage,
+        bundle::{PersistentIdentifier, RenderDataBundleStorage, SurfaceInstanceData},
         cache::shader::ShaderCache,
+        camera_view_projection_matrix, depth_func, depth_range_projection, far_plane_depth,
         framework::{
             error::FrameworkError,
             framebuffer::{
                 Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
             },
             geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
-            gpu_program::GpuProgramBinding,
+            gpu_program::{GpuProgram, GpuProgramBinding},
             gpu_texture::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
@@ -70,12 +76,27 @@ pub(crate) struct GBufferRenderContext<'a, 'b> {
     #[allow(dead_code)]
     pub environment_dummy: Rc<RefCell<GpuTexture>>,
     pub white_dummy: Rc<RefCell<GpuTexture>>,
+    pub diffuse_dummy: Rc<RefCell<GpuTexture>>,
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub use_parallax_mapping: bool,
     pub graph: &'b Graph,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub reverse_z: bool,
+}
+
+/// Checks whether the instance can be drawn in a single instanced draw call with other instances of
+/// the same bundle. The instance matrix is the only per-instance data, that is available for instanced
+/// draw calls. Programs, that do not read instance matrices, would draw every instance at the origin,
+/// so they're drawn instance by instance.
+fn is_instanceable(program: &GpuProgram, instance: &SurfaceInstanceData) -> bool {
+    program.supports_instancing()
+        && instance.instanced
+        && instance.bone_matrices.is_empty()
+        && instance.blend_shapes_weights.is_empty()
+        && instance.depth_offset == 0.0
+        && matches!(instance.element_range, ElementRange::Full)
 }
 
 impl GBuffer {
@@ -170,6 +191,20 @@ impl GBuffer {
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
 
+        let mut emission_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        emission_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
         let framebuffer = FrameBuffer::new(
             state,
             Some(Attachment {
@@ -197,6 +232,10 @@ impl GBuffer {
                     kind: AttachmentKind::Color,
                     texture: Rc::new(RefCell::new(decal_mask_texture)),
                 },
+                Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(emission_texture)),
+                },
             ],
         )?;
 
@@ -230,6 +269,21 @@ impl GBuffer {
         })
     }
 
+    /// Changes the size of the G-Buffer, reusing existing textures and frame buffers.
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        // Decal frame buffer shares its textures with the main frame buffer, so it will be
+        // resized as well.
+        self.framebuffer.resize(state, width, height)?;
+        self.width = width as i32;
+        self.height = height as i32;
+        Ok(())
+    }
+
     pub fn framebuffer(&self) -> &FrameBuffer {
         &self.framebuffer
     }
@@ -258,6 +312,10 @@ impl GBuffer {
         self.framebuffer.color_attachments()[4].texture.clone()
     }
 
+    pub fn emission_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[5].texture.clone()
+    }
+
     pub(crate) fn fill(
         &mut self,
         args: GBufferRenderContext,
@@ -275,24 +333,30 @@ impl GBuffer {
             shader_cache,
             use_parallax_mapping,
             white_dummy,
+            diffuse_dummy,
             normal_dummy,
             black_dummy,
             volume_dummy,
             graph,
             matrix_storage,
+            reverse_z,
             ..
         } = args;
 
+        // Depth function stays the same for every pass of the camera that uses the depth buffer of
+        // G-Buffer, the renderer restores the default one when the camera is rendered.
+        state.set_depth_func(depth_func(reverse_z));
+
         let viewport = Rect::new(0, 0, self.width, self.height);
         self.framebuffer.clear(
             state,
             viewport,
             Some(Color::from_rgba(0, 0, 0, 0)),
-            Some(1.0),
+            Some(far_plane_depth(reverse_z)),
             Some(0),
         );
 
-        let initial_view_projection = camera.view_projection_matrix();
+        let initial_view_projection = camera_view_projection_matrix(camera, reverse_z);
 
         let inv_view = camera.inv_view_matrix().unwrap();
 
@@ -328,12 +392,79 @@ impl GBuffer {
                 continue;
             };
 
-            for instance in bundle.instances.iter() {
+            let draw_params = material.draw_parameters(&render_pass.draw_params);
+
+            let (mut instanced, mut separate): (Vec<_>, Vec<_>) = bundle
+                .instances
+                .iter()
+                .partition(|instance| is_instanceable(&render_pass.program, instance));
+
+            // There's no need to use instancing for a single instance.
+            if instanced.len() == 1 {
+                separate.append(&mut instanced);
+            }
+
+            if !instanced.is_empty() {
+                let instance_matrices = instanced
+                    .iter()
+                    .map(|instance| instance.world_transform)
+                    .collect::<Vec<_>>();
+
+                let apply_uniforms = |mut program_binding: GpuProgramBinding| {
+                    apply_material(MaterialContext {
+                        material,
+                        program_binding: &mut program_binding,
+                        texture_cache,
+                        matrix_storage,
+                        world_matrix: &Matrix4::identity(),
+                        view_projection_matrix: &initial_view_projection,
+                        wvp_matrix: &initial_view_projection,
+                        bone_matrices: &[],
+                        use_skeletal_animation: false,
+                        instance_matrices: &instance_matrices,
+                        camera_position: &camera.global_position(),
+                        camera_up_vector: &camera_up,
+                        camera_side_vector: &camera_side,
+                        z_near: camera.projection().z_near(),
+                        use_pom: use_parallax_mapping,
+                        light_position: &Default::default(),
+                        blend_shapes_storage: None,
+                        blend_shapes_weights: &[],
+                        normal_dummy: &normal_dummy,
+                        white_dummy: &white_dummy,
+                        diffuse_dummy: &diffuse_dummy,
+                        black_dummy: &black_dummy,
+                        volume_dummy: &volume_dummy,
+                        persistent_identifier: PersistentIdentifier::new_instanced(
+                            instanced
+                                .iter()
+                                .map(|instance| instance.persistent_identifier),
+                        ),
+                        light_data: None,
+                        ambient_light: Color::WHITE, // TODO
+                        soft_particle_scale: 1.0,
+                        scene_depth: None, // TODO. Add z-pre-pass.
+                        z_far: camera.projection().z_far(),
+                    });
+                };
+
+                statistics += self.framebuffer.draw_instances(
+                    instanced.len(),
+                    geometry,
+                    state,
+                    viewport,
+                    &render_pass.program,
+                    &draw_params,
+                    apply_uniforms,
+                );
+            }
+
+            for instance in separate {
                 let apply_uniforms = |mut program_binding: GpuProgramBinding| {
                     let view_projection = if instance.depth_offset != 0.0 {
                         let mut projection = camera.projection_matrix();
                         projection[14] -= instance.depth_offset;
-                        projection * camera.view_matrix()
+                        depth_range_projection(projection, reverse_z) * camera.view_matrix()
                     } else {
                         initial_view_projection
                     };
@@ -348,6 +479,7 @@ impl GBuffer {
                         wvp_matrix: &(view_projection * instance.world_transform),
                         bone_matrices: &instance.bone_matrices,
                         use_skeletal_animation: bundle.is_skinned,
+                        instance_matrices: &[],
                         camera_position: &camera.global_position(),
                         camera_up_vector: &camera_up,
                         camera_side_vector: &camera_side,
@@ -358,12 +490,14 @@ impl GBuffer {
                         blend_shapes_weights: &instance.blend_shapes_weights,
                         normal_dummy: &normal_dummy,
                         white_dummy: &white_dummy,
+                        diffuse_dummy: &diffuse_dummy,
                         black_dummy: &black_dummy,
                         volume_dummy: &volume_dummy,
                         persistent_identifier: instance.persistent_identifier,
                         light_data: None,
                         ambient_light: Color::WHITE, // TODO
-                        scene_depth: None,           // TODO. Add z-pre-pass.
+                        soft_particle_scale: 1.0,
+                        scene_depth: None, // TODO. Add z-pre-pass.
                         z_far: camera.projection().z_far(),
                     });
                 };
@@ -373,7 +507,7 @@ impl GBuffer {
                     state,
                     viewport,
                     &render_pass.program,
-                    &render_pass.draw_params,
+                    &draw_params,
                     instance.element_range,
                     apply_uniforms,
                 )?;
@@ -428,7 +562,7 @@ impl GBuffer {
                             decal
                                 .diffuse_texture()
                                 .and_then(|t| texture_cache.get(state, t))
-                                .unwrap_or(&white_dummy),
+                                .unwrap_or(&diffuse_dummy),
                         )
                         .set_texture(
                             &shader.normal_texture,
@@ -0,0 +1,132 @@
+//! GPU timers measure the time spent by GPU on groups of render passes (see [`GpuPass`]). GPU works
+//! asynchronously, so results of the timer queries are read a few frames after they were issued.
+//! Queries of every frame are kept in a ring of [`LATENCY`] frames, when a frame slot is reused, its
+//! results are collected, and the queries are recycled.
+
+use crate::renderer::{
+    framework::{
+        error::FrameworkError,
+        query::{is_timer_query_supported, TimerQuery},
+        state::PipelineState,
+    },
+    GpuPass,
+};
+
+/// Amount of frames between issuing a query and reading its result.
+const LATENCY: usize = 3;
+
+#[derive(Default)]
+pub(crate) struct GpuTimers {
+    enabled: bool,
+    free: Vec<TimerQuery>,
+    frames: [Vec<(GpuPass, TimerQuery)>; LATENCY],
+    current: usize,
+    // There is a query in the current frame slot, that was started, but not ended yet.
+    active: bool,
+    pass_times: [f32; GpuPass::COUNT],
+}
+
+impl GpuTimers {
+    /// Enables or disables the timers. Returns `false` if the timers are not supported and cannot
+    /// be enabled.
+    pub fn set_enabled(&mut self, state: &PipelineState, enabled: bool) -> bool {
+        if enabled && !is_timer_query_supported(state) {
+            return false;
+        }
+
+        if !enabled {
+            self.discard_active(state);
+            self.free.clear();
+            for frame in self.frames.iter_mut() {
+                frame.clear();
+            }
+            self.pass_times = Default::default();
+        }
+
+        self.enabled = enabled;
+
+        true
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Moves to the next frame slot and collects the results of the queries issued in it. If some
+    /// result is still not available, the previous times are kept. A query, that was left running
+    /// by a pass that failed in the previous frame, is discarded.
+    pub fn begin_frame(&mut self, state: &PipelineState) {
+        if !self.enabled {
+            return;
+        }
+
+        self.discard_active(state);
+
+        self.current = (self.current + 1) % LATENCY;
+
+        let frame = std::mem::take(&mut self.frames[self.current]);
+        if !frame.is_empty() {
+            let mut pass_times = [0.0; GpuPass::COUNT];
+            let mut complete = true;
+            for (pass, query) in frame.iter() {
+                match query.try_get_result(state) {
+                    Some(nanoseconds) => pass_times[*pass as usize] += nanoseconds as f32 * 1.0e-9,
+                    None => complete = false,
+                }
+            }
+            if complete {
+                self.pass_times = pass_times;
+            }
+        }
+
+        self.free.extend(frame.into_iter().map(|(_, query)| query));
+    }
+
+    /// Starts measuring of the given pass, must be paired with [`Self::end`]. Timers cannot be
+    /// nested, a query that was not ended (because its pass failed) is discarded.
+    pub fn begin(&mut self, state: &PipelineState, pass: GpuPass) -> Result<(), FrameworkError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        self.discard_active(state);
+
+        let query = match self.free.pop() {
+            Some(query) => query,
+            None => TimerQuery::new(state)?,
+        };
+        query.begin(state);
+        self.frames[self.current].push((pass, query));
+        self.active = true;
+
+        Ok(())
+    }
+
+    pub fn end(&mut self, state: &PipelineState) {
+        if !self.active {
+            return;
+        }
+
+        if let Some((_, query)) = self.frames[self.current].last() {
+            query.end(state);
+        }
+        self.active = false;
+    }
+
+    fn discard_active(&mut self, state: &PipelineState) {
+        if !self.active {
+            return;
+        }
+
+        if let Some((_, query)) = self.frames[self.current].pop() {
+            query.end(state);
+            self.free.push(query);
+        }
+        self.active = false;
+    }
+
+    /// Returns the most recent times (in seconds) of every group of passes.
+    pub fn pass_times(&self) -> [f32; GpuPass::COUNT] {
+        self.pass_times
+    }
+}
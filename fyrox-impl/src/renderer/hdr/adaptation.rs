@@ -16,6 +16,7 @@ pub struct AdaptationShader {
     pub new_lum_sampler: UniformLocation,
     pub wvp_matrix: UniformLocation,
     pub speed: UniformLocation,
+    pub instant_darkening: UniformLocation,
 }
 
 impl AdaptationShader {
@@ -34,6 +35,8 @@ impl AdaptationShader {
             new_lum_sampler: program
                 .uniform_location(state, &ImmutableString::new("newLumSampler"))?,
             speed: program.uniform_location(state, &ImmutableString::new("speed"))?,
+            instant_darkening: program
+                .uniform_location(state, &ImmutableString::new("instantDarkening"))?,
             program,
         })
     }
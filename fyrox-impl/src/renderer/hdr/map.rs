@@ -13,11 +13,20 @@ pub struct MapShader {
     pub bloom_sampler: UniformLocation,
     pub color_map_sampler: UniformLocation,
     pub use_color_grading: UniformLocation,
+    pub color_grading_intensity: UniformLocation,
     pub key_value: UniformLocation,
     pub min_luminance: UniformLocation,
     pub max_luminance: UniformLocation,
+    pub min_exposure: UniformLocation,
+    pub max_exposure: UniformLocation,
     pub auto_exposure: UniformLocation,
     pub fixed_exposure: UniformLocation,
+    pub exposure_compensation: UniformLocation,
+    pub tone_mapping: UniformLocation,
+    pub gamma: UniformLocation,
+    pub brightness: UniformLocation,
+    pub contrast: UniformLocation,
+    pub saturation: UniformLocation,
 }
 
 impl MapShader {
@@ -39,15 +48,26 @@ impl MapShader {
                 .uniform_location(state, &ImmutableString::new("colorMapSampler"))?,
             use_color_grading: program
                 .uniform_location(state, &ImmutableString::new("useColorGrading"))?,
+            color_grading_intensity: program
+                .uniform_location(state, &ImmutableString::new("colorGradingIntensity"))?,
             key_value: program.uniform_location(state, &ImmutableString::new("keyValue"))?,
             min_luminance: program
                 .uniform_location(state, &ImmutableString::new("minLuminance"))?,
             max_luminance: program
                 .uniform_location(state, &ImmutableString::new("maxLuminance"))?,
+            min_exposure: program.uniform_location(state, &ImmutableString::new("minExposure"))?,
+            max_exposure: program.uniform_location(state, &ImmutableString::new("maxExposure"))?,
             auto_exposure: program
                 .uniform_location(state, &ImmutableString::new("autoExposure"))?,
             fixed_exposure: program
                 .uniform_location(state, &ImmutableString::new("fixedExposure"))?,
+            exposure_compensation: program
+                .uniform_location(state, &ImmutableString::new("exposureCompensation"))?,
+            tone_mapping: program.uniform_location(state, &ImmutableString::new("toneMapping"))?,
+            gamma: program.uniform_location(state, &ImmutableString::new("gamma"))?,
+            brightness: program.uniform_location(state, &ImmutableString::new("brightness"))?,
+            contrast: program.uniform_location(state, &ImmutableString::new("contrast"))?,
+            saturation: program.uniform_location(state, &ImmutableString::new("saturation"))?,
             program,
         })
     }
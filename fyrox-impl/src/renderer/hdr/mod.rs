@@ -22,9 +22,9 @@ use crate::{
             luminance::LuminanceShader,
             map::MapShader,
         },
-        make_viewport_matrix, RenderPassStatistics,
+        make_viewport_matrix, AutoExposureSettings, ColorGrading, RenderPassStatistics,
     },
-    scene::camera::{ColorGradingLut, Exposure},
+    scene::camera::{ColorGradingLut, Exposure, ToneMapping},
 };
 use fyrox_core::{transmute_slice, value_as_u8_slice};
 use std::{cell::RefCell, rc::Rc};
@@ -287,7 +287,8 @@ impl HighDynamicRangeRenderer {
         &mut self,
         state: &PipelineState,
         quad: &GeometryBuffer,
-        dt: f32,
+        speed: f32,
+        instant_darkening: bool,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         let new_lum = self.downscale_chain.last().unwrap().texture();
         let ctx = self.adaptation_chain.begin();
@@ -315,8 +316,8 @@ impl HighDynamicRangeRenderer {
                     .set_matrix4(&shader.wvp_matrix, &matrix)
                     .set_texture(&shader.old_lum_sampler, &prev_lum)
                     .set_texture(&shader.new_lum_sampler, &new_lum)
-                    .set_f32(&shader.speed, 0.3 * dt) // TODO: Make configurable
-                ;
+                    .set_f32(&shader.speed, speed)
+                    .set_bool(&shader.instant_darkening, instant_darkening);
             },
         )
     }
@@ -330,17 +331,29 @@ impl HighDynamicRangeRenderer {
         viewport: Rect<i32>,
         quad: &GeometryBuffer,
         exposure: Exposure,
+        exposure_compensation: f32,
+        auto_exposure: Option<&AutoExposureSettings>,
+        tone_mapping: ToneMapping,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
+        color_grading: &ColorGrading,
+        color_lut: Option<&Rc<RefCell<GpuTexture>>>,
+        color_lut_intensity: f32,
         texture_cache: &mut TextureCache,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         let shader = &self.map_shader;
         let frame_matrix = make_viewport_matrix(viewport);
         let avg_lum = self.adaptation_chain.avg_lum_texture();
 
-        let color_grading_lut_tex = color_grading_lut
-            .and_then(|l| texture_cache.get(state, l.lut_ref()))
-            .unwrap_or(&self.stub_lut);
+        // Look-up table of the camera has priority over the global one.
+        let camera_lut_tex = if use_color_grading {
+            color_grading_lut.and_then(|l| texture_cache.get(state, l.lut_ref()))
+        } else {
+            None
+        };
+        let color_grading_lut_tex = camera_lut_tex.or(color_lut);
+        let use_lut = color_grading_lut_tex.is_some();
+        let color_grading_lut_tex = color_grading_lut_tex.unwrap_or(&self.stub_lut);
 
         ldr_framebuffer.draw(
             quad,
@@ -363,11 +376,23 @@ impl HighDynamicRangeRenderer {
                     .set_texture(&shader.lum_sampler, &avg_lum)
                     .set_texture(&shader.bloom_sampler, &bloom_texture)
                     .set_texture(&shader.hdr_sampler, &hdr_scene_frame)
-                    .set_bool(
-                        &shader.use_color_grading,
-                        use_color_grading && color_grading_lut.is_some(),
-                    )
-                    .set_texture(&shader.color_map_sampler, color_grading_lut_tex);
+                    .set_bool(&shader.use_color_grading, use_lut)
+                    .set_f32(&shader.color_grading_intensity, color_lut_intensity)
+                    .set_texture(&shader.color_map_sampler, color_grading_lut_tex)
+                    .set_f32(&shader.gamma, color_grading.gamma.max(0.01))
+                    .set_f32(&shader.brightness, color_grading.brightness)
+                    .set_f32(&shader.contrast, color_grading.contrast)
+                    .set_f32(&shader.saturation, color_grading.saturation)
+                    .set_f32(&shader.exposure_compensation, exposure_compensation)
+                    .set_i32(
+                        &shader.tone_mapping,
+                        match tone_mapping {
+                            ToneMapping::Exponential => 0,
+                            ToneMapping::Reinhard => 1,
+                            ToneMapping::AcesFilmic => 2,
+                            ToneMapping::None => 3,
+                        },
+                    );
 
                 match exposure {
                     Exposure::Auto {
@@ -379,7 +404,15 @@ impl HighDynamicRangeRenderer {
                             .set_bool(&shader.auto_exposure, true)
                             .set_f32(&shader.key_value, key_value)
                             .set_f32(&shader.min_luminance, min_luminance)
-                            .set_f32(&shader.max_luminance, max_luminance);
+                            .set_f32(&shader.max_luminance, max_luminance)
+                            .set_f32(
+                                &shader.min_exposure,
+                                auto_exposure.map_or(0.0, |s| s.min_exposure),
+                            )
+                            .set_f32(
+                                &shader.max_exposure,
+                                auto_exposure.map_or(f32::MAX, |s| s.max_exposure),
+                            );
                     }
                     Exposure::Manual(fixed_exposure) => {
                         program_binding
@@ -401,14 +434,23 @@ impl HighDynamicRangeRenderer {
         quad: &GeometryBuffer,
         dt: f32,
         exposure: Exposure,
+        exposure_compensation: f32,
+        auto_exposure: Option<&AutoExposureSettings>,
+        tone_mapping: ToneMapping,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
+        color_grading: &ColorGrading,
+        color_lut: Option<&Rc<RefCell<GpuTexture>>>,
+        color_lut_intensity: f32,
         texture_cache: &mut TextureCache,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         let mut stats = RenderPassStatistics::default();
         stats += self.calculate_frame_luminance(state, hdr_scene_frame.clone(), quad)?;
         stats += self.calculate_avg_frame_luminance(state, quad)?;
-        stats += self.adaptation(state, quad, dt)?;
+        // Without adaptation, the luminance of the current frame is used as is.
+        let speed = auto_exposure.map_or(1.0, |s| (s.adaptation_speed * dt).min(1.0));
+        let instant_darkening = auto_exposure.map_or(true, |s| s.instant_darkening);
+        stats += self.adaptation(state, quad, speed, instant_darkening)?;
         stats += self.map_hdr_to_ldr(
             state,
             hdr_scene_frame,
@@ -417,8 +459,14 @@ impl HighDynamicRangeRenderer {
             viewport,
             quad,
             exposure,
+            exposure_compensation,
+            auto_exposure,
+            tone_mapping,
             color_grading_lut,
             use_color_grading,
+            color_grading,
+            color_lut,
+            color_lut_intensity,
             texture_cache,
         )?;
         Ok(stats)
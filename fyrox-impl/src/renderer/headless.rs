@@ -0,0 +1,45 @@
+//! Headless renderer is a renderer stub, that is used by the engine when there is no graphics
+//! context (game servers, CI machines without a GPU, etc.). It does not render or upload anything,
+//! but keeps frame timings of [`Statistics`] going, so the engine loop works as usual.
+
+use crate::renderer::{framework::error::FrameworkError, Statistics};
+
+#[derive(Default)]
+pub(crate) struct HeadlessRenderer {
+    statistics: Statistics,
+    // Sum of time deltas of the engine updates since the last frame.
+    time_since_last_frame: f32,
+}
+
+impl HeadlessRenderer {
+    /// Does nothing, since there is no GPU to upload resources to.
+    pub(crate) fn upload_resources(&mut self) {}
+
+    /// Does nothing, since there are no frame buffers to re-create.
+    pub(crate) fn set_frame_size(&mut self, _new_size: (u32, u32)) -> Result<(), FrameworkError> {
+        Ok(())
+    }
+
+    /// Advances time of the renderer, there are no caches to update.
+    pub(crate) fn update_caches(&mut self, dt: f32) {
+        self.upload_resources();
+        self.time_since_last_frame += dt;
+    }
+
+    /// Does not render anything, only updates frame timings of the statistics.
+    pub(crate) fn render(&mut self) -> Result<(), FrameworkError> {
+        self.statistics.begin_frame();
+        // There is no rendering and no vertical synchronization, so wall-clock time between frames
+        // depends only on the speed of the machine. Time deltas of the engine updates are used
+        // instead, this way the timings are reproducible.
+        self.statistics.frame_delta = std::mem::take(&mut self.time_since_last_frame);
+        self.statistics.end_frame();
+        self.statistics.finalize();
+        Ok(())
+    }
+
+    /// Returns statistics of the last frame, only frame timings are valid.
+    pub(crate) fn get_statistics(&self) -> Statistics {
+        self.statistics
+    }
+}
@@ -12,6 +12,11 @@ pub struct AmbientLightShader {
     pub ambient_color: UniformLocation,
     pub ao_sampler: UniformLocation,
     pub ambient_texture: UniformLocation,
+    pub emission_texture: UniformLocation,
+    pub normal_texture: UniformLocation,
+    pub irradiance_map: UniformLocation,
+    pub use_image_based_lighting: UniformLocation,
+    pub irradiance_intensity: UniformLocation,
 }
 
 impl AmbientLightShader {
@@ -30,6 +35,16 @@ impl AmbientLightShader {
             ao_sampler: program.uniform_location(state, &ImmutableString::new("aoSampler"))?,
             ambient_texture: program
                 .uniform_location(state, &ImmutableString::new("ambientTexture"))?,
+            emission_texture: program
+                .uniform_location(state, &ImmutableString::new("emissionTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            irradiance_map: program
+                .uniform_location(state, &ImmutableString::new("irradianceMap"))?,
+            use_image_based_lighting: program
+                .uniform_location(state, &ImmutableString::new("useImageBasedLighting"))?,
+            irradiance_intensity: program
+                .uniform_location(state, &ImmutableString::new("irradianceIntensity"))?,
             program,
         })
     }
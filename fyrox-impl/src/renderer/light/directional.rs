@@ -17,7 +17,6 @@ pub struct DirectionalLightShader {
     pub light_direction: UniformLocation,
     pub light_color: UniformLocation,
     pub inv_view_proj_matrix: UniformLocation,
-    pub camera_position: UniformLocation,
     pub light_intensity: UniformLocation,
     pub cascade_distances: UniformLocation,
     pub shadow_cascade0: UniformLocation,
@@ -57,8 +56,6 @@ impl DirectionalLightShader {
             light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
             inv_view_proj_matrix: program
                 .uniform_location(state, &ImmutableString::new("invViewProj"))?,
-            camera_position: program
-                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
             light_intensity: program
                 .uniform_location(state, &ImmutableString::new("lightIntensity"))?,
             cascade_distances: program
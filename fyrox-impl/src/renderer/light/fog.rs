@@ -0,0 +1,42 @@
+use crate::core::sstorage::ImmutableString;
+use crate::renderer::framework::{
+    error::FrameworkError,
+    gpu_program::{GpuProgram, UniformLocation},
+    state::PipelineState,
+};
+
+pub struct FogShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub depth_sampler: UniformLocation,
+    pub inv_proj_matrix: UniformLocation,
+    pub fog_color: UniformLocation,
+    pub fog_mode: UniformLocation,
+    pub fog_start: UniformLocation,
+    pub fog_end: UniformLocation,
+    pub fog_density: UniformLocation,
+    pub far_depth: UniformLocation,
+}
+
+impl FogShader {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/fog_fs.glsl");
+        let vertex_source = include_str!("../shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(state, "FogShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthSampler"))?,
+            inv_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
+            fog_color: program.uniform_location(state, &ImmutableString::new("fogColor"))?,
+            fog_mode: program.uniform_location(state, &ImmutableString::new("fogMode"))?,
+            fog_start: program.uniform_location(state, &ImmutableString::new("fogStart"))?,
+            fog_end: program.uniform_location(state, &ImmutableString::new("fogEnd"))?,
+            fog_density: program.uniform_location(state, &ImmutableString::new("fogDensity"))?,
+            far_depth: program.uniform_location(state, &ImmutableString::new("farDepth"))?,
+            program,
+        })
+    }
+}
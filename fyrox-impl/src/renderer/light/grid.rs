@@ -0,0 +1,112 @@
+//! Light grid splits the frame into square screen-space tiles and bins every light into the tiles
+//! covered by its bounding sphere. Deferred lighting then shades only the tiles affected by a light
+//! (using scissor test), instead of running light shader for the entire frame.
+
+use crate::core::{
+    algebra::{Matrix4, Vector3, Vector4},
+    math::Rect,
+};
+
+/// Size of a tile of the light grid in pixels.
+pub const LIGHT_GRID_TILE_SIZE: i32 = 32;
+
+/// A set of tiles, that are affected by a light.
+pub struct LightTiles {
+    /// Tile-aligned rectangle in pixels (with origin at lower left corner), clamped to the frame.
+    pub bounds: Rect<i32>,
+    /// Amount of tiles within the bounds.
+    pub count: usize,
+}
+
+pub struct LightGrid {
+    width: i32,
+    height: i32,
+    columns: i32,
+    rows: i32,
+}
+
+impl LightGrid {
+    pub fn new(viewport: Rect<i32>) -> Self {
+        Self {
+            width: viewport.w(),
+            height: viewport.h(),
+            columns: (viewport.w() + LIGHT_GRID_TILE_SIZE - 1) / LIGHT_GRID_TILE_SIZE,
+            rows: (viewport.h() + LIGHT_GRID_TILE_SIZE - 1) / LIGHT_GRID_TILE_SIZE,
+        }
+    }
+
+    /// Returns total amount of tiles in the grid.
+    pub fn tile_count(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+
+    /// Returns all the tiles of the grid.
+    pub fn all_tiles(&self) -> LightTiles {
+        LightTiles {
+            bounds: Rect::new(0, 0, self.width, self.height),
+            count: self.tile_count(),
+        }
+    }
+
+    /// Bins a light with the given bounding sphere into the tiles of the grid. Returns `None` if the
+    /// light does not affect any tile.
+    pub fn bin_light(
+        &self,
+        view_projection: &Matrix4<f32>,
+        position: Vector3<f32>,
+        radius: f32,
+    ) -> Option<LightTiles> {
+        // Project the corners of the bounding box of the sphere, it gives conservative screen-space
+        // bounds of the sphere.
+        let mut min = Vector3::repeat(f32::MAX);
+        let mut max = Vector3::repeat(-f32::MAX);
+        let mut corners_behind = 0;
+        for i in 0..8 {
+            let corner = Vector3::new(
+                if i & 1 == 0 { -radius } else { radius },
+                if i & 2 == 0 { -radius } else { radius },
+                if i & 4 == 0 { -radius } else { radius },
+            ) + position;
+            let clip = view_projection * Vector4::new(corner.x, corner.y, corner.z, 1.0);
+            if clip.w <= f32::EPSILON {
+                corners_behind += 1;
+            } else {
+                let ndc = clip.xyz() / clip.w;
+                min = min.inf(&ndc);
+                max = max.sup(&ndc);
+            }
+        }
+
+        if corners_behind == 8 {
+            return None;
+        } else if corners_behind > 0 {
+            // The sphere intersects the plane of the camera (camera could be inside of it), such
+            // light could affect any tile.
+            return Some(self.all_tiles());
+        }
+
+        let to_column = |x: f32| (x * 0.5 + 0.5) * self.width as f32 / LIGHT_GRID_TILE_SIZE as f32;
+        let to_row = |y: f32| (y * 0.5 + 0.5) * self.height as f32 / LIGHT_GRID_TILE_SIZE as f32;
+
+        let first_column = (to_column(min.x).floor() as i32).clamp(0, self.columns);
+        let last_column = (to_column(max.x).ceil() as i32).clamp(0, self.columns);
+        let first_row = (to_row(min.y).floor() as i32).clamp(0, self.rows);
+        let last_row = (to_row(max.y).ceil() as i32).clamp(0, self.rows);
+
+        if first_column >= last_column || first_row >= last_row {
+            return None;
+        }
+
+        let x = first_column * LIGHT_GRID_TILE_SIZE;
+        let y = first_row * LIGHT_GRID_TILE_SIZE;
+        Some(LightTiles {
+            bounds: Rect::new(
+                x,
+                y,
+                (last_column * LIGHT_GRID_TILE_SIZE).min(self.width) - x,
+                (last_row * LIGHT_GRID_TILE_SIZE).min(self.height) - y,
+            ),
+            count: ((last_column - first_column) * (last_row - first_row)) as usize,
+        })
+    }
+}
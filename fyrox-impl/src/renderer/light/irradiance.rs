@@ -0,0 +1,199 @@
+//! Irradiance map is a tiny cube map, that contains diffuse lighting for every direction. It is
+//! calculated by convolution of an environment cube map (skybox) over the hemisphere and then used
+//! as an ambient term of the lighting (diffuse image-based lighting).
+
+use crate::{
+    core::{math::Rect, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        cache::TimeToLive,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            geometry_buffer::{ElementRange, GeometryBuffer},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, CubeMapFace, GpuTexture, GpuTextureKind, MagnificationFilter,
+                MinificationFilter, PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        make_viewport_matrix, RenderPassStatistics,
+    },
+    resource::texture::TextureResource,
+};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, rc::Rc};
+
+/// Size of a face of the irradiance cube map. Irradiance changes very slowly with the direction,
+/// so a very small map is enough.
+const IRRADIANCE_MAP_SIZE: usize = 32;
+
+struct IrradianceShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    environment_map: UniformLocation,
+    face_index: UniformLocation,
+}
+
+impl IrradianceShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/irradiance_fs.glsl");
+        let vertex_source = include_str!("../shaders/flat_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "IrradianceShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            environment_map: program
+                .uniform_location(state, &ImmutableString::new("environmentMap"))?,
+            face_index: program.uniform_location(state, &ImmutableString::new("faceIndex"))?,
+            program,
+        })
+    }
+}
+
+struct IrradianceMap {
+    framebuffer: FrameBuffer,
+    time_to_live: TimeToLive,
+}
+
+impl IrradianceMap {
+    fn irradiance_map(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+}
+
+fn make_cube_map(state: &PipelineState, size: usize) -> Result<GpuTexture, FrameworkError> {
+    let mut cube_map = GpuTexture::new(
+        state,
+        GpuTextureKind::Cube {
+            width: size,
+            height: size,
+        },
+        PixelKind::RGBA16F,
+        MinificationFilter::Linear,
+        MagnificationFilter::Linear,
+        1,
+        None,
+    )?;
+    cube_map
+        .bind_mut(state, 0)
+        .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::T, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::R, WrapMode::ClampToEdge);
+    Ok(cube_map)
+}
+
+pub struct IrradianceMapRenderer {
+    shader: IrradianceShader,
+    // Irradiance maps of every environment map that was used recently, the key is the key of the
+    // environment map resource. This way, multiple cameras with different skyboxes do not force
+    // the maps to be re-calculated every frame.
+    maps: FxHashMap<u64, IrradianceMap>,
+    // Tiny cube map, that is used when there is no irradiance map available.
+    dummy: Rc<RefCell<GpuTexture>>,
+}
+
+impl IrradianceMapRenderer {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: IrradianceShader::new(state)?,
+            maps: Default::default(),
+            dummy: Rc::new(RefCell::new(make_cube_map(state, 1)?)),
+        })
+    }
+
+    /// Returns a cube map, that could be bound instead of an irradiance map when image-based lighting
+    /// is not used.
+    pub fn dummy(&self) -> Rc<RefCell<GpuTexture>> {
+        self.dummy.clone()
+    }
+
+    /// Returns the irradiance map of the given environment map. The convolution is quite heavy, so
+    /// it is done only once per environment map, the result is cached and will be kept alive while
+    /// the environment map is used.
+    pub fn update(
+        &mut self,
+        state: &PipelineState,
+        quad: &GeometryBuffer,
+        environment: &TextureResource,
+        environment_map: &Rc<RefCell<GpuTexture>>,
+    ) -> Result<(Rc<RefCell<GpuTexture>>, RenderPassStatistics), FrameworkError> {
+        scope_profile!();
+
+        let mut stats = RenderPassStatistics::default();
+
+        let key = environment.key();
+
+        if let Some(entry) = self.maps.get_mut(&key) {
+            entry.time_to_live = TimeToLive::default();
+            return Ok((entry.irradiance_map(), stats));
+        }
+
+        let mut framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(make_cube_map(state, IRRADIANCE_MAP_SIZE)?)),
+            }],
+        )?;
+
+        let viewport = Rect::new(0, 0, IRRADIANCE_MAP_SIZE as i32, IRRADIANCE_MAP_SIZE as i32);
+        let frame_matrix = make_viewport_matrix(viewport);
+
+        for (face_index, face) in [
+            CubeMapFace::PositiveX,
+            CubeMapFace::NegativeX,
+            CubeMapFace::PositiveY,
+            CubeMapFace::NegativeY,
+            CubeMapFace::PositiveZ,
+            CubeMapFace::NegativeZ,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            framebuffer.set_cubemap_face(state, 0, face);
+
+            stats += framebuffer.draw(
+                quad,
+                state,
+                viewport,
+                &self.shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test: false,
+                    blend: None,
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&self.shader.wvp_matrix, &frame_matrix)
+                        .set_texture(&self.shader.environment_map, environment_map)
+                        .set_i32(&self.shader.face_index, face_index as i32);
+                },
+            )?;
+        }
+
+        let entry = IrradianceMap {
+            framebuffer,
+            time_to_live: TimeToLive::default(),
+        };
+        let irradiance_map = entry.irradiance_map();
+        self.maps.insert(key, entry);
+
+        Ok((irradiance_map, stats))
+    }
+
+    /// Destroys irradiance maps of environment maps, that were not used for a while.
+    pub fn update_caches(&mut self, dt: f32) {
+        self.maps.retain(|_, entry| {
+            *entry.time_to_live -= dt;
+            *entry.time_to_live > 0.0
+        });
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
     graph::SceneGraph,
     renderer::{
         cache::shader::ShaderCache,
+        camera_projection_matrix, depth_func, far_plane_depth,
         flat_shader::FlatShader,
         framework::{
             error::FrameworkError,
@@ -22,8 +23,9 @@ use crate::{
         },
         gbuffer::GBuffer,
         light::{
-            ambient::AmbientLightShader, directional::DirectionalLightShader,
-            point::PointLightShader, spot::SpotLightShader,
+            ambient::AmbientLightShader, directional::DirectionalLightShader, fog::FogShader,
+            grid::LightGrid, irradiance::IrradianceMapRenderer, point::PointLightShader,
+            spot::SpotLightShader,
         },
         light_volume::LightVolumeRenderer,
         shadow::{
@@ -34,11 +36,13 @@ use crate::{
         skybox_shader::SkyboxShader,
         ssao::ScreenSpaceAmbientOcclusionRenderer,
         storage::MatrixStorageCache,
-        GeometryCache, QualitySettings, RenderPassStatistics, TextureCache,
+        GeometryCache, QualitySettings, RenderFlags, RenderPassStatistics, TextureCache,
     },
     scene::{
         camera::Camera,
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            directional::DirectionalLight, point::PointLight, spot::SpotLight, LightAttenuation,
+        },
         mesh::{
             buffer::{TriangleBuffer, VertexBuffer},
             surface::SurfaceData,
@@ -51,6 +55,9 @@ use std::{cell::RefCell, rc::Rc};
 
 pub mod ambient;
 pub mod directional;
+pub mod fog;
+pub mod grid;
+pub mod irradiance;
 pub mod point;
 pub mod spot;
 
@@ -60,11 +67,13 @@ pub struct DeferredLightRenderer {
     point_light_shader: PointLightShader,
     directional_light_shader: DirectionalLightShader,
     ambient_light_shader: AmbientLightShader,
+    fog_shader: FogShader,
     quad: GeometryBuffer,
     sphere: GeometryBuffer,
     skybox: GeometryBuffer,
     flat_shader: FlatShader,
     skybox_shader: SkyboxShader,
+    irradiance_renderer: IrradianceMapRenderer,
     spot_shadow_map_renderer: SpotShadowMapRenderer,
     point_shadow_map_renderer: PointShadowMapRenderer,
     csm_renderer: CsmRenderer,
@@ -87,6 +96,8 @@ pub(crate) struct DeferredRendererContext<'a> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub reverse_z: bool,
+    pub render_flags: RenderFlags,
 }
 
 impl DeferredLightRenderer {
@@ -128,8 +139,6 @@ impl DeferredLightRenderer {
             SimpleVertex::new(-0.5, -0.5, -0.5),
         ];
 
-        let quality_defaults = QualitySettings::default();
-
         Ok(Self {
             ssao_renderer: ScreenSpaceAmbientOcclusionRenderer::new(
                 state,
@@ -140,6 +149,7 @@ impl DeferredLightRenderer {
             point_light_shader: PointLightShader::new(state)?,
             directional_light_shader: DirectionalLightShader::new(state)?,
             ambient_light_shader: AmbientLightShader::new(state)?,
+            fog_shader: FogShader::new(state)?,
             quad: GeometryBuffer::from_surface_data(
                 &SurfaceData::make_unit_xy_quad(),
                 GeometryBufferKind::StaticDraw,
@@ -173,21 +183,23 @@ impl DeferredLightRenderer {
             )?,
             flat_shader: FlatShader::new(state)?,
             skybox_shader: SkyboxShader::new(state)?,
+            irradiance_renderer: IrradianceMapRenderer::new(state)?,
             spot_shadow_map_renderer: SpotShadowMapRenderer::new(
                 state,
                 settings.spot_shadow_map_size,
-                quality_defaults.spot_shadow_map_precision,
+                settings.spot_shadow_map_precision,
             )?,
             point_shadow_map_renderer: PointShadowMapRenderer::new(
                 state,
                 settings.point_shadow_map_size,
-                quality_defaults.point_shadow_map_precision,
+                settings.point_shadow_map_precision,
             )?,
             light_volume: LightVolumeRenderer::new(state)?,
             csm_renderer: CsmRenderer::new(
                 state,
-                quality_defaults.csm_settings.size,
-                quality_defaults.csm_settings.precision,
+                settings.csm_settings.size,
+                settings.csm_settings.precision,
+                settings.csm_settings.cascade_count,
             )?,
         })
     }
@@ -217,14 +229,19 @@ impl DeferredLightRenderer {
         }
         if settings.csm_settings.precision != self.csm_renderer.precision()
             || settings.csm_settings.size != self.csm_renderer.size()
+            || settings.csm_settings.cascade_count != self.csm_renderer.cascade_count()
         {
             self.csm_renderer = CsmRenderer::new(
                 state,
                 settings.csm_settings.size,
                 settings.csm_settings.precision,
+                settings.csm_settings.cascade_count,
             )?;
         }
         self.ssao_renderer.set_radius(settings.ssao_radius);
+        self.ssao_renderer.set_bias(settings.ssao_bias);
+        self.ssao_renderer
+            .set_sample_count(settings.ssao_sample_count);
         Ok(())
     }
 
@@ -233,12 +250,12 @@ impl DeferredLightRenderer {
         state: &PipelineState,
         frame_size: (u32, u32),
     ) -> Result<(), FrameworkError> {
-        self.ssao_renderer = ScreenSpaceAmbientOcclusionRenderer::new(
-            state,
-            frame_size.0 as usize,
-            frame_size.1 as usize,
-        )?;
-        Ok(())
+        self.ssao_renderer
+            .resize(state, frame_size.0 as usize, frame_size.1 as usize)
+    }
+
+    pub fn update_caches(&mut self, dt: f32) {
+        self.irradiance_renderer.update_caches(dt);
     }
 
     pub(crate) fn render(
@@ -266,6 +283,8 @@ impl DeferredLightRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            reverse_z,
+            render_flags,
         } = args;
 
         let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
@@ -285,11 +304,11 @@ impl DeferredLightRenderer {
             0.0,
         ));
 
-        let projection_matrix = camera.projection_matrix();
-        let view_projection = camera.view_projection_matrix();
+        let projection_matrix = camera_projection_matrix(camera, reverse_z);
+        let view_projection = projection_matrix * camera.view_matrix();
         let inv_projection = projection_matrix.try_inverse().unwrap_or_default();
         let inv_view_projection = view_projection.try_inverse().unwrap_or_default();
-        let camera_global_position = camera.global_position();
+        let light_grid = LightGrid::new(viewport);
 
         // Fill SSAO map.
         if settings.use_ssao {
@@ -333,6 +352,7 @@ impl DeferredLightRenderer {
                     |mut program_binding| {
                         program_binding
                             .set_texture(&shader.cubemap_texture, gpu_texture)
+                            .set_f32(&shader.intensity, camera.skybox_intensity())
                             .set_matrix4(&shader.wvp_matrix, &(view_projection * wvp));
                     },
                 )?;
@@ -345,8 +365,28 @@ impl DeferredLightRenderer {
         let gbuffer_normal_map = gbuffer.normal_texture();
         let gbuffer_material_map = gbuffer.material_texture();
         let gbuffer_ambient_map = gbuffer.ambient_texture();
+        let gbuffer_emission_map = gbuffer.emission_texture();
         let ao_map = self.ssao_renderer.ao_map();
 
+        // Diffuse image-based lighting uses the skybox of the camera as the source of ambient light.
+        let mut use_image_based_lighting = false;
+        let mut irradiance_map = self.irradiance_renderer.dummy();
+        if scene.rendering_options.image_based_lighting {
+            if let Some(cube_map) = camera.skybox_ref().and_then(|skybox| skybox.cubemap_ref()) {
+                if let Some(gpu_texture) = textures.get(state, cube_map) {
+                    let (map, stats) = self.irradiance_renderer.update(
+                        state,
+                        &self.quad,
+                        cube_map,
+                        gpu_texture,
+                    )?;
+                    pass_stats += stats;
+                    irradiance_map = map;
+                    use_image_based_lighting = true;
+                }
+            }
+        }
+
         pass_stats += frame_buffer.draw(
             &self.quad,
             state,
@@ -384,6 +424,23 @@ impl DeferredLightRenderer {
                     .set_texture(
                         &self.ambient_light_shader.ambient_texture,
                         &gbuffer_ambient_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.emission_texture,
+                        &gbuffer_emission_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.normal_texture,
+                        &gbuffer_normal_map,
+                    )
+                    .set_texture(&self.ambient_light_shader.irradiance_map, &irradiance_map)
+                    .set_bool(
+                        &self.ambient_light_shader.use_image_based_lighting,
+                        use_image_based_lighting,
+                    )
+                    .set_f32(
+                        &self.ambient_light_shader.irradiance_intensity,
+                        camera.skybox_intensity(),
                     );
             },
         )?;
@@ -411,7 +468,9 @@ impl DeferredLightRenderer {
                         settings.point_shadows_distance,
                         point_light.base_light_ref().is_cast_shadows()
                             && distance_to_camera <= settings.point_shadows_distance
-                            && settings.point_shadows_enabled,
+                            && settings.point_shadows_enabled
+                            && light_stats.point_shadow_maps_rendered
+                                < settings.point_shadows_max_lights,
                         settings.point_shadows_fade_out_range,
                     )
                 } else if let Some(directional) = light.cast::<DirectionalLight>() {
@@ -441,6 +500,18 @@ impl DeferredLightRenderer {
                 continue;
             }
 
+            let is_directional = light.cast::<DirectionalLight>().is_some();
+            let light_tiles = if settings.use_light_grid && !is_directional {
+                // Stencil volume is inflated, so the inflated radius must be used to make sure that
+                // the whole marked area of the stencil buffer will be shaded (and cleared).
+                match light_grid.bin_light(&view_projection, light_position, light_r_inflate) {
+                    Some(light_tiles) => light_tiles,
+                    None => continue,
+                }
+            } else {
+                light_grid.all_tiles()
+            };
+
             let b1 = shadows_distance * 0.2;
             let b2 = shadows_distance * 0.4;
             let cascade_index =
@@ -462,6 +533,9 @@ impl DeferredLightRenderer {
             let mut light_view_projection = Matrix4::identity();
 
             if shadows_enabled {
+                // Shadow maps always use the default depth range.
+                state.set_depth_func(CompareFunc::default());
+
                 if let Some(spot) = light.cast::<SpotLight>() {
                     let z_near = 0.01;
                     let z_far = light_radius;
@@ -500,6 +574,7 @@ impl DeferredLightRenderer {
                         black_dummy.clone(),
                         volume_dummy.clone(),
                         matrix_storage,
+                        render_flags,
                     )?;
 
                     light_stats.spot_shadow_maps_rendered += 1;
@@ -520,6 +595,7 @@ impl DeferredLightRenderer {
                                 black_dummy: black_dummy.clone(),
                                 volume_dummy: volume_dummy.clone(),
                                 matrix_storage,
+                                render_flags,
                             })?;
 
                     light_stats.point_shadow_maps_rendered += 1;
@@ -538,10 +614,13 @@ impl DeferredLightRenderer {
                         black_dummy: black_dummy.clone(),
                         volume_dummy: volume_dummy.clone(),
                         matrix_storage,
+                        render_flags,
                     })?;
 
                     light_stats.csm_rendered += 1;
                 };
+
+                state.set_depth_func(depth_func(reverse_z));
             }
 
             // Mark lighted areas in stencil buffer to do light calculations only on them.
@@ -631,6 +710,19 @@ impl DeferredLightRenderer {
 
             let quad = &self.quad;
 
+            light_stats.light_tiles_shaded += light_tiles.count;
+
+            // Shade only the tiles affected by the light.
+            if settings.use_light_grid {
+                state.set_scissor_test(true);
+                state.set_scissor_box(
+                    light_tiles.bounds.x(),
+                    light_tiles.bounds.y(),
+                    light_tiles.bounds.w(),
+                    light_tiles.bounds.h(),
+                );
+            }
+
             pass_stats += if let Some(spot_light) = light.cast::<SpotLight>() {
                 let shader = &self.spot_light_shader;
 
@@ -681,7 +773,6 @@ impl DeferredLightRenderer {
                                 1.0 / (self.spot_shadow_map_renderer.cascade_size(cascade_index)
                                     as f32),
                             )
-                            .set_vector3(&shader.camera_position, &camera_global_position)
                             .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
                             .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
@@ -697,6 +788,11 @@ impl DeferredLightRenderer {
                                 &shader.light_intensity,
                                 spot_light.base_light_ref().intensity(),
                             )
+                            .set_bool(
+                                &shader.inverse_square_attenuation,
+                                spot_light.base_light_ref().attenuation()
+                                    == LightAttenuation::InverseSquare,
+                            )
                             .set_f32(&shader.shadow_alpha, shadows_alpha);
                     },
                 )?
@@ -724,12 +820,16 @@ impl DeferredLightRenderer {
                                 &point_light.base_light_ref().color(),
                             )
                             .set_matrix4(&shader.wvp_matrix, &frame_matrix)
-                            .set_vector3(&shader.camera_position, &camera_global_position)
                             .set_f32(&shader.shadow_bias, point_light.shadow_bias())
                             .set_f32(
                                 &shader.light_intensity,
                                 point_light.base_light_ref().intensity(),
                             )
+                            .set_bool(
+                                &shader.inverse_square_attenuation,
+                                point_light.base_light_ref().attenuation()
+                                    == LightAttenuation::InverseSquare,
+                            )
                             .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
                             .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
@@ -767,15 +867,17 @@ impl DeferredLightRenderer {
                     },
                     ElementRange::Full,
                     |mut program_binding| {
-                        let distances = [
-                            self.csm_renderer.cascades()[0].z_far,
-                            self.csm_renderer.cascades()[1].z_far,
-                            self.csm_renderer.cascades()[2].z_far,
-                        ];
+                        let cascades = self.csm_renderer.cascades();
+                        // Disabled cascades are never selected in the shader, because of negative
+                        // distance, but they still must have valid textures bound.
+                        let cascade = |i: usize| cascades.get(i).unwrap_or(&cascades[0]);
+                        let distance =
+                            |i: usize| cascades.get(i).map_or(-1.0, |cascade| cascade.z_far);
+                        let distances = [distance(0), distance(1), distance(2)];
                         let matrices = [
-                            self.csm_renderer.cascades()[0].view_proj_matrix,
-                            self.csm_renderer.cascades()[1].view_proj_matrix,
-                            self.csm_renderer.cascades()[2].view_proj_matrix,
+                            cascade(0).view_proj_matrix,
+                            cascade(1).view_proj_matrix,
+                            cascade(2).view_proj_matrix,
                         ];
                         let csm_map_size = self.csm_renderer.size() as f32;
 
@@ -787,7 +889,6 @@ impl DeferredLightRenderer {
                                 &directional.base_light_ref().color(),
                             )
                             .set_matrix4(&shader.wvp_matrix, &frame_matrix)
-                            .set_vector3(&shader.camera_position, &camera_global_position)
                             .set_f32(
                                 &shader.light_intensity,
                                 directional.base_light_ref().intensity(),
@@ -797,18 +898,9 @@ impl DeferredLightRenderer {
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
                             .set_texture(&shader.material_sampler, &gbuffer_material_map)
                             .set_matrix4_array(&shader.light_view_proj_matrices, &matrices)
-                            .set_texture(
-                                &shader.shadow_cascade0,
-                                &self.csm_renderer.cascades()[0].texture(),
-                            )
-                            .set_texture(
-                                &shader.shadow_cascade1,
-                                &self.csm_renderer.cascades()[1].texture(),
-                            )
-                            .set_texture(
-                                &shader.shadow_cascade2,
-                                &self.csm_renderer.cascades()[2].texture(),
-                            )
+                            .set_texture(&shader.shadow_cascade0, &cascade(0).texture())
+                            .set_texture(&shader.shadow_cascade1, &cascade(1).texture())
+                            .set_texture(&shader.shadow_cascade2, &cascade(2).texture())
                             .set_f32_slice(&shader.cascade_distances, &distances)
                             .set_matrix4(&shader.view_matrix, &camera.view_matrix())
                             .set_f32(&shader.shadow_bias, directional.csm_options.shadow_bias())
@@ -821,6 +913,8 @@ impl DeferredLightRenderer {
                 unreachable!()
             };
 
+            state.set_scissor_test(false);
+
             if settings.light_scatter_enabled {
                 pass_stats += self.light_volume.render_volume(
                     state,
@@ -838,6 +932,43 @@ impl DeferredLightRenderer {
             }
         }
 
+        // Fog is applied on top of the lit scene, but before forward rendering of transparent
+        // objects.
+        if let Some(fog) = scene.rendering_options.fog.as_ref() {
+            let shader = &self.fog_shader;
+            pass_stats += frame_buffer.draw(
+                &self.quad,
+                state,
+                viewport,
+                &shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test: false,
+                    blend: Some(BlendParameters {
+                        func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                        ..Default::default()
+                    }),
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                        .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
+                        .set_matrix4(&shader.inv_proj_matrix, &inv_projection)
+                        .set_linear_color(&shader.fog_color, &fog.color)
+                        .set_i32(&shader.fog_mode, fog.mode as i32)
+                        .set_f32(&shader.fog_start, fog.start)
+                        .set_f32(&shader.fog_end, fog.end)
+                        .set_f32(&shader.fog_density, fog.density)
+                        .set_f32(&shader.far_depth, far_plane_depth(reverse_z));
+                },
+            )?;
+        }
+
         Ok((pass_stats, light_stats))
     }
 }
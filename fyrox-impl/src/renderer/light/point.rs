@@ -19,9 +19,9 @@ pub struct PointLightShader {
     pub light_radius: UniformLocation,
     pub light_color: UniformLocation,
     pub inv_view_proj_matrix: UniformLocation,
-    pub camera_position: UniformLocation,
     pub shadow_bias: UniformLocation,
     pub light_intensity: UniformLocation,
+    pub inverse_square_attenuation: UniformLocation,
     pub shadow_alpha: UniformLocation,
 }
 
@@ -52,11 +52,11 @@ impl PointLightShader {
             light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
             inv_view_proj_matrix: program
                 .uniform_location(state, &ImmutableString::new("invViewProj"))?,
-            camera_position: program
-                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
             shadow_bias: program.uniform_location(state, &ImmutableString::new("shadowBias"))?,
             light_intensity: program
                 .uniform_location(state, &ImmutableString::new("lightIntensity"))?,
+            inverse_square_attenuation: program
+                .uniform_location(state, &ImmutableString::new("inverseSquareAttenuation"))?,
             shadow_alpha: program.uniform_location(state, &ImmutableString::new("shadowAlpha"))?,
             program,
         })
@@ -26,9 +26,9 @@ pub struct SpotLightShader {
     pub half_hotspot_cone_angle_cos: UniformLocation,
     pub half_cone_angle_cos: UniformLocation,
     pub inv_view_proj_matrix: UniformLocation,
-    pub camera_position: UniformLocation,
     pub shadow_bias: UniformLocation,
     pub light_intensity: UniformLocation,
+    pub inverse_square_attenuation: UniformLocation,
     pub shadow_alpha: UniformLocation,
 }
 
@@ -73,11 +73,11 @@ impl SpotLightShader {
                 .uniform_location(state, &ImmutableString::new("halfConeAngleCos"))?,
             inv_view_proj_matrix: program
                 .uniform_location(state, &ImmutableString::new("invViewProj"))?,
-            camera_position: program
-                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
             shadow_bias: program.uniform_location(state, &ImmutableString::new("shadowBias"))?,
             light_intensity: program
                 .uniform_location(state, &ImmutableString::new("lightIntensity"))?,
+            inverse_square_attenuation: program
+                .uniform_location(state, &ImmutableString::new("inverseSquareAttenuation"))?,
             shadow_alpha: program.uniform_location(state, &ImmutableString::new("shadowAlpha"))?,
             program,
         })
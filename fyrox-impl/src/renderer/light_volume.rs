@@ -36,6 +36,7 @@ struct SpotLightShader {
     light_position: UniformLocation,
     light_direction: UniformLocation,
     cone_angle_cos: UniformLocation,
+    hotspot_cone_angle_cos: UniformLocation,
     light_color: UniformLocation,
     scatter_factor: UniformLocation,
     inv_proj: UniformLocation,
@@ -59,6 +60,8 @@ impl SpotLightShader {
                 .uniform_location(state, &ImmutableString::new("lightDirection"))?,
             cone_angle_cos: program
                 .uniform_location(state, &ImmutableString::new("coneAngleCos"))?,
+            hotspot_cone_angle_cos: program
+                .uniform_location(state, &ImmutableString::new("hotspotConeAngleCos"))?,
             light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
             scatter_factor: program
                 .uniform_location(state, &ImmutableString::new("scatterFactor"))?,
@@ -272,6 +275,10 @@ impl LightVolumeRenderer {
                         .set_matrix4(&shader.world_view_proj_matrix, &frame_matrix)
                         .set_matrix4(&shader.inv_proj, &inv_proj)
                         .set_f32(&shader.cone_angle_cos, (spot.full_cone_angle() * 0.5).cos())
+                        .set_f32(
+                            &shader.hotspot_cone_angle_cos,
+                            (spot.hotspot_cone_angle() * 0.5).cos(),
+                        )
                         .set_vector3(&shader.light_position, &position)
                         .set_vector3(&shader.light_direction, &direction)
                         .set_texture(&shader.depth_sampler, &depth_map)
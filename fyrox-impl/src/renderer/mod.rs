@@ -24,9 +24,13 @@ mod flat_shader;
 mod forward_renderer;
 mod fxaa;
 mod gbuffer;
+mod gpu_timer;
 mod hdr;
+mod headless;
 mod light;
 mod light_volume;
+mod oit;
+mod picking;
 mod shadow;
 mod skybox_shader;
 mod ssao;
@@ -35,6 +39,8 @@ mod stats;
 use crate::renderer::cache::texture::TextureRenderData;
 
 use crate::renderer::cache::TimeToLive;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::renderer::framework::backend::GlPresentTarget;
 use crate::renderer::framework::state::SharedPipelineState;
 use crate::{
     asset::{event::ResourceEvent, manager::ResourceManager},
@@ -43,12 +49,13 @@ use crate::{
         color::Color,
         instant,
         log::{Log, MessageKind},
-        math::Rect,
+        math::{aabb::AxisAlignedBoundingBox, Rect},
         pool::Handle,
         reflect::prelude::*,
         scope_profile,
         sstorage::ImmutableString,
     },
+    graph::BaseSceneGraph,
     gui::draw::DrawingContext,
     material::{
         shader::{SamplerFallback, Shader, ShaderResource, ShaderResourceExtension},
@@ -59,11 +66,12 @@ use crate::{
         bundle::{ObserverInfo, PersistentIdentifier, RenderDataBundleStorage},
         cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
         debug_renderer::DebugRenderer,
-        flat_shader::FlatShader,
-        forward_renderer::{ForwardRenderContext, ForwardRenderer},
+        flat_shader::{FlatShader, GBufferDebugShader, WireframeShader},
+        forward_renderer::{ForwardRenderContext, ForwardRenderer, OitContext},
         framework::{
+            backend::RenderBackend,
             error::FrameworkError,
-            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            framebuffer::{AttachmentKind, DrawParameters, FrameBuffer},
             geometry_buffer::{
                 DrawCallStatistics, ElementRange, GeometryBuffer, GeometryBufferKind,
             },
@@ -72,17 +80,29 @@ use crate::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
             },
-            state::{GlKind, PipelineState, PolygonFace, PolygonFillMode},
+            state::{CompareFunc, GlKind, PipelineState, PolygonFace, PolygonFillMode},
         },
         fxaa::FxaaRenderer,
         gbuffer::{GBuffer, GBufferRenderContext},
+        gpu_timer::GpuTimers,
         hdr::HighDynamicRangeRenderer,
         light::{DeferredLightRenderer, DeferredRendererContext},
+        oit::OitRenderer,
+        picking::{ObjectIdRenderContext, ObjectIdRenderer},
         storage::MatrixStorageCache,
         ui_renderer::{UiRenderContext, UiRenderer},
     },
     resource::texture::{Texture, TextureKind, TextureResource},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    scene::{
+        camera::Camera,
+        debug::{Line, SceneDrawingContext},
+        light::directional::CSM_NUM_CASCADES,
+        mesh::surface::SurfaceData,
+        node::Node,
+        particle_system::ParticleSystem,
+        sprite::Sprite,
+        Scene, SceneContainer,
+    },
 };
 use fxhash::FxHashMap;
 use fyrox_core::algebra::Vector4;
@@ -91,19 +111,25 @@ use glow::HasContext;
 #[cfg(not(target_arch = "wasm32"))]
 use glutin::{
     context::PossiblyCurrentContext,
-    prelude::GlSurface,
     surface::{Surface, WindowSurface},
 };
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::any::TypeId;
-use std::{cell::RefCell, collections::hash_map::Entry, rc::Rc, sync::mpsc::Receiver};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, VecDeque},
+    rc::Rc,
+    sync::mpsc::Receiver,
+};
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 #[cfg(not(target_arch = "wasm32"))]
 use winit::window::Window;
 
 pub use stats::*;
 
+pub(crate) use headless::HeadlessRenderer;
+
 lazy_static! {
     static ref GBUFFER_PASS_NAME: ImmutableString = ImmutableString::new("GBuffer");
     static ref DIRECTIONAL_SHADOW_PASS_NAME: ImmutableString =
@@ -162,6 +188,15 @@ pub struct CsmSettings {
 
     /// Whether to use Percentage-Closer Filtering or not.
     pub pcf: bool,
+
+    /// Amount of cascades, must be in `[1; CSM_NUM_CASCADES]` range. The less cascades the better
+    /// performance is, but shadows of distant objects will be less detailed.
+    #[serde(default = "default_csm_cascade_count")]
+    pub cascade_count: usize,
+}
+
+fn default_csm_cascade_count() -> usize {
+    CSM_NUM_CASCADES
 }
 
 impl Default for CsmSettings {
@@ -171,10 +206,180 @@ impl Default for CsmSettings {
             size: 2048,
             precision: ShadowMapPrecision::Full,
             pcf: true,
+            cascade_count: CSM_NUM_CASCADES,
+        }
+    }
+}
+
+/// Bloom settings.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct BloomSettings {
+    /// Minimal luminance of a pixel to contribute to the bloom. Default value is 1.0, which means
+    /// that only pixels brighter than "white" will glow.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub threshold: f32,
+
+    /// Multiplier of the bloom color, that is added to the final frame.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub intensity: f32,
+
+    /// Amount of blur passes, the more passes the wider glow will be. Every pass adds two full-screen
+    /// draw calls.
+    #[reflect(min_value = 1.0, max_value = 16.0)]
+    pub blur_iterations: usize,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            intensity: 1.0,
+            blur_iterations: 1,
+        }
+    }
+}
+
+/// Simple color grading, that is applied to the final (tone mapped) frame. Default values define an
+/// identity transform. All the values are applied in sRGB color space in the following order:
+/// brightness and contrast, saturation, gamma. See [`Renderer::set_color_grading`] for more info.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct ColorGrading {
+    /// Gamma correction exponent, values above 1.0 make the frame brighter. Default value is 1.0.
+    #[reflect(min_value = 0.01, step = 0.05)]
+    pub gamma: f32,
+
+    /// Value, that is added to every color channel. Default value is 0.0.
+    #[reflect(step = 0.05)]
+    pub brightness: f32,
+
+    /// Scale of the color relative to the middle gray. Default value is 1.0.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub contrast: f32,
+
+    /// Scale of the color relative to its luminance, 0.0 makes the frame grayscale. Default value
+    /// is 1.0.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub saturation: f32,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            gamma: 1.0,
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+        }
+    }
+}
+
+/// Eye adaptation settings of automatic exposure (see [`crate::scene::camera::Exposure::Auto`]).
+/// See [`Renderer::set_auto_exposure`] for more info.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct AutoExposureSettings {
+    /// How fast the average luminance of previous frames approaches the luminance of the current
+    /// frame, in fractions per second. Default value is 0.3.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub adaptation_speed: f32,
+
+    /// Minimum exposure, that could be produced by automatic exposure. Prevents the frame from
+    /// becoming too dark when looking at bright light sources. Default value is 0.0.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub min_exposure: f32,
+
+    /// Maximum exposure, that could be produced by automatic exposure. Prevents dark areas from
+    /// being over-brightened. Default value is [`f32::MAX`].
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub max_exposure: f32,
+
+    /// Whether the average luminance should instantly follow the luminance of the current frame, when
+    /// the scene becomes darker. If `false`, the adaptation uses [`Self::adaptation_speed`] in both
+    /// directions. Default value is `true`.
+    #[serde(default = "default_instant_darkening")]
+    pub instant_darkening: bool,
+}
+
+fn default_instant_darkening() -> bool {
+    true
+}
+
+impl Default for AutoExposureSettings {
+    fn default() -> Self {
+        Self {
+            adaptation_speed: 0.3,
+            min_exposure: 0.0,
+            max_exposure: f32::MAX,
+            instant_darkening: true,
         }
     }
 }
 
+/// Defines how alpha-blended particles are rendered. See [`Renderer::set_particle_transparency`]
+/// for more info.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum TransparencyMode {
+    /// Particles are sorted back-to-front (both within a particle system and between particle
+    /// systems) and blended one by one. It gives correct result, but sorting is quite expensive
+    /// for large amount of particles and intersecting particle systems could still be blended in
+    /// wrong order.
+    #[default]
+    Sorted,
+    /// Weighted blended order-independent transparency. Particles are rendered in any order and
+    /// the result is approximated using weights, that depend on depth and opacity of fragments.
+    /// It is much faster for large amount of particles and does not have sorting artifacts, but
+    /// the result is slightly less accurate when there are many layers of opaque-ish particles.
+    /// Only particle systems, which material shader has `OitForward` render pass, are rendered
+    /// this way, the rest are still sorted. Order-independent particles are composed before the
+    /// sorted transparent meshes, so such meshes are always drawn over them.
+    WeightedBlended,
+}
+
+uuid_provider!(TransparencyMode = "efcd64b5-4d6a-40eb-b1b8-a9795d0f2595");
+
+bitflags::bitflags! {
+    /// A set of parts of a frame, that could be disabled independently for debugging purposes. See
+    /// [`Renderer::set_render_flags`].
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct RenderFlags: u32 {
+        /// Scenes. When disabled, scenes are not rendered at all, only the UI is visible.
+        const SCENE = 1 << 0;
+        /// Particle systems. When disabled, particle systems are not rendered in any pass,
+        /// including shadow passes.
+        const PARTICLES = 1 << 1;
+        /// Sprites. When disabled, sprites are not rendered in any pass, including shadow passes.
+        const SPRITES = 1 << 2;
+        /// User interface.
+        const UI = 1 << 3;
+    }
+}
+
+impl Default for RenderFlags {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl RenderFlags {
+    /// Returns `false` if the node belongs to a disabled part of the frame.
+    pub fn allows_node(&self, node: &Node) -> bool {
+        (self.contains(Self::PARTICLES) || node.cast::<ParticleSystem>().is_none())
+            && (self.contains(Self::SPRITES) || node.cast::<Sprite>().is_none())
+    }
+}
+
 /// Quality settings allows you to find optimal balance between performance and
 /// graphics quality.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
@@ -195,6 +400,11 @@ pub struct QualitySettings {
     /// Specifies the distance from the camera at which point shadows start to fade out.
     /// Shadows beyond this distance will gradually become less visible.
     pub point_shadows_fade_out_range: f32,
+    /// Maximum amount of point lights that can cast shadows per frame (per camera). Point shadows
+    /// are quite expensive, since every point light requires six render passes to fill its cube
+    /// shadow map. Lights exceeding this limit are rendered without shadows.
+    #[serde(default = "default_point_shadows_max_lights")]
+    pub point_shadows_max_lights: usize,
 
     /// Spot shadows
     /// Size of square shadow map texture in pixels
@@ -220,11 +430,24 @@ pub struct QualitySettings {
     /// Radius of sampling hemisphere used in SSAO, it defines much ambient
     /// occlusion will be in your scene.
     pub ssao_radius: f32,
+    /// Depth bias of SSAO samples, that is used to prevent self-occlusion artifacts on flat surfaces.
+    #[serde(default = "default_ssao_bias")]
+    pub ssao_bias: f32,
+    /// Amount of SSAO samples per pixel, max value is 32. The more samples the less noisy ambient
+    /// occlusion will be, but the more it will cost.
+    #[serde(default = "default_ssao_sample_count")]
+    pub ssao_sample_count: usize,
 
     /// Global switch to enable or disable light scattering. Each light can have
     /// its own scatter switch, but this one is able to globally disable scatter.
     pub light_scatter_enabled: bool,
 
+    /// Whether to use screen-space light grid or not. When enabled, the frame is split into tiles
+    /// and every point and spot light is shaded only in the tiles covered by its bounding sphere.
+    /// It significantly reduces the cost of lighting for scenes with lots of small lights.
+    #[serde(default = "default_use_light_grid")]
+    pub use_light_grid: bool,
+
     /// Whether to use Fast Approximate AntiAliasing or not.
     pub fxaa: bool,
 
@@ -233,6 +456,27 @@ pub struct QualitySettings {
 
     /// Whether to use bloom effect.
     pub use_bloom: bool,
+
+    /// Bloom settings. Has no effect if [`Self::use_bloom`] is `false`.
+    #[serde(default)]
+    pub bloom_settings: BloomSettings,
+}
+
+// Settings files without the field get the same value as the default settings.
+fn default_point_shadows_max_lights() -> usize {
+    QualitySettings::default().point_shadows_max_lights
+}
+
+fn default_use_light_grid() -> bool {
+    true
+}
+
+fn default_ssao_bias() -> f32 {
+    0.04
+}
+
+fn default_ssao_sample_count() -> usize {
+    32
 }
 
 impl Default for QualitySettings {
@@ -250,6 +494,7 @@ impl QualitySettings {
             point_shadows_enabled: true,
             point_soft_shadows: true,
             point_shadows_fade_out_range: 1.0,
+            point_shadows_max_lights: 32,
 
             spot_shadow_map_size: 2048,
             spot_shadows_distance: 20.0,
@@ -259,8 +504,11 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_bias: 0.04,
+            ssao_sample_count: 32,
 
             light_scatter_enabled: true,
+            use_light_grid: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
@@ -268,6 +516,7 @@ impl QualitySettings {
             fxaa: true,
 
             use_bloom: true,
+            bloom_settings: Default::default(),
 
             use_parallax_mapping: true,
 
@@ -283,6 +532,7 @@ impl QualitySettings {
             point_shadows_enabled: true,
             point_soft_shadows: true,
             point_shadows_fade_out_range: 1.0,
+            point_shadows_max_lights: 16,
 
             spot_shadow_map_size: 1024,
             spot_shadows_distance: 15.0,
@@ -292,8 +542,11 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_bias: 0.04,
+            ssao_sample_count: 32,
 
             light_scatter_enabled: true,
+            use_light_grid: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
@@ -301,6 +554,7 @@ impl QualitySettings {
             fxaa: true,
 
             use_bloom: true,
+            bloom_settings: Default::default(),
 
             use_parallax_mapping: true,
 
@@ -309,6 +563,7 @@ impl QualitySettings {
                 size: 2048,
                 precision: ShadowMapPrecision::Full,
                 pcf: true,
+                cascade_count: CSM_NUM_CASCADES,
             },
         }
     }
@@ -321,6 +576,7 @@ impl QualitySettings {
             point_shadows_enabled: true,
             point_soft_shadows: false,
             point_shadows_fade_out_range: 1.0,
+            point_shadows_max_lights: 8,
 
             spot_shadow_map_size: 512,
             spot_shadows_distance: 5.0,
@@ -330,8 +586,11 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_bias: 0.04,
+            ssao_sample_count: 16,
 
             light_scatter_enabled: false,
+            use_light_grid: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
@@ -339,6 +598,7 @@ impl QualitySettings {
             fxaa: true,
 
             use_bloom: true,
+            bloom_settings: Default::default(),
 
             use_parallax_mapping: false,
 
@@ -347,6 +607,7 @@ impl QualitySettings {
                 size: 512,
                 precision: ShadowMapPrecision::Full,
                 pcf: false,
+                cascade_count: CSM_NUM_CASCADES,
             },
         }
     }
@@ -359,6 +620,7 @@ impl QualitySettings {
             point_shadows_enabled: false,
             point_soft_shadows: false,
             point_shadows_fade_out_range: 1.0,
+            point_shadows_max_lights: 4,
 
             spot_shadow_map_size: 1,
             spot_shadows_distance: 0.0,
@@ -368,8 +630,11 @@ impl QualitySettings {
 
             use_ssao: false,
             ssao_radius: 0.5,
+            ssao_bias: 0.04,
+            ssao_sample_count: 8,
 
             light_scatter_enabled: false,
+            use_light_grid: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
@@ -377,6 +642,7 @@ impl QualitySettings {
             fxaa: false,
 
             use_bloom: false,
+            bloom_settings: Default::default(),
 
             use_parallax_mapping: false,
 
@@ -385,26 +651,44 @@ impl QualitySettings {
                 size: 512,
                 precision: ShadowMapPrecision::Half,
                 pcf: false,
+                cascade_count: CSM_NUM_CASCADES,
             },
         }
     }
 }
 
+/// Weight of the last frame in the average frame time, the smaller the value the smoother (and
+/// the slower to react to changes) the average is.
+const FRAME_TIME_SMOOTHING: f32 = 0.05;
+
 impl Statistics {
     /// Must be called before render anything.
-    fn begin_frame(&mut self) {
-        self.frame_start_time = instant::Instant::now();
+    pub(crate) fn begin_frame(&mut self) {
+        let current_time = instant::Instant::now();
+        self.frame_delta = current_time
+            .duration_since(self.frame_start_time)
+            .as_secs_f32();
+        self.frame_start_time = current_time;
         self.geometry = Default::default();
         self.lighting = Default::default();
+        self.culling = Default::default();
+        self.particles = 0;
     }
 
     /// Must be called before SwapBuffers but after all rendering is done.
-    fn end_frame(&mut self) {
+    pub(crate) fn end_frame(&mut self) {
         let current_time = instant::Instant::now();
 
         self.pure_frame_time = current_time
             .duration_since(self.frame_start_time)
             .as_secs_f32();
+        self.avg_frame_time = if self.avg_frame_time == 0.0 {
+            // Start from the first measured value, not from zero.
+            self.pure_frame_time
+        } else {
+            self.avg_frame_time
+                + (self.pure_frame_time - self.avg_frame_time) * FRAME_TIME_SMOOTHING
+        };
         self.frame_counter += 1;
 
         if current_time
@@ -419,7 +703,7 @@ impl Statistics {
     }
 
     /// Must be called after SwapBuffers to get capped frame time.
-    fn finalize(&mut self) {
+    pub(crate) fn finalize(&mut self) {
         self.capped_frame_time = instant::Instant::now()
             .duration_since(self.frame_start_time)
             .as_secs_f32();
@@ -432,9 +716,19 @@ impl Default for Statistics {
             pipeline: Default::default(),
             lighting: Default::default(),
             geometry: Default::default(),
+            culling: Default::default(),
+            particles: 0,
             pure_frame_time: 0.0,
+            avg_frame_time: 0.0,
             capped_frame_time: 0.0,
+            frame_delta: 0.0,
             frames_per_second: 0,
+            evicted_texture_memory: 0,
+            texture_memory: 0,
+            geometry_memory: 0,
+            geometry_buffers: 0,
+            paused: false,
+            pass_times: Default::default(),
             frame_counter: 0,
             frame_start_time: instant::Instant::now(),
             last_fps_commit_time: instant::Instant::now(),
@@ -456,6 +750,10 @@ pub struct AssociatedSceneData {
     /// Additional frame buffer for post processing.
     pub ldr_temp_framebuffer: FrameBuffer,
 
+    /// Accumulation frame buffer for order-independent transparency. See [`TransparencyMode`] for
+    /// more info.
+    pub oit_framebuffer: FrameBuffer,
+
     /// HDR renderer has be created per scene, because it contains
     /// scene luminance.
     pub hdr_renderer: HighDynamicRangeRenderer,
@@ -466,13 +764,16 @@ pub struct AssociatedSceneData {
 
     /// Rendering statistics for a scene.
     pub statistics: SceneStatistics,
+
+    /// Object id renderer of the scene, it is created only if object picking is enabled in the
+    /// renderer. See [`Renderer::set_object_picking`] for more info.
+    pub object_id_renderer: Option<ObjectIdRenderer>,
 }
 
 impl AssociatedSceneData {
     /// Creates new scene data.
     pub fn new(state: &PipelineState, width: usize, height: usize) -> Result<Self, FrameworkError> {
-        let mut depth_stencil_texture = GpuTexture::new(
-            state,
+        let mut depth_stencil_texture = state.create_texture(
             GpuTextureKind::Rectangle { width, height },
             PixelKind::D24S8,
             MinificationFilter::Nearest,
@@ -487,8 +788,7 @@ impl AssociatedSceneData {
 
         let depth_stencil = Rc::new(RefCell::new(depth_stencil_texture));
 
-        let hdr_frame_texture = GpuTexture::new(
-            state,
+        let hdr_frame_texture = state.create_texture(
             GpuTextureKind::Rectangle { width, height },
             // Intermediate scene frame will be rendered in HDR render target.
             PixelKind::RGBA16F,
@@ -498,20 +798,12 @@ impl AssociatedSceneData {
             None,
         )?;
 
-        let hdr_scene_framebuffer = FrameBuffer::new(
-            state,
-            Some(Attachment {
-                kind: AttachmentKind::DepthStencil,
-                texture: depth_stencil.clone(),
-            }),
-            vec![Attachment {
-                kind: AttachmentKind::Color,
-                texture: Rc::new(RefCell::new(hdr_frame_texture)),
-            }],
+        let hdr_scene_framebuffer = state.create_frame_buffer(
+            Some((AttachmentKind::DepthStencil, depth_stencil.clone())),
+            vec![Rc::new(RefCell::new(hdr_frame_texture))],
         )?;
 
-        let ldr_frame_texture = GpuTexture::new(
-            state,
+        let ldr_frame_texture = state.create_texture(
             GpuTextureKind::Rectangle { width, height },
             // Final scene frame is in standard sRGB space.
             PixelKind::RGBA8,
@@ -521,20 +813,12 @@ impl AssociatedSceneData {
             None,
         )?;
 
-        let ldr_scene_framebuffer = FrameBuffer::new(
-            state,
-            Some(Attachment {
-                kind: AttachmentKind::DepthStencil,
-                texture: depth_stencil.clone(),
-            }),
-            vec![Attachment {
-                kind: AttachmentKind::Color,
-                texture: Rc::new(RefCell::new(ldr_frame_texture)),
-            }],
+        let ldr_scene_framebuffer = state.create_frame_buffer(
+            Some((AttachmentKind::DepthStencil, depth_stencil.clone())),
+            vec![Rc::new(RefCell::new(ldr_frame_texture))],
         )?;
 
-        let ldr_temp_texture = GpuTexture::new(
-            state,
+        let ldr_temp_texture = state.create_texture(
             GpuTextureKind::Rectangle { width, height },
             // Final scene frame is in standard sRGB space.
             PixelKind::RGBA8,
@@ -544,16 +828,9 @@ impl AssociatedSceneData {
             None,
         )?;
 
-        let ldr_temp_framebuffer = FrameBuffer::new(
-            state,
-            Some(Attachment {
-                kind: AttachmentKind::DepthStencil,
-                texture: depth_stencil,
-            }),
-            vec![Attachment {
-                kind: AttachmentKind::Color,
-                texture: Rc::new(RefCell::new(ldr_temp_texture)),
-            }],
+        let ldr_temp_framebuffer = state.create_frame_buffer(
+            Some((AttachmentKind::DepthStencil, depth_stencil.clone())),
+            vec![Rc::new(RefCell::new(ldr_temp_texture))],
         )?;
 
         Ok(Self {
@@ -563,10 +840,77 @@ impl AssociatedSceneData {
             hdr_scene_framebuffer,
             ldr_scene_framebuffer,
             ldr_temp_framebuffer,
+            oit_framebuffer: oit::make_oit_framebuffer(state, width, height, depth_stencil)?,
             statistics: Default::default(),
+            object_id_renderer: None,
         })
     }
 
+    /// Changes the size of every frame buffer of the scene data. Unlike re-creation of the scene data,
+    /// this method reuses existing GPU objects and only re-allocates storage of their textures.
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        self.gbuffer.resize(state, width, height)?;
+        self.hdr_scene_framebuffer.resize(state, width, height)?;
+        self.ldr_scene_framebuffer.resize(state, width, height)?;
+        self.ldr_temp_framebuffer.resize(state, width, height)?;
+        self.oit_framebuffer.resize(state, width, height)?;
+        self.bloom_renderer.resize(state, width, height)?;
+        if let Some(object_id_renderer) = self.object_id_renderer.as_mut() {
+            object_id_renderer.resize(state, width, height)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the given region of the temporary LDR frame buffer to the same region of the LDR frame
+    /// buffer.
+    fn copy_ldr_temp_to_scene_framebuffer(&mut self, state: &PipelineState, viewport: Rect<i32>) {
+        state.blit_framebuffer(
+            self.ldr_temp_framebuffer.id(),
+            self.ldr_scene_framebuffer.id(),
+            viewport.x(),
+            viewport.y(),
+            viewport.x() + viewport.w(),
+            viewport.y() + viewport.h(),
+            viewport.x(),
+            viewport.y(),
+            viewport.x() + viewport.w(),
+            viewport.y() + viewport.h(),
+            true,
+            false,
+            false,
+        );
+    }
+
+    /// Copies depth and stencil of the G-Buffer to the given region of the LDR frame buffer. It
+    /// squeezes them the same way as tone mapping squeezes the frame, so anything rendered in the
+    /// LDR frame buffer is depth tested against the depth of the camera, that owns the region.
+    fn squeeze_depth_stencil_to_ldr_framebuffer(
+        &mut self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+    ) {
+        state.blit_framebuffer(
+            self.gbuffer.framebuffer().id(),
+            self.ldr_scene_framebuffer.id(),
+            0,
+            0,
+            self.gbuffer.width,
+            self.gbuffer.height,
+            viewport.x(),
+            viewport.y(),
+            viewport.x() + viewport.w(),
+            viewport.y() + viewport.h(),
+            false,
+            true,
+            true,
+        );
+    }
+
     fn copy_depth_stencil_to_scene_framebuffer(&mut self, state: &PipelineState) {
         state.blit_framebuffer(
             self.gbuffer.framebuffer().id(),
@@ -607,6 +951,117 @@ impl AssociatedSceneData {
     }
 }
 
+// Associated scene data used for offscreen rendering. It is destroyed if it wasn't used for some
+// time, because there's no way to tell whether a render target is still used by the user or not.
+struct OffscreenSceneData {
+    scene_data: AssociatedSceneData,
+    time_to_live: TimeToLive,
+}
+
+fn create_scene_data(
+    scene_handle: Handle<Scene>,
+    state: &PipelineState,
+    frame_size: Vector2<f32>,
+) -> AssociatedSceneData {
+    let width = frame_size.x as usize;
+    let height = frame_size.y as usize;
+
+    Log::info(format!(
+        "A new associated scene rendering data was created for scene {}!",
+        scene_handle
+    ));
+
+    AssociatedSceneData::new(state, width, height).unwrap()
+}
+
+fn fit_scene_data(
+    data: &mut AssociatedSceneData,
+    scene_handle: Handle<Scene>,
+    state: &PipelineState,
+    frame_size: Vector2<f32>,
+) {
+    if data.gbuffer.width != frame_size.x as i32 || data.gbuffer.height != frame_size.y as i32 {
+        let width = frame_size.x as usize;
+        let height = frame_size.y as usize;
+
+        Log::info(format!(
+            "Associated scene rendering data was resized for scene {}, because render frame size was changed. Old is {}x{}, new {}x{}!",
+            scene_handle,
+            data.gbuffer.width,
+            data.gbuffer.height,
+            width,
+            height
+        ));
+
+        if let Err(e) = data.resize(state, width, height) {
+            Log::err(format!(
+                "Unable to resize associated scene rendering data, re-creating it. Reason: {:?}",
+                e
+            ));
+            *data = AssociatedSceneData::new(state, width, height).unwrap();
+        }
+    }
+}
+
+fn obtain_scene_data<'a>(
+    map: &'a mut FxHashMap<Handle<Scene>, AssociatedSceneData>,
+    scene_handle: Handle<Scene>,
+    state: &PipelineState,
+    frame_size: Vector2<f32>,
+) -> &'a mut AssociatedSceneData {
+    let data = map
+        .entry(scene_handle)
+        .or_insert_with(|| create_scene_data(scene_handle, state, frame_size));
+    fit_scene_data(data, scene_handle, state, frame_size);
+    data
+}
+
+fn obtain_offscreen_scene_data<'a>(
+    map: &'a mut FxHashMap<(Handle<Scene>, u64), OffscreenSceneData>,
+    scene_handle: Handle<Scene>,
+    render_target: &TextureResource,
+    state: &PipelineState,
+    frame_size: Vector2<f32>,
+) -> &'a mut AssociatedSceneData {
+    let entry = map
+        .entry((scene_handle, render_target.key()))
+        .or_insert_with(|| OffscreenSceneData {
+            scene_data: create_scene_data(scene_handle, state, frame_size),
+            time_to_live: Default::default(),
+        });
+    entry.time_to_live = TimeToLive::default();
+    fit_scene_data(&mut entry.scene_data, scene_handle, state, frame_size);
+    &mut entry.scene_data
+}
+
+fn sanitize_shadow_map_size(size: usize, max_size: i32) -> usize {
+    // Largest power of two that does not exceed the maximum supported size.
+    let max_size = (max_size.max(1) as usize + 1).next_power_of_two() / 2;
+    size.max(1)
+        .next_power_of_two()
+        .clamp(16.min(max_size), max_size)
+}
+
+fn sanitize_quality_settings(
+    mut settings: QualitySettings,
+    capabilities: &RendererCapabilities,
+) -> QualitySettings {
+    // Shadow maps must have power-of-two sizes that are supported by current GPU.
+    let max_texture_size = capabilities.max_texture_size as i32;
+    let max_cube_map_size = capabilities.max_cube_map_size as i32;
+    settings.spot_shadow_map_size =
+        sanitize_shadow_map_size(settings.spot_shadow_map_size, max_texture_size);
+    settings.csm_settings.size =
+        sanitize_shadow_map_size(settings.csm_settings.size, max_texture_size);
+    settings.point_shadow_map_size =
+        sanitize_shadow_map_size(settings.point_shadow_map_size, max_cube_map_size);
+    settings.csm_settings.cascade_count = settings
+        .csm_settings
+        .cascade_count
+        .clamp(1, CSM_NUM_CASCADES);
+    settings
+}
+
 pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
     Matrix4::new_orthographic(
         0.0,
@@ -622,12 +1077,88 @@ pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
     ))
 }
 
+/// Capabilities of the graphics device, that are queried once on renderer creation. They could be used to
+/// check sizes of textures or frame buffers before allocating them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RendererCapabilities {
+    /// Maximum width or height of a texture (in pixels).
+    pub max_texture_size: u32,
+    /// Maximum width or height of a face of a cube map texture (in pixels).
+    pub max_cube_map_size: u32,
+    /// Maximum anisotropy level, that could be used for texture filtering. It is `1.0` if
+    /// anisotropic filtering is not supported.
+    pub max_anisotropy: f32,
+    /// Maximum amount of samples for multisampled render targets.
+    pub max_samples: u32,
+    /// Whether floating-point textures could be used as render targets or not.
+    pub float_render_targets: bool,
+}
+
+impl RendererCapabilities {
+    fn query(state: &PipelineState) -> Self {
+        let extensions = state.gl.supported_extensions();
+        unsafe {
+            Self {
+                max_texture_size: state.gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE).max(0) as u32,
+                max_cube_map_size: state
+                    .gl
+                    .get_parameter_i32(glow::MAX_CUBE_MAP_TEXTURE_SIZE)
+                    .max(0) as u32,
+                max_anisotropy: if extensions.contains("GL_EXT_texture_filter_anisotropic") {
+                    state
+                        .gl
+                        .get_parameter_f32(glow::MAX_TEXTURE_MAX_ANISOTROPY_EXT)
+                        .max(1.0)
+                } else {
+                    1.0
+                },
+                max_samples: state.gl.get_parameter_i32(glow::MAX_SAMPLES).max(0) as u32,
+                // Float render targets are core in desktop OpenGL 3.3, but require an extension on
+                // OpenGL ES 3.0.
+                float_render_targets: state.gl_kind() == GlKind::OpenGL
+                    || extensions.contains("GL_EXT_color_buffer_float"),
+            }
+        }
+    }
+}
+
+/// Defines which image will be shown as the result of scene rendering. It is useful for debugging
+/// purposes, for example to check whether lighting issues come from wrong normals or not.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GBufferDebugView {
+    /// Lit and post-processed frame, this is the default mode.
+    #[default]
+    Final,
+    /// Unlit diffuse color of the objects.
+    Albedo,
+    /// World-space normals, packed into `[0; 1]` range.
+    Normal,
+    /// Linear depth, black - near, white - far clipping plane.
+    Depth,
+    /// World-space positions, reconstructed from depth. Every axis repeats with one meter period.
+    Position,
+}
+
+/// A copy of the contents of the back buffer, captured at the end of a frame. See
+/// [`Renderer::request_frame_capture`] for more info.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedFrame {
+    /// Width of the frame in pixels.
+    pub width: u32,
+    /// Height of the frame in pixels.
+    pub height: u32,
+    /// Tightly packed RGBA8 pixels of the frame, rows are ordered from top to bottom.
+    pub pixels: Vec<u8>,
+}
+
 /// See module docs.
 pub struct Renderer {
     backbuffer: FrameBuffer,
     scene_render_passes: Vec<Rc<RefCell<dyn SceneRenderPass>>>,
     deferred_light_renderer: DeferredLightRenderer,
     flat_shader: FlatShader,
+    gbuffer_debug_shader: GBufferDebugShader,
+    wireframe_shader: WireframeShader,
     /// Dummy white one pixel texture which will be used as stub when rendering
     /// something without texture specified.
     pub white_dummy: Rc<RefCell<GpuTexture>>,
@@ -636,6 +1167,10 @@ pub struct Renderer {
     // Dummy one pixel texture with (0, 1, 0) vector is used as stub when rendering
     // something without normal map.
     normal_dummy: Rc<RefCell<GpuTexture>>,
+    // Textures that are used instead of missing diffuse and normal maps when rendering
+    // scene geometry. By default they're the white and normal dummies.
+    fallback_diffuse: Rc<RefCell<GpuTexture>>,
+    fallback_normal: Rc<RefCell<GpuTexture>>,
     // Dummy one pixel texture used as stub when rendering something without a
     // metallic texture. Default metalness is 0.0
     metallic_dummy: Rc<RefCell<GpuTexture>>,
@@ -649,8 +1184,14 @@ pub struct Renderer {
     quality_settings: QualitySettings,
     /// Debug renderer instance can be used for debugging purposes
     pub debug_renderer: DebugRenderer,
+    // Immediate debug geometry, that is drawn in every scene and cleared at the end of each frame.
+    debug_drawing_context: SceneDrawingContext,
     /// A set of associated data for each scene that was rendered.
     pub scene_data_map: FxHashMap<Handle<Scene>, AssociatedSceneData>,
+    // A set of associated data for each pair of scene and render target, that was used in
+    // offscreen rendering via `Renderer::render_scene_to_texture`. Unused entries are removed
+    // in `Renderer::update_caches`.
+    offscreen_scene_data_map: FxHashMap<(Handle<Scene>, u64), OffscreenSceneData>,
     backbuffer_clear_color: Color,
     /// Texture cache with GPU textures.
     pub texture_cache: TextureCache,
@@ -664,6 +1205,34 @@ pub struct Renderer {
     // TextureId -> FrameBuffer mapping. This mapping is used for temporal frame buffers
     // like ones used to render UI instances.
     ui_frame_buffers: FxHashMap<u64, FrameBuffer>,
+    frame_capture_requested: bool,
+    captured_frame: Option<CapturedFrame>,
+    strict_error_checking: bool,
+    strict_timing: bool,
+    reverse_z: bool,
+    capabilities: RendererCapabilities,
+    minimized: bool,
+    monitor_refresh_rate: Option<f32>,
+    frustum_culling: bool,
+    object_picking: bool,
+    debug_view: GBufferDebugView,
+    color_grading: ColorGrading,
+    color_lut: Option<Rc<RefCell<GpuTexture>>>,
+    color_lut_intensity: f32,
+    auto_exposure: Option<AutoExposureSettings>,
+    particle_transparency: TransparencyMode,
+    particle_budget: Option<usize>,
+    render_flags: RenderFlags,
+    paused: bool,
+    step_requested: bool,
+    gpu_timers: GpuTimers,
+    oit_renderer: OitRenderer,
+    // Textures, that were loaded by resource manager, but not yet uploaded to GPU.
+    pending_texture_uploads: VecDeque<TextureResource>,
+    upload_budget: usize,
+    soft_particle_scale: f32,
+    post_render_hook: Option<(PostRenderHookLayer, PostRenderHook)>,
+    post_render_drawing_context: DrawingContext,
     /// Pipeline state.
     pub state: SharedPipelineState,
 }
@@ -673,8 +1242,7 @@ fn make_ui_frame_buffer(
     state: &PipelineState,
     pixel_kind: PixelKind,
 ) -> Result<FrameBuffer, FrameworkError> {
-    let color_texture = Rc::new(RefCell::new(GpuTexture::new(
-        state,
+    let color_texture = Rc::new(RefCell::new(state.create_texture(
         GpuTextureKind::Rectangle {
             width: frame_size.x as usize,
             height: frame_size.y as usize,
@@ -686,8 +1254,7 @@ fn make_ui_frame_buffer(
         None,
     )?));
 
-    let depth_stencil = Rc::new(RefCell::new(GpuTexture::new(
-        state,
+    let depth_stencil = Rc::new(RefCell::new(state.create_texture(
         GpuTextureKind::Rectangle {
             width: frame_size.x as usize,
             height: frame_size.y as usize,
@@ -699,19 +1266,36 @@ fn make_ui_frame_buffer(
         None,
     )?));
 
-    FrameBuffer::new(
-        state,
-        Some(Attachment {
-            kind: AttachmentKind::DepthStencil,
-            texture: depth_stencil,
-        }),
-        vec![Attachment {
-            kind: AttachmentKind::Color,
-            texture: color_texture,
-        }],
+    state.create_frame_buffer(
+        Some((AttachmentKind::DepthStencil, depth_stencil)),
+        vec![color_texture],
     )
 }
 
+/// Defines where drawings of the post-render hook will be placed relative to the user interface.
+/// See [`Renderer::set_post_render_hook`] for more info.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum PostRenderHookLayer {
+    /// Drawings will be placed on top of the scenes, but under the user interface.
+    #[default]
+    UnderUi,
+    /// Drawings will be placed on top of everything, including the user interface.
+    OverUi,
+}
+
+/// A context for post-render hook.
+pub struct PostRenderContext<'a> {
+    /// Drawing context, that is cleared before each call of the hook. Everything that is drawn in
+    /// it will be rendered on top of the final frame of the scenes.
+    pub drawing_context: &'a mut DrawingContext,
+    /// Size of the frame (window) in pixels.
+    pub frame_size: Vector2<f32>,
+}
+
+/// A callback, that is called once per frame after all the scenes were rendered. See
+/// [`Renderer::set_post_render_hook`] for more info.
+pub type PostRenderHook = Box<dyn FnMut(&mut PostRenderContext)>;
+
 /// A context for custom scene render passes.
 pub struct SceneRenderPassContext<'a, 'b> {
     /// A pipeline state that is used as a wrapper to underlying graphics API.
@@ -831,6 +1415,54 @@ pub trait SceneRenderPass {
     fn source_type_id(&self) -> TypeId;
 }
 
+/// Reverses depth range of the given projection matrix if `reverse_z` is `true`, so the near plane
+/// is mapped to 1.0 and the far plane to 0.0 in the depth buffer. See [`Renderer::set_reverse_z`].
+pub(crate) fn depth_range_projection(
+    mut projection: Matrix4<f32>,
+    reverse_z: bool,
+) -> Matrix4<f32> {
+    if reverse_z {
+        for i in 0..4 {
+            projection[(2, i)] = -projection[(2, i)];
+        }
+    }
+    projection
+}
+
+/// Returns projection matrix of the camera with the depth range used by the renderer.
+pub(crate) fn camera_projection_matrix(camera: &Camera, reverse_z: bool) -> Matrix4<f32> {
+    depth_range_projection(camera.projection_matrix(), reverse_z)
+}
+
+/// Returns view-projection matrix of the camera with the depth range used by the renderer.
+pub(crate) fn camera_view_projection_matrix(camera: &Camera, reverse_z: bool) -> Matrix4<f32> {
+    camera_projection_matrix(camera, reverse_z) * camera.view_matrix()
+}
+
+/// Returns depth comparison function, that passes fragments closer to the camera.
+pub(crate) fn depth_func(reverse_z: bool) -> CompareFunc {
+    if reverse_z {
+        CompareFunc::GreaterOrEqual
+    } else {
+        CompareFunc::LessOrEqual
+    }
+}
+
+/// Returns depth of the far plane, depth buffers of cameras are cleared with this value.
+pub(crate) fn far_plane_depth(reverse_z: bool) -> f32 {
+    if reverse_z {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+fn has_enabled_camera(scene: &Scene) -> bool {
+    scene.graph.linear_iter().any(|node| {
+        node.is_globally_enabled() && node.cast::<Camera>().map_or(false, |c| c.is_enabled())
+    })
+}
+
 fn blit_pixels(
     state: &PipelineState,
     framebuffer: &mut FrameBuffer,
@@ -839,9 +1471,9 @@ fn blit_pixels(
     viewport: Rect<i32>,
     quad: &GeometryBuffer,
 ) -> Result<DrawCallStatistics, FrameworkError> {
-    framebuffer.draw(
+    state.draw(
+        framebuffer,
         quad,
-        state,
         viewport,
         &shader.program,
         &DrawParameters {
@@ -875,48 +1507,174 @@ fn blit_pixels(
     )
 }
 
-#[allow(missing_docs)] // TODO
-pub struct LightData<const N: usize = 16> {
-    pub count: usize,
-    pub color_radius: [Vector4<f32>; N],
-    pub position: [Vector3<f32>; N],
-    pub direction: [Vector3<f32>; N],
-    pub parameters: [Vector2<f32>; N],
-}
+#[allow(clippy::too_many_arguments)]
+fn blit_gbuffer_debug_view(
+    state: &PipelineState,
+    framebuffer: &mut FrameBuffer,
+    gbuffer: &GBuffer,
+    mode: GBufferDebugView,
+    camera: &Camera,
+    reverse_z: bool,
+    shader: &GBufferDebugShader,
+    viewport: Rect<i32>,
+    quad: &GeometryBuffer,
+) -> Result<DrawCallStatistics, FrameworkError> {
+    let (texture, mode_index) = match mode {
+        GBufferDebugView::Final | GBufferDebugView::Albedo => (gbuffer.diffuse_texture(), 0),
+        GBufferDebugView::Normal => (gbuffer.normal_texture(), 1),
+        GBufferDebugView::Depth => (gbuffer.depth(), 2),
+        GBufferDebugView::Position => (gbuffer.depth(), 3),
+    };
+
+    let inv_projection = camera_projection_matrix(camera, reverse_z)
+        .try_inverse()
+        .unwrap_or_default();
+    let inv_view_projection = camera_view_projection_matrix(camera, reverse_z)
+        .try_inverse()
+        .unwrap_or_default();
 
-impl<const N: usize> Default for LightData<N> {
-    fn default() -> Self {
-        Self {
-            count: 0,
-            color_radius: [Default::default(); N],
-            position: [Default::default(); N],
-            direction: [Default::default(); N],
-            parameters: [Default::default(); N],
-        }
-    }
+    framebuffer.draw(
+        quad,
+        state,
+        viewport,
+        &shader.program,
+        &DrawParameters {
+            cull_face: None,
+            color_write: Default::default(),
+            depth_write: false,
+            stencil_test: None,
+            depth_test: false,
+            blend: None,
+            stencil_op: Default::default(),
+        },
+        ElementRange::Full,
+        |mut program_binding| {
+            program_binding
+                .set_matrix4(&shader.wvp_matrix, &{
+                    Matrix4::new_orthographic(
+                        0.0,
+                        viewport.w() as f32,
+                        viewport.h() as f32,
+                        0.0,
+                        -1.0,
+                        1.0,
+                    ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+                        viewport.w() as f32,
+                        viewport.h() as f32,
+                        0.0,
+                    ))
+                })
+                .set_texture(&shader.diffuse_texture, &texture)
+                .set_texture(&shader.depth_texture, &gbuffer.depth())
+                .set_matrix4(&shader.inv_proj_matrix, &inv_projection)
+                .set_matrix4(&shader.inv_view_proj_matrix, &inv_view_projection)
+                .set_f32(&shader.z_far, camera.projection().z_far())
+                .set_f32(&shader.far_depth, far_plane_depth(reverse_z))
+                .set_i32(&shader.mode, mode_index);
+        },
+    )
 }
 
-#[allow(missing_docs)] // TODO
-pub struct MaterialContext<'a, 'b, 'c> {
-    pub material: &'a Material,
-    pub program_binding: &'a mut GpuProgramBinding<'b, 'c>,
-    pub texture_cache: &'a mut TextureCache,
-    pub matrix_storage: &'a mut MatrixStorageCache,
-    pub persistent_identifier: PersistentIdentifier,
+/// Color of the edges of the geometry, when a scene is rendered in wireframe mode.
+const WIREFRAME_COLOR: Color = Color::WHITE;
 
-    // Built-in uniforms.
-    pub world_matrix: &'a Matrix4<f32>,
-    pub view_projection_matrix: &'a Matrix4<f32>,
-    pub wvp_matrix: &'a Matrix4<f32>,
-    pub bone_matrices: &'a [Matrix4<f32>],
-    pub use_skeletal_animation: bool,
-    pub use_pom: bool,
-    pub light_position: &'a Vector3<f32>,
-    pub blend_shapes_storage: Option<&'a TextureResource>,
-    pub blend_shapes_weights: &'a [f32],
-    pub light_data: Option<&'a LightData>,
-    pub ambient_light: Color,
-    // TODO: Add depth pre-pass to remove Option here. Current architecture allows only forward
+fn blit_wireframe(
+    state: &PipelineState,
+    framebuffer: &mut FrameBuffer,
+    gbuffer: &GBuffer,
+    reverse_z: bool,
+    shader: &WireframeShader,
+    viewport: Rect<i32>,
+    quad: &GeometryBuffer,
+) -> Result<DrawCallStatistics, FrameworkError> {
+    framebuffer.draw(
+        quad,
+        state,
+        viewport,
+        &shader.program,
+        &DrawParameters {
+            cull_face: None,
+            color_write: Default::default(),
+            depth_write: false,
+            stencil_test: None,
+            depth_test: false,
+            blend: None,
+            stencil_op: Default::default(),
+        },
+        ElementRange::Full,
+        |mut program_binding| {
+            program_binding
+                .set_matrix4(&shader.wvp_matrix, &{
+                    Matrix4::new_orthographic(
+                        0.0,
+                        viewport.w() as f32,
+                        viewport.h() as f32,
+                        0.0,
+                        -1.0,
+                        1.0,
+                    ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+                        viewport.w() as f32,
+                        viewport.h() as f32,
+                        0.0,
+                    ))
+                })
+                .set_texture(&shader.depth_texture, &gbuffer.depth())
+                .set_linear_color(&shader.wire_color, &WIREFRAME_COLOR)
+                .set_f32(&shader.far_depth, far_plane_depth(reverse_z));
+        },
+    )
+}
+
+#[allow(missing_docs)] // TODO
+pub struct LightData<const N: usize = 16> {
+    pub count: usize,
+    pub color_radius: [Vector4<f32>; N],
+    pub position: [Vector3<f32>; N],
+    pub direction: [Vector3<f32>; N],
+    pub parameters: [Vector2<f32>; N],
+    /// `1` for lights with [`crate::scene::light::LightAttenuation::InverseSquare`] attenuation, `0` -
+    /// otherwise.
+    pub inverse_square_attenuation: [i32; N],
+}
+
+impl<const N: usize> Default for LightData<N> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            color_radius: [Default::default(); N],
+            position: [Default::default(); N],
+            direction: [Default::default(); N],
+            parameters: [Default::default(); N],
+            inverse_square_attenuation: [0; N],
+        }
+    }
+}
+
+#[allow(missing_docs)] // TODO
+pub struct MaterialContext<'a, 'b, 'c> {
+    pub material: &'a Material,
+    pub program_binding: &'a mut GpuProgramBinding<'b, 'c>,
+    pub texture_cache: &'a mut TextureCache,
+    pub matrix_storage: &'a mut MatrixStorageCache,
+    pub persistent_identifier: PersistentIdentifier,
+
+    // Built-in uniforms.
+    pub world_matrix: &'a Matrix4<f32>,
+    pub view_projection_matrix: &'a Matrix4<f32>,
+    pub wvp_matrix: &'a Matrix4<f32>,
+    pub bone_matrices: &'a [Matrix4<f32>],
+    pub use_skeletal_animation: bool,
+    /// World matrices of instances, that are drawn in a single instanced draw call. Empty if the
+    /// draw call is not instanced.
+    pub instance_matrices: &'a [Matrix4<f32>],
+    pub use_pom: bool,
+    pub light_position: &'a Vector3<f32>,
+    pub blend_shapes_storage: Option<&'a TextureResource>,
+    pub blend_shapes_weights: &'a [f32],
+    pub light_data: Option<&'a LightData>,
+    pub ambient_light: Color,
+    pub soft_particle_scale: f32,
+    // TODO: Add depth pre-pass to remove Option here. Current architecture allows only forward
     // renderer to have access to depth buffer that is available from G-Buffer.
     pub scene_depth: Option<&'a Rc<RefCell<GpuTexture>>>,
 
@@ -931,6 +1689,8 @@ pub struct MaterialContext<'a, 'b, 'c> {
     pub white_dummy: &'a Rc<RefCell<GpuTexture>>,
     pub black_dummy: &'a Rc<RefCell<GpuTexture>>,
     pub volume_dummy: &'a Rc<RefCell<GpuTexture>>,
+    /// Used instead of a missing diffuse texture (`diffuseTexture` sampler with white fallback).
+    pub diffuse_dummy: &'a Rc<RefCell<GpuTexture>>,
 }
 
 #[allow(missing_docs)] // TODO
@@ -967,6 +1727,25 @@ pub fn apply_material(ctx: MaterialContext) {
         ctx.program_binding
             .set_bool(location, ctx.use_skeletal_animation);
     }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::InstanceMatrices as usize] {
+        let active_sampler = ctx.program_binding.active_sampler();
+
+        let storage = ctx
+            .matrix_storage
+            .try_bind_and_upload(
+                ctx.program_binding.state,
+                ctx.persistent_identifier,
+                ctx.instance_matrices,
+                active_sampler,
+            )
+            .expect("Failed to upload instance matrices!");
+
+        ctx.program_binding.set_texture(location, storage.texture());
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::UseInstancing as usize] {
+        ctx.program_binding
+            .set_bool(location, !ctx.instance_matrices.is_empty());
+    }
     if let Some(location) = &built_in_uniforms[BuiltInUniform::CameraPosition as usize] {
         ctx.program_binding
             .set_vector3(location, ctx.camera_position);
@@ -996,6 +1775,10 @@ pub fn apply_material(ctx: MaterialContext) {
     if let Some(location) = &built_in_uniforms[BuiltInUniform::UsePOM as usize] {
         ctx.program_binding.set_bool(location, ctx.use_pom);
     }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::SoftParticleScale as usize] {
+        ctx.program_binding
+            .set_f32(location, ctx.soft_particle_scale);
+    }
     if let Some(location) = &built_in_uniforms[BuiltInUniform::LightPosition as usize] {
         ctx.program_binding
             .set_vector3(location, ctx.light_position);
@@ -1026,6 +1809,13 @@ pub fn apply_material(ctx: MaterialContext) {
             ctx.program_binding
                 .set_vector2_slice(location, &light_data.parameters);
         }
+
+        if let Some(location) =
+            &built_in_uniforms[BuiltInUniform::LightsInverseSquareAttenuation as usize]
+        {
+            ctx.program_binding
+                .set_i32_slice(location, &light_data.inverse_square_attenuation);
+        }
     }
 
     if let Some(location) = &built_in_uniforms[BuiltInUniform::AmbientLight as usize] {
@@ -1057,6 +1847,7 @@ pub fn apply_material(ctx: MaterialContext) {
     }
 
     // Apply material properties.
+    let mut diffuse_texture_srgb = false;
     for (name, value) in ctx.material.properties() {
         if let Some(uniform) = ctx.program_binding.uniform_location(name) {
             match value {
@@ -1098,11 +1889,21 @@ pub fn apply_material(ctx: MaterialContext) {
                         .as_ref()
                         .and_then(|t| ctx.texture_cache.get(ctx.program_binding.state, t))
                         .unwrap_or(match fallback {
+                            SamplerFallback::White if name.as_str() == "diffuseTexture" => {
+                                ctx.diffuse_dummy
+                            }
                             SamplerFallback::White => ctx.white_dummy,
                             SamplerFallback::Normal => ctx.normal_dummy,
                             SamplerFallback::Black => ctx.black_dummy,
                         });
 
+                    if name.as_str() == "diffuseTexture" {
+                        diffuse_texture_srgb = matches!(
+                            texture.borrow().pixel_kind(),
+                            PixelKind::SRGB8 | PixelKind::SRGBA8
+                        );
+                    }
+
                     ctx.program_binding.set_texture(&uniform, texture);
                 }
                 PropertyValue::FloatArray(v) => {
@@ -1135,6 +1936,11 @@ pub fn apply_material(ctx: MaterialContext) {
             }
         }
     }
+
+    // Must be set for every draw call, because programs are shared across materials.
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::DiffuseTextureSrgb as usize] {
+        ctx.program_binding.set_bool(location, diffuse_texture_srgb);
+    }
 }
 
 impl Renderer {
@@ -1144,8 +1950,6 @@ impl Renderer {
         resource_manager: &ResourceManager,
         gl_kind: GlKind,
     ) -> Result<Self, FrameworkError> {
-        let settings = QualitySettings::default();
-
         let (texture_event_sender, texture_event_receiver) = std::sync::mpsc::channel();
 
         resource_manager
@@ -1168,31 +1972,51 @@ impl Renderer {
             state.gl.supported_extensions()
         ));
 
+        let capabilities = RendererCapabilities::query(&state);
+        Log::info(format!("Renderer capabilities: {:?}", capabilities));
+
+        let settings = sanitize_quality_settings(QualitySettings::default(), &capabilities);
+
         let mut shader_cache = ShaderCache::default();
 
         for shader in ShaderResource::standard_shaders() {
             shader_cache.get(&state, &shader);
         }
 
+        let white_dummy = Rc::new(RefCell::new(state.create_texture(
+            GpuTextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            PixelKind::RGBA8,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            Some(&[255u8, 255u8, 255u8, 255u8]),
+        )?));
+        let normal_dummy = Rc::new(RefCell::new(state.create_texture(
+            GpuTextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            PixelKind::RGBA8,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            Some(&[128u8, 128u8, 255u8, 255u8]),
+        )?));
+
         Ok(Self {
             backbuffer: FrameBuffer::backbuffer(&state),
             frame_size,
             deferred_light_renderer: DeferredLightRenderer::new(&state, frame_size, &settings)?,
             flat_shader: FlatShader::new(&state)?,
-            white_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
-                GpuTextureKind::Rectangle {
-                    width: 1,
-                    height: 1,
-                },
-                PixelKind::RGBA8,
-                MinificationFilter::Linear,
-                MagnificationFilter::Linear,
-                1,
-                Some(&[255u8, 255u8, 255u8, 255u8]),
-            )?)),
-            black_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
+            gbuffer_debug_shader: GBufferDebugShader::new(&state)?,
+            wireframe_shader: WireframeShader::new(&state)?,
+            fallback_diffuse: white_dummy.clone(),
+            fallback_normal: normal_dummy.clone(),
+            white_dummy,
+            black_dummy: Rc::new(RefCell::new(state.create_texture(
                 GpuTextureKind::Rectangle {
                     width: 1,
                     height: 1,
@@ -1203,8 +2027,7 @@ impl Renderer {
                 1,
                 Some(&[0u8, 0u8, 0u8, 255u8]),
             )?)),
-            environment_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
+            environment_dummy: Rc::new(RefCell::new(state.create_texture(
                 GpuTextureKind::Cube {
                     width: 1,
                     height: 1,
@@ -1222,20 +2045,8 @@ impl Renderer {
                     0u8, 0u8, 0u8, 255u8, // neg-z
                 ]),
             )?)),
-            normal_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
-                GpuTextureKind::Rectangle {
-                    width: 1,
-                    height: 1,
-                },
-                PixelKind::RGBA8,
-                MinificationFilter::Linear,
-                MagnificationFilter::Linear,
-                1,
-                Some(&[128u8, 128u8, 255u8, 255u8]),
-            )?)),
-            metallic_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
+            normal_dummy,
+            metallic_dummy: Rc::new(RefCell::new(state.create_texture(
                 GpuTextureKind::Rectangle {
                     width: 1,
                     height: 1,
@@ -1246,8 +2057,7 @@ impl Renderer {
                 1,
                 Some(&[0u8, 0u8, 0u8, 0u8]),
             )?)),
-            volume_dummy: Rc::new(RefCell::new(GpuTexture::new(
-                &state,
+            volume_dummy: Rc::new(RefCell::new(state.create_texture(
                 GpuTextureKind::Volume {
                     width: 1,
                     height: 1,
@@ -1259,31 +2069,422 @@ impl Renderer {
                 1,
                 Some(&[0u8, 0u8, 0u8, 0u8]),
             )?)),
-            quad: GeometryBuffer::from_surface_data(
+            quad: state.create_geometry_buffer(
                 &SurfaceData::make_unit_xy_quad(),
                 GeometryBufferKind::StaticDraw,
-                &state,
             )?,
             ui_renderer: UiRenderer::new(&state)?,
             quality_settings: settings,
             debug_renderer: DebugRenderer::new(&state)?,
+            debug_drawing_context: Default::default(),
             scene_data_map: Default::default(),
+            offscreen_scene_data_map: Default::default(),
             backbuffer_clear_color: Color::BLACK,
             texture_cache: Default::default(),
             geometry_cache: Default::default(),
             forward_renderer: ForwardRenderer::new(),
             ui_frame_buffers: Default::default(),
+            frame_capture_requested: false,
+            captured_frame: None,
+            strict_error_checking: false,
+            strict_timing: false,
+            reverse_z: false,
+            capabilities,
+            minimized: frame_size.0 == 0 || frame_size.1 == 0,
+            monitor_refresh_rate: None,
+            frustum_culling: true,
+            object_picking: false,
+            debug_view: GBufferDebugView::Final,
+            color_grading: Default::default(),
+            color_lut: None,
+            color_lut_intensity: 1.0,
+            auto_exposure: Some(Default::default()),
+            particle_transparency: Default::default(),
+            particle_budget: None,
+            render_flags: Default::default(),
+            paused: false,
+            step_requested: false,
+            gpu_timers: Default::default(),
+            oit_renderer: OitRenderer::new(&state)?,
+            pending_texture_uploads: Default::default(),
+            upload_budget: usize::MAX,
+            soft_particle_scale: 1.0,
             fxaa_renderer: FxaaRenderer::new(&state)?,
             statistics: Statistics::default(),
             shader_event_receiver,
             texture_event_receiver,
             shader_cache,
             scene_render_passes: Default::default(),
+            post_render_hook: None,
+            post_render_drawing_context: Default::default(),
             matrix_storage: MatrixStorageCache::new(&state)?,
             state,
         })
     }
 
+    /// Requests a capture of the next rendered frame. The back buffer will be read right after
+    /// the frame is fully rendered (including the UI) and before the buffers are swapped. Use
+    /// [`Self::take_captured_frame`] to fetch the result. Reading the back buffer stalls the GPU
+    /// pipeline, so this method should not be used every frame.
+    pub fn request_frame_capture(&mut self) {
+        self.frame_capture_requested = true;
+    }
+
+    /// Takes the last captured frame (if any). See [`Self::request_frame_capture`] for more info.
+    pub fn take_captured_frame(&mut self) -> Option<CapturedFrame> {
+        self.captured_frame.take()
+    }
+
+    /// Sets a texture that will be used instead of a missing diffuse texture when rendering scene
+    /// geometry. Could be useful to make missing textures more noticeable. Returns previous fallback
+    /// texture. Default fallback texture is a one pixel white texture, see
+    /// [`Self::default_fallback_diffuse`]. Other samplers with white fallback are not affected.
+    pub fn set_fallback_diffuse(&mut self, texture: GpuTexture) -> Rc<RefCell<GpuTexture>> {
+        std::mem::replace(&mut self.fallback_diffuse, Rc::new(RefCell::new(texture)))
+    }
+
+    /// Returns current fallback diffuse texture.
+    pub fn fallback_diffuse(&self) -> Rc<RefCell<GpuTexture>> {
+        self.fallback_diffuse.clone()
+    }
+
+    /// Sets a texture that will be used instead of a missing normal map when rendering scene
+    /// geometry. Returns previous fallback texture. Default fallback texture is a flat normal
+    /// map, see [`Self::default_fallback_normal`].
+    pub fn set_fallback_normal(&mut self, texture: GpuTexture) -> Rc<RefCell<GpuTexture>> {
+        std::mem::replace(&mut self.fallback_normal, Rc::new(RefCell::new(texture)))
+    }
+
+    /// Returns current fallback normal map.
+    pub fn fallback_normal(&self) -> Rc<RefCell<GpuTexture>> {
+        self.fallback_normal.clone()
+    }
+
+    /// Returns default fallback diffuse texture (one pixel white texture).
+    pub fn default_fallback_diffuse(&self) -> Rc<RefCell<GpuTexture>> {
+        self.white_dummy.clone()
+    }
+
+    /// Returns default fallback normal map (one pixel texture with (0, 0, 1) normal).
+    pub fn default_fallback_normal(&self) -> Rc<RefCell<GpuTexture>> {
+        self.normal_dummy.clone()
+    }
+
+    /// Restores default fallback diffuse and normal textures.
+    pub fn reset_fallback_textures(&mut self) {
+        self.fallback_diffuse = self.white_dummy.clone();
+        self.fallback_normal = self.normal_dummy.clone();
+    }
+
+    /// Sets renderer-wide anisotropy level for textures. Anisotropy level of every texture is limited
+    /// by this value, so it could be used to reduce texture sampling cost on low-end GPUs. The value
+    /// is also clamped to the maximum supported by current GPU. Default value is 16.0.
+    pub fn set_texture_anisotropy(&mut self, level: f32) {
+        self.texture_cache.set_max_anisotropy(level);
+    }
+
+    /// Returns current renderer-wide anisotropy level for textures.
+    pub fn texture_anisotropy(&self) -> f32 {
+        self.texture_cache.max_anisotropy()
+    }
+
+    /// Enables or disables strict error checking. When enabled, the renderer checks for OpenGL errors
+    /// at the end of each frame and returns [`FrameworkError::GlError`] if there were any. It is disabled
+    /// by default, because fetching errors forces the driver to synchronize with the GPU.
+    pub fn set_strict_error_checking(&mut self, enabled: bool) {
+        self.strict_error_checking = enabled;
+    }
+
+    /// Returns `true` if strict error checking is enabled, `false` - otherwise.
+    pub fn is_strict_error_checking_enabled(&self) -> bool {
+        self.strict_error_checking
+    }
+
+    /// Blocks until GPU has finished all previously issued commands. It is very slow, use it only
+    /// for debugging or benchmarking purposes.
+    pub fn finish(&self) {
+        self.state.finish();
+    }
+
+    /// Enables or disables strict timing. When enabled, the renderer waits for GPU to finish the frame
+    /// (see [`Self::finish`]) before measuring [`Statistics::pure_frame_time`], so it reflects the
+    /// actual time of rendering instead of the time of command submission. It is disabled by default,
+    /// because it kills the throughput.
+    pub fn set_strict_timing(&mut self, enabled: bool) {
+        self.strict_timing = enabled;
+    }
+
+    /// Returns `true` if strict timing is enabled, `false` - otherwise.
+    pub fn is_strict_timing_enabled(&self) -> bool {
+        self.strict_timing
+    }
+
+    /// Enables or disables reverse-Z. When enabled, the near plane of cameras is mapped to 1.0 and
+    /// the far plane to 0.0 in the depth buffer, and the depth test passes fragments with greater
+    /// depth. Keep in mind, that it does not improve depth precision on its own: the renderer uses
+    /// fixed-point depth buffers and the default `[-1; 1]` clip-space depth range of OpenGL, while
+    /// reverse-Z pays off only with floating-point depth buffers and `[0; 1]` depth range, which
+    /// are not supported yet. Shadow maps are not affected. Custom render passes, that use the depth buffer
+    /// (see [`SceneRenderPass`]), must take the reversed range into account. Disabled by default.
+    pub fn set_reverse_z(&mut self, enabled: bool) {
+        self.reverse_z = enabled;
+    }
+
+    /// Returns `true` if reverse-Z is enabled, `false` - otherwise.
+    pub fn is_reverse_z_enabled(&self) -> bool {
+        self.reverse_z
+    }
+
+    /// Returns time (in seconds) between the starts of two last consecutive frames. Unlike frame time
+    /// statistics, it includes the time that was spent outside of the renderer, so it could be used
+    /// to drive fixed-step updates with interpolation.
+    pub fn last_frame_delta(&self) -> f32 {
+        self.statistics.frame_delta
+    }
+
+    /// Enables or disables frustum culling of scene nodes. Frustum culling is enabled by default, disabling
+    /// it could be useful for debugging purposes. See [`CullingStatistics`] to check how many nodes were
+    /// culled.
+    pub fn set_frustum_culling(&mut self, enabled: bool) {
+        self.frustum_culling = enabled;
+    }
+
+    /// Returns `true` if frustum culling is enabled, `false` - otherwise.
+    pub fn is_frustum_culling_enabled(&self) -> bool {
+        self.frustum_culling
+    }
+
+    /// Enables or disables object picking. When enabled, the renderer draws an additional pass for
+    /// every scene, that writes an id of each scene node into a separate frame buffer. The ids can
+    /// then be read back by [`Self::pick`]. The pass is disabled by default, because it adds one more
+    /// draw call per every surface instance.
+    pub fn set_object_picking(&mut self, enabled: bool) {
+        self.object_picking = enabled;
+    }
+
+    /// Returns `true` if object picking is enabled, `false` - otherwise.
+    pub fn is_object_picking_enabled(&self) -> bool {
+        self.object_picking
+    }
+
+    /// Returns a handle of a scene node, that was rendered at the given pixel of the last frame of
+    /// the scene. Coordinates are in pixels with the origin at the top left corner of the frame.
+    /// Returns [`None`] if there's nothing at the pixel or object picking is disabled (see
+    /// [`Self::set_object_picking`]).
+    ///
+    /// # Performance
+    ///
+    /// The method reads back a pixel from GPU memory, which stalls the pipeline until the frame is
+    /// rendered. It is fine to call it once per frame (for example, on mouse click), but do not call
+    /// it for a lot of pixels.
+    pub fn pick(&self, scene_handle: Handle<Scene>, x: u32, y: u32) -> Option<Handle<Node>> {
+        self.scene_data_map
+            .get(&scene_handle)?
+            .object_id_renderer
+            .as_ref()?
+            .pick(&self.state, x, y)
+    }
+
+    /// Reads back a depth value at the given pixel of the last frame of the scene and converts it
+    /// into a distance from the camera along its view direction. Coordinates are in pixels with the
+    /// origin at the top left corner of the frame. Returns [`None`] if there's no geometry at the
+    /// pixel. `camera` must be the camera that was used to render the scene, otherwise the distance
+    /// will be wrong.
+    ///
+    /// Raw depth buffer itself is available as a texture via [`GBuffer::depth`] and can be sampled
+    /// in custom render passes.
+    ///
+    /// # Performance
+    ///
+    /// The same as for [`Self::pick`] - it stalls the pipeline, so use it sparingly.
+    pub fn read_depth(
+        &self,
+        scene_handle: Handle<Scene>,
+        camera: &Camera,
+        x: u32,
+        y: u32,
+    ) -> Option<f32> {
+        let gbuffer = &self.scene_data_map.get(&scene_handle)?.gbuffer;
+
+        let (x, y) = (x as i32, y as i32);
+        if x >= gbuffer.width || y >= gbuffer.height {
+            return None;
+        }
+
+        let depth = gbuffer
+            .framebuffer()
+            .read_depth(&self.state, x, gbuffer.height - 1 - y);
+        if depth == far_plane_depth(self.reverse_z) {
+            return None;
+        }
+
+        let inv_projection = camera_projection_matrix(camera, self.reverse_z).try_inverse()?;
+        let view_position = inv_projection * Vector4::new(0.0, 0.0, depth * 2.0 - 1.0, 1.0);
+        Some((view_position.z / view_position.w).abs())
+    }
+
+    /// Sets which image will be shown as the result of scene rendering. Any mode other than
+    /// [`GBufferDebugView::Final`] replaces the contents of the frame with a raw channel of the
+    /// GBuffer, it is meant to be used for debugging only. Debug geometry and custom LDR render
+    /// passes are not visible in debug views.
+    pub fn set_debug_view(&mut self, mode: GBufferDebugView) {
+        self.debug_view = mode;
+    }
+
+    /// Returns current debug view mode.
+    pub fn debug_view(&self) -> GBufferDebugView {
+        self.debug_view
+    }
+
+    /// Sets color grading parameters, that will be applied to the final frame of every scene right
+    /// after tone mapping (and before color grading look-up table of a camera, if any). It is
+    /// cheap and could be changed every frame, for example to fade the screen out.
+    pub fn set_color_grading(&mut self, color_grading: ColorGrading) {
+        self.color_grading = color_grading;
+    }
+
+    /// Returns current color grading parameters.
+    pub fn color_grading(&self) -> ColorGrading {
+        self.color_grading
+    }
+
+    /// Sets a color grading look-up table, that will be applied to the final frame of every scene
+    /// as the last grading step. The texture must be a 16x16x16 volume texture; 2D strips could be
+    /// converted to such format using [`crate::scene::camera::ColorGradingLut`]. Cameras with their
+    /// own color grading look-up table (see [`Camera::set_color_grading_lut`]) use their table
+    /// instead of this one. Returns previous look-up table.
+    pub fn set_color_lut(&mut self, lut: Option<GpuTexture>) -> Option<Rc<RefCell<GpuTexture>>> {
+        std::mem::replace(
+            &mut self.color_lut,
+            lut.map(|lut| Rc::new(RefCell::new(lut))),
+        )
+    }
+
+    /// Returns current color grading look-up table (if any).
+    pub fn color_lut(&self) -> Option<Rc<RefCell<GpuTexture>>> {
+        self.color_lut.clone()
+    }
+
+    /// Sets a blend factor between the original color (0.0) and the color from a color grading
+    /// look-up table (1.0). It is applied to look-up tables of cameras as well. Default value is 1.0.
+    pub fn set_color_lut_intensity(&mut self, intensity: f32) {
+        self.color_lut_intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Returns current blend factor of color grading look-up tables.
+    pub fn color_lut_intensity(&self) -> f32 {
+        self.color_lut_intensity
+    }
+
+    /// Sets eye adaptation settings for cameras with automatic exposure. The average luminance of a
+    /// frame smoothly follows the luminance of the scene, simulating adaptation of an eye to bright
+    /// or dark environments. `None` disables adaptation, so the exposure instantly matches the
+    /// luminance of the current frame and is not clamped. Cameras with manual exposure are not
+    /// affected.
+    pub fn set_auto_exposure(&mut self, settings: Option<AutoExposureSettings>) {
+        self.auto_exposure = settings;
+    }
+
+    /// Returns current eye adaptation settings.
+    pub fn auto_exposure(&self) -> Option<AutoExposureSettings> {
+        self.auto_exposure
+    }
+
+    /// Sets a method of rendering of alpha-blended particles. [`TransparencyMode::WeightedBlended`]
+    /// removes the need of sorting of particles, which could be a significant win for scenes with
+    /// lots of particles. Additively blended particles are not affected, they do not need sorting
+    /// anyway. Default is [`TransparencyMode::Sorted`].
+    pub fn set_particle_transparency(&mut self, mode: TransparencyMode) {
+        self.particle_transparency = mode;
+    }
+
+    /// Returns current method of rendering of alpha-blended particles.
+    pub fn particle_transparency(&self) -> TransparencyMode {
+        self.particle_transparency
+    }
+
+    /// Sets maximum total amount of particles, that could be rendered per frame. The budget is
+    /// shared among all cameras of all scenes in their rendering order, visible particle systems
+    /// closer to a camera are served first, and particles closest to the camera are kept within a
+    /// particle system. Scenes rendered by [`Self::render_scene`] or
+    /// [`Self::render_scene_to_texture`] get a budget of their own. The amount of particles, that
+    /// were not rendered, could be fetched from [`CullingStatistics::culled_particles`]. By
+    /// default, there is no budget.
+    pub fn set_particle_budget(&mut self, max: usize) {
+        self.particle_budget = Some(max);
+    }
+
+    /// Removes the particle budget, every alive particle will be rendered.
+    pub fn remove_particle_budget(&mut self) {
+        self.particle_budget = None;
+    }
+
+    /// Returns current particle budget, if any.
+    pub fn particle_budget(&self) -> Option<usize> {
+        self.particle_budget
+    }
+
+    /// Sets which parts of a frame will be rendered, everything is rendered by default. Disabled
+    /// parts are skipped without removing anything from scenes, which could be useful to find out
+    /// which part causes a visual glitch or a performance drop.
+    pub fn set_render_flags(&mut self, flags: RenderFlags) {
+        self.render_flags = flags;
+    }
+
+    /// Returns current set of parts of a frame, that will be rendered.
+    pub fn render_flags(&self) -> RenderFlags {
+        self.render_flags
+    }
+
+    /// Pauses or resumes the renderer. While paused, the renderer does not re-render scenes and
+    /// presents the last rendered frame of each scene instead, the UI is still rendered as usual.
+    /// Use [`Self::step`] to render exactly one frame while paused. This could be useful to inspect
+    /// animations or effects frame by frame. Keep in mind, that the renderer does not update scenes,
+    /// so it is up to the caller to stop updating them as well.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.step_requested = false;
+    }
+
+    /// Returns `true` if the renderer is paused, `false` - otherwise.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Forces the renderer to render scenes on the next frame while paused. Does nothing if the
+    /// renderer is not paused.
+    pub fn step(&mut self) {
+        if self.paused {
+            self.step_requested = true;
+        }
+    }
+
+    /// Enables or disables GPU timers, that measure the time spent by GPU on every group of render
+    /// passes (see [`GpuPass`]). The results are available in [`Statistics::pass_times`]. Timers
+    /// add a bit of overhead, so they are disabled by default. Returns `false` if the timers are
+    /// not supported by the current graphics context (OpenGL ES and WebGL need a special
+    /// extension).
+    pub fn set_gpu_timers_enabled(&mut self, enabled: bool) -> bool {
+        self.gpu_timers.set_enabled(&self.state, enabled)
+    }
+
+    /// Returns `true` if GPU timers are enabled, `false` - otherwise.
+    pub fn is_gpu_timers_enabled(&self) -> bool {
+        self.gpu_timers.is_enabled()
+    }
+
+    /// Sets a global multiplier for the sharpness of soft particles boundaries. It is multiplied with
+    /// per-material sharpness factor, so it could be used to tune how aggressively particles fade
+    /// against scene geometry across all effects at once. Default value is 1.0.
+    pub fn set_soft_particle_scale(&mut self, scale: f32) {
+        self.soft_particle_scale = scale.max(0.0);
+    }
+
+    /// Returns current global multiplier for the sharpness of soft particles boundaries.
+    pub fn soft_particle_scale(&self) -> f32 {
+        self.soft_particle_scale
+    }
+
     /// Adds a custom render pass.
     pub fn add_render_pass(&mut self, pass: Rc<RefCell<dyn SceneRenderPass>>) {
         self.scene_render_passes.push(pass);
@@ -1310,6 +2511,46 @@ impl Renderer {
         self.scene_render_passes.clear()
     }
 
+    /// Draws a line with the given color in every scene, that will be rendered on screen in the current
+    /// frame. Scenes with a render target, as well as scenes rendered using [`Self::render_scene`] or
+    /// [`Self::render_scene_to_texture`], do not show such lines. Unlike [`Scene::drawing_context`],
+    /// the line is removed automatically at the end of the frame, so it must be drawn every frame to
+    /// stay visible. Lines are transformed by view-projection matrix of every camera that renders a
+    /// scene.
+    pub fn draw_line(&mut self, from: Vector3<f32>, to: Vector3<f32>, color: Color) {
+        self.debug_drawing_context.add_line(Line {
+            begin: from,
+            end: to,
+            color,
+        });
+    }
+
+    /// Draws a wireframe axis-aligned box with the given color. See [`Self::draw_line`] for more
+    /// info.
+    pub fn draw_box(&mut self, aabb: &AxisAlignedBoundingBox, color: Color) {
+        self.debug_drawing_context.draw_aabb(aabb, color);
+    }
+
+    /// Returns a reference to the drawing context, that contains every debug line of the current
+    /// frame. It could be used to draw more complex shapes (spheres, frustums, etc.). See
+    /// [`Self::draw_line`] for more info.
+    pub fn debug_drawing_context_mut(&mut self) -> &mut SceneDrawingContext {
+        &mut self.debug_drawing_context
+    }
+
+    /// Sets a callback, that will be called once per frame after all the scenes were rendered. The
+    /// callback could draw anything (debug lines, text, etc.) in the given drawing context using
+    /// screen-space coordinates; the drawings will be placed on top of the scenes and under or over
+    /// the user interface, depending on the `layer`. Replaces previous hook, if any.
+    pub fn set_post_render_hook(&mut self, layer: PostRenderHookLayer, hook: PostRenderHook) {
+        self.post_render_hook = Some((layer, hook));
+    }
+
+    /// Removes current post-render hook and returns it, if any.
+    pub fn remove_post_render_hook(&mut self) -> Option<PostRenderHook> {
+        self.post_render_hook.take().map(|(_, hook)| hook)
+    }
+
     /// Returns statistics for last frame.
     pub fn get_statistics(&self) -> Statistics {
         self.statistics
@@ -1320,27 +2561,51 @@ impl Renderer {
         self.texture_cache.unload(texture)
     }
 
-    /// Sets color which will be used to fill screen when there is nothing to render.
+    /// Sets color which will be used to fill screen when there is nothing to render. It is also
+    /// used as a background of every scene without its own clear color (see
+    /// [`crate::scene::SceneRenderingOptions::clear_color`]) and without a skybox. Keep in mind,
+    /// that the background of a scene is a part of its high dynamic range frame, so it is affected
+    /// by exposure and tone mapping of a camera.
     pub fn set_backbuffer_clear_color(&mut self, color: Color) {
         self.backbuffer_clear_color = color;
     }
 
+    /// Returns current clear color of the back buffer.
+    pub fn backbuffer_clear_color(&self) -> Color {
+        self.backbuffer_clear_color
+    }
+
     /// Returns a reference to current pipeline state.
     pub fn pipeline_state(&self) -> &PipelineState {
         &self.state
     }
 
+    /// Returns a reference to current render backend. See [`RenderBackend`] docs for more info.
+    /// The actual type of the backend is hidden, so the code that uses this method does not depend
+    /// on OpenGL, use [`Self::pipeline_state`] if you need OpenGL-specific functionality.
+    pub fn backend(&self) -> &impl RenderBackend {
+        &self.state
+    }
+
     /// Sets new frame size. You should call the same method on [`crate::engine::Engine`]
     /// instead, which will update the size for the user interface and rendering context
     /// as well as this one.
     ///
     /// # Notes
     ///
-    /// Input values will be set to 1 pixel if new size is 0. Rendering cannot
-    /// be performed into 0x0 texture.
+    /// Zero width or height means that the window is minimized. In this case the previous frame
+    /// size is kept and rendering is suspended until a non-zero size is set.
     pub(crate) fn set_frame_size(&mut self, new_size: (u32, u32)) -> Result<(), FrameworkError> {
-        self.frame_size.0 = new_size.0.max(1);
-        self.frame_size.1 = new_size.1.max(1);
+        // Minimized windows on some platforms report zero size, there's no need to re-allocate
+        // frame buffers in this case - rendering is suspended until a non-zero size arrives.
+        if new_size.0 == 0 || new_size.1 == 0 {
+            self.minimized = true;
+            return Ok(());
+        }
+        self.minimized = false;
+
+        self.frame_size.0 = new_size.0;
+        self.frame_size.1 = new_size.1;
 
         self.deferred_light_renderer
             .set_frame_size(&self.state, new_size)?;
@@ -1348,6 +2613,24 @@ impl Renderer {
         Ok(())
     }
 
+    /// Returns capabilities of the graphics device. They could be used to clamp sizes of frame
+    /// buffers and textures before allocating them.
+    pub fn capabilities(&self) -> &RendererCapabilities {
+        &self.capabilities
+    }
+
+    pub(crate) fn set_monitor_refresh_rate(&mut self, refresh_rate: Option<f32>) {
+        self.monitor_refresh_rate = refresh_rate;
+    }
+
+    /// Returns refresh rate (in Hz) of the monitor, that shows the window. It is updated every time
+    /// the window is resized, and it is `None` if the platform does not provide it. When vertical
+    /// synchronization is enabled, [`Statistics::capped_frame_time`] is expected to be close to
+    /// `1.0 / refresh_rate` (or its multiple, if the frame has missed vertical blank).
+    pub fn monitor_refresh_rate(&self) -> Option<f32> {
+        self.monitor_refresh_rate
+    }
+
     /// Returns current (width, height) pair of back buffer size.
     pub fn get_frame_size(&self) -> (u32, u32) {
         self.frame_size
@@ -1361,13 +2644,19 @@ impl Renderer {
     /// Sets new quality settings for renderer. Never call this method in a loop, otherwise
     /// you may get **significant** lags. Always check if current quality setting differs
     /// from new!
+    ///
+    /// Shadow map sizes are rounded up to the nearest power of two and clamped to the `[16; max]`
+    /// range, where `max` is the maximum texture size supported by current GPU. Amount of shadow
+    /// cascades is clamped to `[1; CSM_NUM_CASCADES]` range. Use [`Self::get_quality_settings`] to
+    /// fetch actual values.
     pub fn set_quality_settings(
         &mut self,
         settings: &QualitySettings,
     ) -> Result<(), FrameworkError> {
-        self.quality_settings = *settings;
+        let settings = sanitize_quality_settings(*settings, &self.capabilities);
+        self.quality_settings = settings;
         self.deferred_light_renderer
-            .set_quality_settings(&self.state, settings)
+            .set_quality_settings(&self.state, &settings)
     }
 
     /// Returns current quality settings.
@@ -1375,6 +2664,28 @@ impl Renderer {
         self.quality_settings
     }
 
+    /// Sets new size of shadow maps of every light source (size of a cascade for directional lights
+    /// and size of a cube map face for point lights). The size is rounded up to the nearest power of
+    /// two and clamped to the `[16; max]` range, where `max` is the maximum texture size supported by
+    /// current GPU. Shadow maps are re-created only if the size has changed.
+    pub fn set_shadow_map_size(&mut self, size: usize) -> Result<(), FrameworkError> {
+        let mut settings = self.quality_settings;
+        settings.spot_shadow_map_size = size;
+        settings.point_shadow_map_size = size;
+        settings.csm_settings.size = size;
+        self.set_quality_settings(&settings)
+    }
+
+    /// Sets new amount of shadow cascades for directional lights. The amount is clamped to the
+    /// `[1; CSM_NUM_CASCADES]` range. When there's less cascades than [`CSM_NUM_CASCADES`], the
+    /// last cascade covers the rest of the camera frustum. Shadow maps are re-created only if the
+    /// amount has changed.
+    pub fn set_shadow_cascade_count(&mut self, count: usize) -> Result<(), FrameworkError> {
+        let mut settings = self.quality_settings;
+        settings.csm_settings.cascade_count = count;
+        self.set_quality_settings(&settings)
+    }
+
     /// Removes all cached GPU data, forces renderer to re-upload data to GPU.
     /// Do not call this method until you absolutely need! It may cause **significant**
     /// performance lag!
@@ -1393,8 +2704,33 @@ impl Renderer {
         clear_color: Color,
         pixel_kind: PixelKind,
     ) -> Result<(), FrameworkError> {
-        let new_width = screen_size.x as usize;
-        let new_height = screen_size.y as usize;
+        self.render_ui_to_texture_with_resolution(
+            render_target,
+            screen_size,
+            Vector2::new(screen_size.x as u32, screen_size.y as u32),
+            drawing_context,
+            clear_color,
+            pixel_kind,
+        )
+    }
+
+    /// Does the same as [`Self::render_ui_to_texture`], but allows you to set the resolution (in
+    /// pixels) of the render target separately from the size of the UI (in its own units). The UI
+    /// will be stretched to fill the entire render target. This is useful for UIs in the world space
+    /// (for example, a control panel on a mesh), where the size of the UI defines the layout and the
+    /// resolution defines how crisp the UI looks.
+    pub fn render_ui_to_texture_with_resolution(
+        &mut self,
+        render_target: TextureResource,
+        screen_size: Vector2<f32>,
+        resolution: Vector2<u32>,
+        drawing_context: &DrawingContext,
+        clear_color: Color,
+        pixel_kind: PixelKind,
+    ) -> Result<(), FrameworkError> {
+        let new_width = resolution.x as usize;
+        let new_height = resolution.y as usize;
+        let frame_size = Vector2::new(resolution.x as f32, resolution.y as f32);
 
         // Create or reuse existing frame buffer.
         let frame_buffer = match self.ui_frame_buffers.entry(render_target.key()) {
@@ -1407,7 +2743,7 @@ impl Renderer {
                         || height != new_height
                         || frame.texture.borrow().pixel_kind() != pixel_kind
                     {
-                        *frame_buffer = make_ui_frame_buffer(screen_size, &self.state, pixel_kind)?;
+                        *frame_buffer = make_ui_frame_buffer(frame_size, &self.state, pixel_kind)?;
                     }
                 } else {
                     panic!("ui can be rendered only in rectangle texture!")
@@ -1415,7 +2751,7 @@ impl Renderer {
                 frame_buffer
             }
             Entry::Vacant(entry) => {
-                entry.insert(make_ui_frame_buffer(screen_size, &self.state, pixel_kind)?)
+                entry.insert(make_ui_frame_buffer(frame_size, &self.state, pixel_kind)?)
             }
         };
 
@@ -1453,51 +2789,123 @@ impl Renderer {
         Ok(())
     }
 
-    fn update_texture_cache(&mut self, dt: f32) {
-        // Maximum amount of textures uploaded to GPU per frame. This defines throughput **only** for
-        // requests from resource manager. This is needed to prevent huge lag when there are tons of
-        // requests, so this is some kind of work load balancer.
-        const THROUGHPUT: usize = 5;
+    /// Frees GPU textures, which source texture resources were dropped. Normally, unused GPU
+    /// textures are freed automatically after some time (see [`Self::update_caches`]), but this
+    /// method allows you to release them right away, which is useful for streaming worlds. Returns
+    /// estimated amount of freed GPU memory in bytes, the total amount is also accumulated in
+    /// [`Statistics::evicted_texture_memory`].
+    pub fn evict_unused_textures(&mut self) -> usize {
+        let freed_bytes = self.texture_cache.evict_unused();
+        self.statistics.evicted_texture_memory += freed_bytes;
+        freed_bytes
+    }
 
-        let mut uploaded = 0;
+    /// Sets maximum amount of bytes of texture data, that will be uploaded to GPU per frame for
+    /// textures loaded by resource manager. Textures, that did not fit into the budget, will be
+    /// uploaded in the next frames. At least one texture is uploaded per frame, even if it is larger
+    /// than the budget. The budget does not affect textures, that are used for rendering right away,
+    /// they're uploaded on demand. Default value is [`usize::MAX`] (no limit).
+    pub fn set_upload_budget(&mut self, bytes: usize) {
+        self.upload_budget = bytes;
+    }
+
+    /// Returns current per-frame upload budget in bytes. See [`Self::set_upload_budget`] for more
+    /// info.
+    pub fn upload_budget(&self) -> usize {
+        self.upload_budget
+    }
+
+    /// Uploads textures loaded by resource manager to GPU, until either `max_textures` textures or
+    /// `max_bytes` bytes are uploaded. Returns the amount of textures, that are still waiting for
+    /// upload. This method is called automatically in [`Self::update_caches`] with the budget set by
+    /// [`Self::set_upload_budget`], but it could be used to spread uploads across frames manually,
+    /// for example to upload everything on a loading screen.
+    pub fn upload_pending_textures(&mut self, max_textures: usize, max_bytes: usize) -> usize {
         while let Ok(event) = self.texture_event_receiver.try_recv() {
             if let ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded(resource) = event {
                 if let Some(texture) = resource.try_cast::<Texture>() {
-                    match self.texture_cache.upload(&self.state, &texture) {
-                        Ok(_) => {
-                            uploaded += 1;
-                            if uploaded >= THROUGHPUT {
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            Log::writeln(
-                                MessageKind::Error,
-                                format!("Failed to upload texture to GPU. Reason: {:?}", e),
-                            );
-                        }
-                    }
+                    self.pending_texture_uploads.push_back(texture);
                 }
             }
         }
 
+        let mut uploaded = 0;
+        let mut uploaded_bytes = 0;
+        while uploaded < max_textures {
+            let Some(texture) = self.pending_texture_uploads.front() else {
+                break;
+            };
+
+            let size = texture.state().data().map_or(0, |data| data.data().len());
+            if uploaded > 0 && uploaded_bytes + size > max_bytes {
+                break;
+            }
+
+            if let Err(e) = self.texture_cache.upload(&self.state, texture) {
+                Log::writeln(
+                    MessageKind::Error,
+                    format!("Failed to upload texture to GPU. Reason: {:?}", e),
+                );
+            }
+
+            self.pending_texture_uploads.pop_front();
+            uploaded += 1;
+            uploaded_bytes += size;
+        }
+
+        self.pending_texture_uploads.len()
+    }
+
+    fn update_texture_cache(&mut self, dt: f32) {
+        // Maximum amount of textures uploaded to GPU per frame. This defines throughput **only** for
+        // requests from resource manager. This is needed to prevent huge lag when there are tons of
+        // requests, so this is some kind of work load balancer.
+        const THROUGHPUT: usize = 5;
+
+        self.upload_pending_textures(THROUGHPUT, self.upload_budget);
+
         self.texture_cache.update(dt);
     }
 
     fn update_shader_cache(&mut self, dt: f32) {
         while let Ok(event) = self.shader_event_receiver.try_recv() {
-            if let ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded(resource) = event {
-                if let Some(shader) = resource.try_cast::<Shader>() {
-                    // Remove and immediately "touch" the shader cache to force upload shader.
-                    self.shader_cache.remove(&shader);
-                    let _ = self.shader_cache.get(&self.state, &shader);
+            match event {
+                ResourceEvent::Loaded(resource) => {
+                    if let Some(shader) = resource.try_cast::<Shader>() {
+                        // Remove and immediately "touch" the shader cache to force upload shader.
+                        self.shader_cache.remove(&shader);
+                        let _ = self.shader_cache.get(&self.state, &shader);
+                    }
+                }
+                ResourceEvent::Reloaded(resource) => {
+                    if let Some(shader) = resource.try_cast::<Shader>() {
+                        // Keep the previous version of the shader if the new one is broken.
+                        if let Err(error) = self.shader_cache.reload(&self.state, &shader) {
+                            Log::err(format!(
+                                "Unable to reload shader. The previous version will be used. \
+                                Reason: {}",
+                                error
+                            ));
+                        }
+                    }
                 }
+                _ => (),
             }
         }
 
         self.shader_cache.update(dt)
     }
 
+    /// Recompiles GPU programs of every shader resource, that is currently in use. Shader files are
+    /// watched by the resource manager and reloaded automatically (if file system watcher is
+    /// enabled), this method could be used to force the recompilation, for example after changes
+    /// in the shader definitions made from code. The previous programs of a shader are kept if its
+    /// compilation fails. Built-in shaders of the renderer are embedded in the engine and
+    /// could not be reloaded.
+    pub fn reload_shaders(&mut self) -> Result<(), FrameworkError> {
+        self.shader_cache.reload_all(&self.state)
+    }
+
     /// Update caches - this will remove timed out resources.
     ///
     /// Normally, this is called from `Engine::update()`.
@@ -1506,6 +2914,12 @@ impl Renderer {
         self.update_texture_cache(dt);
         self.update_shader_cache(dt);
         self.geometry_cache.update(dt);
+        self.deferred_light_renderer.update_caches(dt);
+
+        self.offscreen_scene_data_map.retain(|_, entry| {
+            *entry.time_to_live -= dt;
+            *entry.time_to_live > 0.0
+        });
     }
 
     /// Unconditionally renders a scene and returns a reference to a [`AssociatedSceneData`] instance
@@ -1515,6 +2929,58 @@ impl Renderer {
         scene_handle: Handle<Scene>,
         scene: &Scene,
         dt: f32,
+    ) -> Result<&AssociatedSceneData, FrameworkError> {
+        let render_target = scene.rendering_options.render_target.clone();
+        let mut particle_budget = self.particle_budget;
+        self.render_scene_internal(
+            scene_handle,
+            scene,
+            None,
+            render_target,
+            false,
+            particle_budget.as_mut(),
+            dt,
+        )
+    }
+
+    /// Renders a scene from the given camera into the given texture, ignoring the render target of
+    /// the scene. This could be useful to render reflection probes, portals, etc. The camera is used
+    /// even if it is disabled, so it is possible to have cameras that are rendered only by this method.
+    /// The texture must be a rectangle texture, its size defines the size of rendered frame. Rendered
+    /// frame will be available for sampling (on any mesh, sprite, etc.) via the render target.
+    ///
+    /// Intermediate data (G-Buffer, etc.) for each pair of scene and render target is stored separately
+    /// from the data used by [`Self::render_scene`], so rendering into textures of different sizes
+    /// does not cause re-creation of the scene frame buffers.
+    pub fn render_scene_to_texture(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        scene: &Scene,
+        camera: Handle<Node>,
+        render_target: TextureResource,
+        dt: f32,
+    ) -> Result<&AssociatedSceneData, FrameworkError> {
+        let mut particle_budget = self.particle_budget;
+        self.render_scene_internal(
+            scene_handle,
+            scene,
+            Some(camera),
+            Some(render_target),
+            false,
+            particle_budget.as_mut(),
+            dt,
+        )
+    }
+
+    fn render_scene_internal(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        scene: &Scene,
+        camera: Option<Handle<Node>>,
+        render_target: Option<TextureResource>,
+        draw_debug_lines: bool,
+        mut particle_budget: Option<&mut usize>,
+        dt: f32,
     ) -> Result<&AssociatedSceneData, FrameworkError> {
         let graph = &scene.graph;
 
@@ -1523,67 +2989,50 @@ impl Renderer {
 
         let window_viewport = Rect::new(0, 0, self.frame_size.0 as i32, self.frame_size.1 as i32);
 
-        let frame_size = scene
-            .rendering_options
-            .render_target
-            .as_ref()
-            .map_or_else(
-                // Use either backbuffer size
-                || Vector2::new(backbuffer_width, backbuffer_height),
-                // Or framebuffer size
-                |rt| {
-                    if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
-                        Vector2::new(width as f32, height as f32)
-                    } else {
-                        panic!("only rectangle textures can be used as render target!")
-                    }
-                },
-            )
-            // Clamp to [1.0; infinity] range.
-            .sup(&Vector2::new(1.0, 1.0));
-
-        let state = &mut self.state;
-
-        let scene_associated_data = self
-            .scene_data_map
-            .entry(scene_handle)
-            .and_modify(|data| {
-                if data.gbuffer.width != frame_size.x as i32
-                    || data.gbuffer.height != frame_size.y as i32
-                {
-                    let width = frame_size.x as usize;
-                    let height = frame_size.y as usize;
-
-                    Log::info(format!(
-                        "Associated scene rendering data was re-created for scene {}, because render frame size was changed. Old is {}x{}, new {}x{}!",
-                        scene_handle,
-                        data.gbuffer.width,data.gbuffer.height,width,height
+        let frame_size = match render_target.as_ref() {
+            // Use either backbuffer size
+            None => Vector2::new(backbuffer_width, backbuffer_height),
+            // Or framebuffer size
+            Some(rt) => {
+                if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+                    Vector2::new(width as f32, height as f32)
+                } else {
+                    return Err(FrameworkError::Custom(
+                        "only rectangle textures can be used as render target!".to_string(),
                     ));
-
-                    *data = AssociatedSceneData::new(state, width, height).unwrap();
                 }
-            })
-            .or_insert_with(|| {
-                let width = frame_size.x as usize;
-                let height = frame_size.y as usize;
+            }
+        }
+        // Clamp to [1.0; infinity] range.
+        .sup(&Vector2::new(1.0, 1.0));
 
-                Log::info(format!(
-                    "A new associated scene rendering data was created for scene {}!",
-                    scene_handle
-                ));
+        let state = &mut self.state;
 
-                AssociatedSceneData::new(state, width, height).unwrap()
-            });
+        let scene_associated_data = match (camera, render_target.as_ref()) {
+            (Some(_), Some(rt)) => obtain_offscreen_scene_data(
+                &mut self.offscreen_scene_data_map,
+                scene_handle,
+                rt,
+                state,
+                frame_size,
+            ),
+            _ => obtain_scene_data(&mut self.scene_data_map, scene_handle, state, frame_size),
+        };
 
         let pipeline_stats = state.pipeline_statistics();
         scene_associated_data.statistics = Default::default();
+        scene_associated_data.statistics.particles = graph
+            .linear_iter()
+            .filter_map(|node| node.cast::<ParticleSystem>())
+            .map(|particle_system| particle_system.alive_particle_count())
+            .sum();
 
         // If we specified a texture to draw to, we have to register it in texture cache
         // so it can be used in later on as texture. This is useful in case if you need
         // to draw something on offscreen and then draw it on some mesh.
         // TODO: However it can be dangerous to use frame texture as it may be bound to
         //  pipeline.
-        if let Some(rt) = scene.rendering_options.render_target.clone() {
+        if let Some(rt) = render_target.as_ref() {
             self.texture_cache.map.spawn(
                 TextureRenderData {
                     gpu_texture: scene_associated_data.ldr_scene_frame_texture(),
@@ -1594,12 +3043,69 @@ impl Renderer {
             );
         }
 
-        for camera in graph
-            .linear_iter()
-            .filter(|&node| node.is_globally_enabled())
-            .filter_map(|node| node.cast::<Camera>().filter(|c| c.is_enabled()))
-        {
+        // Cameras, that render the scene into a specific texture, are secondary.
+        let has_main_camera = camera.is_none();
+
+        let cameras = match camera {
+            Some(camera) => graph
+                .try_get(camera)
+                .and_then(|node| node.cast::<Camera>())
+                .into_iter()
+                .collect::<Vec<_>>(),
+            None => graph
+                .linear_iter()
+                .filter(|&node| node.is_globally_enabled())
+                .filter_map(|node| node.cast::<Camera>().filter(|c| c.is_enabled()))
+                .collect::<Vec<_>>(),
+        };
+
+        if self.object_picking {
+            if scene_associated_data.object_id_renderer.is_none() {
+                scene_associated_data.object_id_renderer = Some(ObjectIdRenderer::new(
+                    state,
+                    scene_associated_data.gbuffer.width as usize,
+                    scene_associated_data.gbuffer.height as usize,
+                )?);
+            }
+        } else {
+            scene_associated_data.object_id_renderer = None;
+        }
+
+        if let Some(object_id_renderer) = scene_associated_data.object_id_renderer.as_mut() {
+            object_id_renderer.clear(state, self.reverse_z);
+        }
+
+        // Nothing will be rendered into the render target, but it still must not keep the content
+        // of previous frames.
+        if cameras.is_empty() && render_target.is_some() {
+            scene_associated_data.ldr_scene_framebuffer.clear(
+                state,
+                Rect::new(0, 0, frame_size.x as i32, frame_size.y as i32),
+                Some(
+                    scene
+                        .rendering_options
+                        .clear_color
+                        .unwrap_or(self.backbuffer_clear_color),
+                ),
+                None,
+                None,
+            );
+        }
+
+        for (camera_index, camera) in cameras.into_iter().enumerate() {
             let viewport = camera.viewport_pixels(frame_size);
+            // Everything up to tone mapping is rendered in the frame buffers of full size (with the
+            // aspect ratio of the camera viewport), then tone mapping squeezes the frame into the
+            // viewport of the camera in the LDR frame buffer. This way multiple cameras (split
+            // screen, picture-in-picture, etc.) could share the same intermediate frame buffers.
+            // Keep in mind, that every camera is rendered in full resolution, so four-way split
+            // screen costs roughly four times more than a single camera.
+            let hdr_viewport = Rect::new(
+                0,
+                0,
+                scene_associated_data.gbuffer.width,
+                scene_associated_data.gbuffer.height,
+            );
 
             let bundle_storage = RenderDataBundleStorage::from_graph(
                 graph,
@@ -1609,15 +3115,25 @@ impl Renderer {
                     z_far: camera.projection().z_far(),
                     view_matrix: camera.view_matrix(),
                     projection_matrix: camera.projection_matrix(),
+                    frustum_culling: self.frustum_culling,
+                    particle_transparency: self.particle_transparency,
+                    render_flags: self.render_flags,
+                    // The first enabled camera is the main one.
+                    lod_hysteresis: has_main_camera && camera_index == 0,
                 },
                 GBUFFER_PASS_NAME.clone(),
+                // Cameras share the same budget.
+                particle_budget.as_deref_mut(),
             );
 
+            scene_associated_data.statistics += bundle_storage.culling_statistics;
+
             state.set_polygon_fill_mode(
                 PolygonFace::FrontAndBack,
                 scene.rendering_options.polygon_rasterization_mode,
             );
 
+            self.gpu_timers.begin(state, GpuPass::GBuffer)?;
             scene_associated_data.statistics +=
                 scene_associated_data.gbuffer.fill(GBufferRenderContext {
                     state,
@@ -1628,13 +3144,28 @@ impl Renderer {
                     shader_cache: &mut self.shader_cache,
                     environment_dummy: self.environment_dummy.clone(),
                     use_parallax_mapping: self.quality_settings.use_parallax_mapping,
-                    normal_dummy: self.normal_dummy.clone(),
+                    normal_dummy: self.fallback_normal.clone(),
                     white_dummy: self.white_dummy.clone(),
+                    diffuse_dummy: self.fallback_diffuse.clone(),
                     black_dummy: self.black_dummy.clone(),
                     volume_dummy: self.volume_dummy.clone(),
                     graph,
                     matrix_storage: &mut self.matrix_storage,
+                    reverse_z: self.reverse_z,
                 })?;
+            self.gpu_timers.end(state);
+
+            if let Some(object_id_renderer) = scene_associated_data.object_id_renderer.as_mut() {
+                scene_associated_data.statistics +=
+                    object_id_renderer.render(ObjectIdRenderContext {
+                        state,
+                        camera,
+                        viewport,
+                        geom_cache: &mut self.geometry_cache,
+                        bundle_storage: &bundle_storage,
+                        reverse_z: self.reverse_z,
+                    })?;
+            }
 
             state.set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
 
@@ -1642,7 +3173,7 @@ impl Renderer {
 
             scene_associated_data.hdr_scene_framebuffer.clear(
                 state,
-                viewport,
+                hdr_viewport,
                 Some(
                     scene
                         .rendering_options
@@ -1653,7 +3184,23 @@ impl Renderer {
                 Some(0),
             );
 
-            let (pass_stats, light_stats) =
+            self.gpu_timers.begin(state, GpuPass::Lighting)?;
+            let wireframe =
+                scene.rendering_options.polygon_rasterization_mode == PolygonFillMode::Line;
+            let (pass_stats, light_stats) = if wireframe {
+                // Lighting makes no sense for wireframe view, so just show unlit edges of the geometry.
+                let mut pass_stats = RenderPassStatistics::default();
+                pass_stats += blit_wireframe(
+                    state,
+                    &mut scene_associated_data.hdr_scene_framebuffer,
+                    &scene_associated_data.gbuffer,
+                    self.reverse_z,
+                    &self.wireframe_shader,
+                    hdr_viewport,
+                    &self.quad,
+                )?;
+                (pass_stats, LightingStatistics::default())
+            } else {
                 self.deferred_light_renderer
                     .render(DeferredRendererContext {
                         state,
@@ -1671,13 +3218,18 @@ impl Renderer {
                         black_dummy: self.black_dummy.clone(),
                         volume_dummy: self.volume_dummy.clone(),
                         matrix_storage: &mut self.matrix_storage,
-                    })?;
+                        reverse_z: self.reverse_z,
+                        render_flags: self.render_flags,
+                    })?
+            };
+            self.gpu_timers.end(state);
 
             scene_associated_data.statistics += light_stats;
             scene_associated_data.statistics += pass_stats;
 
             let depth = scene_associated_data.gbuffer.depth();
 
+            self.gpu_timers.begin(state, GpuPass::Forward)?;
             scene_associated_data.statistics +=
                 self.forward_renderer.render(ForwardRenderContext {
                     state,
@@ -1688,16 +3240,26 @@ impl Renderer {
                     shader_cache: &mut self.shader_cache,
                     bundle_storage: &bundle_storage,
                     framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                    viewport,
+                    viewport: hdr_viewport,
                     quality_settings: &self.quality_settings,
                     white_dummy: self.white_dummy.clone(),
-                    normal_dummy: self.normal_dummy.clone(),
+                    diffuse_dummy: self.fallback_diffuse.clone(),
+                    normal_dummy: self.fallback_normal.clone(),
                     black_dummy: self.black_dummy.clone(),
                     volume_dummy: self.volume_dummy.clone(),
                     scene_depth: depth,
                     matrix_storage: &mut self.matrix_storage,
                     ambient_light: scene.rendering_options.ambient_lighting_color,
+                    soft_particle_scale: self.soft_particle_scale,
+                    reverse_z: self.reverse_z,
+                    oit: (self.particle_transparency == TransparencyMode::WeightedBlended).then(
+                        || OitContext {
+                            renderer: &self.oit_renderer,
+                            framebuffer: &mut scene_associated_data.oit_framebuffer,
+                        },
+                    ),
                 })?;
+            self.gpu_timers.end(state);
 
             for render_pass in self.scene_render_passes.iter() {
                 scene_associated_data.statistics +=
@@ -1710,7 +3272,7 @@ impl Renderer {
                             shader_cache: &mut self.shader_cache,
                             quality_settings: &self.quality_settings,
                             bundle_storage: &bundle_storage,
-                            viewport,
+                            viewport: hdr_viewport,
                             scene,
                             camera,
                             scene_handle,
@@ -1731,25 +3293,39 @@ impl Renderer {
 
             let quad = &self.quad;
 
+            self.gpu_timers.begin(state, GpuPass::PostProcessing)?;
+
             // Prepare glow map.
-            scene_associated_data.statistics += scene_associated_data.bloom_renderer.render(
-                state,
-                quad,
-                scene_associated_data.hdr_scene_frame_texture(),
-            )?;
+            let bloom_texture = if self.quality_settings.use_bloom {
+                scene_associated_data.statistics += scene_associated_data.bloom_renderer.render(
+                    state,
+                    quad,
+                    scene_associated_data.hdr_scene_frame_texture(),
+                    &self.quality_settings.bloom_settings,
+                )?;
+                scene_associated_data.bloom_renderer.result()
+            } else {
+                self.black_dummy.clone()
+            };
 
             // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
             scene_associated_data.statistics += scene_associated_data.hdr_renderer.render(
                 state,
                 scene_associated_data.hdr_scene_frame_texture(),
-                scene_associated_data.bloom_renderer.result(),
+                bloom_texture,
                 &mut scene_associated_data.ldr_scene_framebuffer,
                 viewport,
                 quad,
                 dt,
                 camera.exposure(),
+                camera.exposure_compensation(),
+                self.auto_exposure.as_ref(),
+                camera.tone_mapping(),
                 camera.color_grading_lut_ref(),
                 camera.color_grading_enabled(),
+                &self.color_grading,
+                self.color_lut.as_ref(),
+                self.color_lut_intensity,
                 &mut self.texture_cache,
             )?;
 
@@ -1762,26 +3338,38 @@ impl Renderer {
                     &mut scene_associated_data.ldr_temp_framebuffer,
                 )?;
 
-                let quad = &self.quad;
-                let temp_frame_texture = scene_associated_data.ldr_temp_frame_texture();
-                scene_associated_data.statistics += blit_pixels(
-                    state,
-                    &mut scene_associated_data.ldr_scene_framebuffer,
-                    temp_frame_texture,
-                    &self.flat_shader,
-                    viewport,
-                    quad,
-                )?;
+                scene_associated_data.copy_ldr_temp_to_scene_framebuffer(state, viewport);
+            }
+
+            self.gpu_timers.end(state);
+
+            // Depth in the LDR frame buffer is still the full frame depth of the camera, it must be
+            // squeezed into the viewport too, otherwise the passes below are depth tested against
+            // misaligned depth.
+            if viewport != hdr_viewport {
+                scene_associated_data.squeeze_depth_stencil_to_ldr_framebuffer(state, viewport);
             }
 
             // Render debug geometry in the LDR frame buffer.
-            scene_associated_data.statistics += self.debug_renderer.render(
-                state,
-                viewport,
-                &mut scene_associated_data.ldr_scene_framebuffer,
-                &scene.drawing_context,
-                camera,
-            )?;
+            scene_associated_data.statistics += if draw_debug_lines {
+                self.debug_renderer.render_contexts(
+                    state,
+                    viewport,
+                    &mut scene_associated_data.ldr_scene_framebuffer,
+                    &[&scene.drawing_context, &self.debug_drawing_context],
+                    camera,
+                    self.reverse_z,
+                )
+            } else {
+                self.debug_renderer.render(
+                    state,
+                    viewport,
+                    &mut scene_associated_data.ldr_scene_framebuffer,
+                    &scene.drawing_context,
+                    camera,
+                    self.reverse_z,
+                )
+            }?;
 
             for render_pass in self.scene_render_passes.iter() {
                 scene_associated_data.statistics +=
@@ -1812,10 +3400,28 @@ impl Renderer {
                             matrix_storage: &mut self.matrix_storage,
                         })?;
             }
+
+            if self.debug_view != GBufferDebugView::Final {
+                scene_associated_data.statistics += blit_gbuffer_debug_view(
+                    state,
+                    &mut scene_associated_data.ldr_scene_framebuffer,
+                    &scene_associated_data.gbuffer,
+                    self.debug_view,
+                    camera,
+                    self.reverse_z,
+                    &self.gbuffer_debug_shader,
+                    viewport,
+                    &self.quad,
+                )?;
+            }
+
+            // Depth function is changed by G-Buffer pass, restore the default one, so it won't
+            // affect anything rendered after the scene.
+            state.set_depth_func(CompareFunc::default());
         }
 
         // Optionally render everything into back buffer.
-        if scene.rendering_options.render_target.is_none() {
+        if render_target.is_none() {
             let quad = &self.quad;
             scene_associated_data.statistics += blit_pixels(
                 state,
@@ -1833,6 +3439,35 @@ impl Renderer {
         Ok(scene_associated_data)
     }
 
+    /// Blits the last rendered frame of the scene to the back buffer. The scene is rendered (without
+    /// advancing time) only if it does not have a rendered frame yet.
+    fn present_last_scene_frame(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        scene: &Scene,
+        window_viewport: Rect<i32>,
+    ) -> Result<(), FrameworkError> {
+        if scene.rendering_options.render_target.is_some() {
+            // Render target keeps its content.
+            return Ok(());
+        }
+
+        if let Some(scene_associated_data) = self.scene_data_map.get(&scene_handle) {
+            self.statistics.geometry += blit_pixels(
+                &self.state,
+                &mut self.backbuffer,
+                scene_associated_data.ldr_scene_frame_texture(),
+                &self.flat_shader,
+                window_viewport,
+                &self.quad,
+            )?;
+        } else {
+            self.render_scene(scene_handle, scene, 0.0)?;
+        }
+
+        Ok(())
+    }
+
     fn render_frame<'a>(
         &mut self,
         scenes: &SceneContainer,
@@ -1840,7 +3475,10 @@ impl Renderer {
     ) -> Result<(), FrameworkError> {
         scope_profile!();
 
-        if self.frame_size.0 == 0 || self.frame_size.1 == 0 {
+        if self.minimized {
+            // Keep the statistics going, so frame time will be correct when the window is restored.
+            self.statistics.begin_frame();
+            self.debug_drawing_context.clear_lines();
             return Ok(());
         }
 
@@ -1849,6 +3487,8 @@ impl Renderer {
         // Make sure to drop associated data for destroyed scenes.
         self.scene_data_map
             .retain(|h, _| scenes.is_valid_handle(*h));
+        self.offscreen_scene_data_map
+            .retain(|(h, _), _| scenes.is_valid_handle(*h));
 
         // We have to invalidate resource bindings cache because some textures or programs,
         // or other GL resources can be destroyed and then on their "names" some new resource
@@ -1857,6 +3497,7 @@ impl Renderer {
         self.state.invalidate_resource_bindings_cache();
         let dt = self.statistics.capped_frame_time;
         self.statistics.begin_frame();
+        self.gpu_timers.begin_frame(&self.state);
 
         let window_viewport = Rect::new(0, 0, self.frame_size.0 as i32, self.frame_size.1 as i32);
         self.backbuffer.clear(
@@ -1870,25 +3511,92 @@ impl Renderer {
         let backbuffer_width = self.frame_size.0 as f32;
         let backbuffer_height = self.frame_size.1 as f32;
 
-        for (scene_handle, scene) in scenes.pair_iter().filter(|(_, s)| *s.enabled) {
-            self.render_scene(scene_handle, scene, dt)?;
+        let mut ordered_scenes = scenes
+            .pair_iter()
+            .filter(|(_, s)| *s.enabled)
+            // Scenes without cameras produce nothing on screen, there is no need to prepare
+            // G-Buffer, etc. Scenes with render targets are kept to clear their targets.
+            .filter(|(_, s)| s.rendering_options.render_target.is_some() || has_enabled_camera(s))
+            .collect::<Vec<_>>();
+        // Stable sort keeps the order of addition for scenes with the same render order.
+        ordered_scenes.sort_by_key(|(_, s)| s.rendering_options.render_order);
+
+        let frozen = self.paused && !std::mem::take(&mut self.step_requested);
+        self.statistics.paused = self.paused;
+
+        // The budget is shared by all cameras of all scenes rendered in the frame.
+        let mut particle_budget = self.particle_budget;
+
+        // There could be no scenes at all (main menu, loading screen, etc.), in this case only the
+        // UI is rendered on top of the cleared back buffer.
+        if self.render_flags.contains(RenderFlags::SCENE) {
+            for (scene_handle, scene) in ordered_scenes {
+                if frozen {
+                    self.present_last_scene_frame(scene_handle, scene, window_viewport)?;
+                } else {
+                    // Global debug lines are drawn only in the scenes, that are rendered on screen,
+                    // offscreen scenes (previews, etc.) use their own drawing contexts.
+                    let render_target = scene.rendering_options.render_target.clone();
+                    let draw_debug_lines = render_target.is_none();
+                    self.render_scene_internal(
+                        scene_handle,
+                        scene,
+                        None,
+                        render_target,
+                        draw_debug_lines,
+                        particle_budget.as_mut(),
+                        dt,
+                    )?;
+                }
+            }
+
+            self.pipeline_state()
+                .set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
         }
 
-        self.pipeline_state()
-            .set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
+        let post_render_layer = if let Some((layer, hook)) = self.post_render_hook.as_mut() {
+            self.post_render_drawing_context.clear();
+            hook(&mut PostRenderContext {
+                drawing_context: &mut self.post_render_drawing_context,
+                frame_size: Vector2::new(backbuffer_width, backbuffer_height),
+            });
+            Some(*layer)
+        } else {
+            None
+        };
+        let post_render_drawings =
+            |layer| (post_render_layer == Some(layer)).then_some(&self.post_render_drawing_context);
+        let under_ui = post_render_drawings(PostRenderHookLayer::UnderUi);
+        let over_ui = post_render_drawings(PostRenderHookLayer::OverUi);
 
         // Render UI on top of everything without gamma correction.
-        for drawing_context in drawing_contexts {
-            self.statistics += self.ui_renderer.render(UiRenderContext {
-                state: &mut self.state,
-                viewport: window_viewport,
-                frame_buffer: &mut self.backbuffer,
-                frame_width: backbuffer_width,
-                frame_height: backbuffer_height,
-                drawing_context,
-                white_dummy: self.white_dummy.clone(),
-                texture_cache: &mut self.texture_cache,
-            })?;
+        self.gpu_timers.begin(&self.state, GpuPass::Ui)?;
+        if self.render_flags.contains(RenderFlags::UI) {
+            for drawing_context in under_ui.into_iter().chain(drawing_contexts).chain(over_ui) {
+                self.statistics += self.ui_renderer.render(UiRenderContext {
+                    state: &mut self.state,
+                    viewport: window_viewport,
+                    frame_buffer: &mut self.backbuffer,
+                    frame_width: backbuffer_width,
+                    frame_height: backbuffer_height,
+                    drawing_context,
+                    white_dummy: self.white_dummy.clone(),
+                    texture_cache: &mut self.texture_cache,
+                })?;
+            }
+        }
+        self.gpu_timers.end(&self.state);
+        self.statistics.pass_times = self.gpu_timers.pass_times();
+
+        self.debug_drawing_context.clear_lines();
+
+        if self.frame_capture_requested {
+            self.frame_capture_requested = false;
+            self.captured_frame = Some(CapturedFrame {
+                width: self.frame_size.0,
+                height: self.frame_size.1,
+                pixels: self.backbuffer.read_pixels(&self.state, window_viewport),
+            });
         }
 
         Ok(())
@@ -1904,11 +3612,21 @@ impl Renderer {
         window: &Window,
     ) -> Result<(), FrameworkError> {
         self.render_frame(scenes, drawing_contexts)?;
+        if self.strict_error_checking {
+            self.state.check_error()?;
+        }
+        if self.strict_timing {
+            self.finish();
+        }
         self.statistics.end_frame();
         window.pre_present_notify();
-        surface.swap_buffers(context)?;
+        self.state
+            .swap_buffers(GlPresentTarget { surface, context })?;
         self.statistics.finalize();
         self.statistics.pipeline = self.state.pipeline_statistics();
+        self.statistics.texture_memory = self.state.texture_memory();
+        self.statistics.geometry_memory = self.state.geometry_memory();
+        self.statistics.geometry_buffers = self.geometry_cache.alive_count();
         Ok(())
     }
 
@@ -1919,9 +3637,18 @@ impl Renderer {
         drawing_contexts: impl Iterator<Item = &'a DrawingContext>,
     ) -> Result<(), FrameworkError> {
         self.render_frame(scenes, drawing_contexts)?;
+        if self.strict_error_checking {
+            self.state.check_error()?;
+        }
+        if self.strict_timing {
+            self.finish();
+        }
         self.statistics.end_frame();
         self.statistics.finalize();
         self.statistics.pipeline = self.state.pipeline_statistics();
+        self.statistics.texture_memory = self.state.texture_memory();
+        self.statistics.geometry_memory = self.state.geometry_memory();
+        self.statistics.geometry_buffers = self.geometry_cache.alive_count();
         Ok(())
     }
 }
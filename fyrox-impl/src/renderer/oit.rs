@@ -0,0 +1,193 @@
+//! Weighted blended order-independent transparency (McGuire and Bavoil, 2013). Transparent objects
+//! are rendered in any order into two render targets: sum of weighted premultiplied colors (and the
+//! product of transparencies in the alpha channel) and sum of weights. Then these targets are
+//! composited on top of the frame. It removes the need of back-to-front sorting at the cost of
+//! slightly less accurate result, which is hardly noticeable for particles.
+//!
+//! Per-target blending is not available everywhere, so both targets share the same blending
+//! function: color channels are accumulated additively and alpha channel is multiplied by
+//! `1 - alpha` of each fragment. See `OitForward` pass of the standard particle system shader as an
+//! example of a shader, that supports this technique.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+        math::Rect,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        RenderPassStatistics,
+    },
+    scene::mesh::surface::SurfaceData,
+};
+use std::{cell::RefCell, rc::Rc};
+
+struct OitCompositeShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    accumulation_texture: UniformLocation,
+    weight_texture: UniformLocation,
+}
+
+impl OitCompositeShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/oit_composite_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+
+        let program =
+            GpuProgram::from_source(state, "OitCompositeShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            accumulation_texture: program
+                .uniform_location(state, &ImmutableString::new("accumulationTexture"))?,
+            weight_texture: program
+                .uniform_location(state, &ImmutableString::new("weightTexture"))?,
+            program,
+        })
+    }
+}
+
+/// Creates a frame buffer for order-independent transparency. The frame buffer shares depth-stencil
+/// texture with the scene frame buffer, so transparent objects are correctly occluded by opaque ones.
+pub(crate) fn make_oit_framebuffer(
+    state: &PipelineState,
+    width: usize,
+    height: usize,
+    depth_stencil: Rc<RefCell<GpuTexture>>,
+) -> Result<FrameBuffer, FrameworkError> {
+    let make_texture = |pixel_kind| -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            pixel_kind,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        Ok(Rc::new(RefCell::new(texture)))
+    };
+
+    FrameBuffer::new(
+        state,
+        Some(Attachment {
+            kind: AttachmentKind::DepthStencil,
+            texture: depth_stencil,
+        }),
+        vec![
+            // Weighted premultiplied color + product of transparencies.
+            Attachment {
+                kind: AttachmentKind::Color,
+                texture: make_texture(PixelKind::RGBA16F)?,
+            },
+            // Sum of weights.
+            Attachment {
+                kind: AttachmentKind::Color,
+                texture: make_texture(PixelKind::R16F)?,
+            },
+        ],
+    )
+}
+
+pub struct OitRenderer {
+    shader: OitCompositeShader,
+    quad: GeometryBuffer,
+}
+
+impl OitRenderer {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: OitCompositeShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+        })
+    }
+
+    /// Prepares the frame buffer for a new portion of transparent objects.
+    pub(crate) fn clear(
+        &self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+        oit_framebuffer: &mut FrameBuffer,
+    ) {
+        // Zero colors and weights, full revealage (nothing is covered yet). Depth must be kept.
+        oit_framebuffer.clear(state, viewport, Some(Color::BLACK), None, None);
+    }
+
+    /// Composites accumulated transparent objects on top of the given frame buffer.
+    pub(crate) fn composite(
+        &self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+        oit_framebuffer: &FrameBuffer,
+        frame_buffer: &mut FrameBuffer,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let mut statistics = RenderPassStatistics::default();
+
+        let frame_matrix = Matrix4::new_orthographic(
+            0.0,
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+            -1.0,
+            1.0,
+        ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+        ));
+
+        let accumulation = oit_framebuffer.color_attachments()[0].texture.clone();
+        let weight = oit_framebuffer.color_attachments()[1].texture.clone();
+
+        statistics += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &self.shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&self.shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&self.shader.accumulation_texture, &accumulation)
+                    .set_texture(&self.shader.weight_texture, &weight);
+            },
+        )?;
+
+        Ok(statistics)
+    }
+}
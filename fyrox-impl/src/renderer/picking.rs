@@ -0,0 +1,217 @@
+//! Object picking renders an "object id" of every scene node into a separate frame buffer, which can then be
+//! read back to find out which scene node is under a pixel. It is much more robust than CPU ray casting for
+//! overlapping geometry, because the id is resolved with the depth test.
+//!
+//! # Limitations
+//!
+//! The pass uses a simple position-only shader, so vertex animations of materials are not taken into account:
+//! skinned meshes are drawn in the bind pose and camera-facing billboards (particles, sprites) are not
+//! pickable at all.
+
+use crate::{
+    core::{color::Color, math::Rect, pool::Handle, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        bundle::RenderDataBundleStorage,
+        camera_view_projection_matrix, far_plane_depth,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        GeometryCache, RenderPassStatistics,
+    },
+    scene::{camera::Camera, node::Node},
+};
+use std::{cell::RefCell, rc::Rc};
+
+struct ObjectIdShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    object_id: UniformLocation,
+}
+
+impl ObjectIdShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/object_id_fs.glsl");
+        let vertex_source = include_str!("shaders/object_id_vs.glsl");
+
+        let program =
+            GpuProgram::from_source(state, "ObjectIdShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            object_id: program.uniform_location(state, &ImmutableString::new("objectId"))?,
+            program,
+        })
+    }
+}
+
+pub(crate) struct ObjectIdRenderContext<'a, 'b> {
+    pub state: &'a PipelineState,
+    pub camera: &'b Camera,
+    pub viewport: Rect<i32>,
+    pub geom_cache: &'a mut GeometryCache,
+    pub bundle_storage: &'a RenderDataBundleStorage,
+    pub reverse_z: bool,
+}
+
+/// Object id renderer keeps the frame buffer with ids of scene nodes and the table that maps the ids
+/// back to the handles of scene nodes.
+pub struct ObjectIdRenderer {
+    framebuffer: FrameBuffer,
+    shader: ObjectIdShader,
+    // Zero id means "nothing", so an id of a node is its index in this array plus one.
+    nodes: Vec<Handle<Node>>,
+    width: i32,
+    height: i32,
+}
+
+fn create_texture(
+    state: &PipelineState,
+    width: usize,
+    height: usize,
+    pixel_kind: PixelKind,
+) -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+    let mut texture = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        MinificationFilter::Nearest,
+        MagnificationFilter::Nearest,
+        1,
+        None,
+    )?;
+    texture
+        .bind_mut(state, 0)
+        .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+    Ok(Rc::new(RefCell::new(texture)))
+}
+
+impl ObjectIdRenderer {
+    pub(crate) fn new(
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, FrameworkError> {
+        let framebuffer = FrameBuffer::new(
+            state,
+            Some(Attachment {
+                kind: AttachmentKind::Depth,
+                texture: create_texture(state, width, height, PixelKind::D32F)?,
+            }),
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: create_texture(state, width, height, PixelKind::RGBA8)?,
+            }],
+        )?;
+
+        Ok(Self {
+            framebuffer,
+            shader: ObjectIdShader::new(state)?,
+            nodes: Default::default(),
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    pub(crate) fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        self.framebuffer.resize(state, width, height)?;
+        self.width = width as i32;
+        self.height = height as i32;
+        Ok(())
+    }
+
+    pub(crate) fn clear(&mut self, state: &PipelineState, reverse_z: bool) {
+        self.nodes.clear();
+        self.framebuffer.clear(
+            state,
+            Rect::new(0, 0, self.width, self.height),
+            Some(Color::from_rgba(0, 0, 0, 0)),
+            Some(far_plane_depth(reverse_z)),
+            None,
+        );
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        args: ObjectIdRenderContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let ObjectIdRenderContext {
+            state,
+            camera,
+            viewport,
+            geom_cache,
+            bundle_storage,
+            reverse_z,
+        } = args;
+
+        let view_projection = camera_view_projection_matrix(camera, reverse_z);
+
+        for bundle in bundle_storage.bundles.iter() {
+            let Some(geometry) = geom_cache.get(state, &bundle.data, bundle.time_to_live) else {
+                continue;
+            };
+
+            for instance in bundle.instances.iter() {
+                self.nodes.push(instance.node_handle);
+                let object_id = self.nodes.len() as u32;
+
+                statistics += self.framebuffer.draw(
+                    geometry,
+                    state,
+                    viewport,
+                    &self.shader.program,
+                    &DrawParameters {
+                        cull_face: None,
+                        ..Default::default()
+                    },
+                    instance.element_range,
+                    |mut program_binding| {
+                        program_binding
+                            .set_matrix4(
+                                &self.shader.wvp_matrix,
+                                &(view_projection * instance.world_transform),
+                            )
+                            .set_u32(&self.shader.object_id, object_id);
+                    },
+                )?;
+            }
+        }
+
+        Ok(statistics)
+    }
+
+    /// Returns a handle of a scene node, that was rendered at the given pixel. Coordinates are
+    /// in pixels with the origin at the top left corner of the frame.
+    pub fn pick(&self, state: &PipelineState, x: u32, y: u32) -> Option<Handle<Node>> {
+        let (x, y) = (x as i32, y as i32);
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let pixel = self
+            .framebuffer
+            .read_pixels(state, Rect::new(x, self.height - 1 - y, 1, 1));
+        let object_id = u32::from_le_bytes(pixel.as_slice().try_into().ok()?);
+
+        object_id
+            .checked_sub(1)
+            .and_then(|index| self.nodes.get(index as usize))
+            .cloned()
+    }
+}
@@ -1,6 +1,11 @@
 uniform sampler2D diffuseTexture;
 uniform sampler2D aoSampler;
 uniform sampler2D ambientTexture;
+uniform sampler2D emissionTexture;
+uniform sampler2D normalTexture;
+uniform samplerCube irradianceMap;
+uniform bool useImageBasedLighting;
+uniform float irradianceIntensity;
 uniform vec4 ambientColor;
 
 out vec4 FragColor;
@@ -10,9 +15,16 @@ void main()
 {
     float ambientOcclusion = texture(aoSampler, texCoord).r;
     vec4 ambientPixel = texture(ambientTexture, texCoord);
-    FragColor = (ambientColor + ambientPixel) * S_SRGBToLinear(texture(diffuseTexture, texCoord));
+    vec4 ambient = ambientColor;
+    if (useImageBasedLighting) {
+        // Ambient color is used as a tint of diffuse image-based lighting.
+        vec3 normal = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+        ambient.rgb *= irradianceIntensity * texture(irradianceMap, normal).rgb;
+    }
+    FragColor = (ambient + ambientPixel) * S_SRGBToLinear(texture(diffuseTexture, texCoord));
     FragColor.rgb *= ambientOcclusion;
+    // Emission does not depend on lighting, so it is added as is. This makes emissive surfaces
+    // visible even if there's no lights in the scene at all.
+    FragColor.rgb += texture(emissionTexture, texCoord).rgb;
     FragColor.a = ambientPixel.a;
-
-    // TODO: Implement IBL.
 }
\ No newline at end of file
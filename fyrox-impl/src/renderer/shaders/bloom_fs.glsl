@@ -1,4 +1,6 @@
 uniform sampler2D hdrSampler;
+uniform float threshold;
+uniform float intensity;
 
 in vec2 texCoord;
 
@@ -7,8 +9,8 @@ out vec4 outBrightColor;
 void main() {
     vec3 hdrPixel = texture(hdrSampler, texCoord).rgb;
 
-    if (S_Luminance(hdrPixel) > 1.0) {
-        outBrightColor = vec4(hdrPixel, 0.0);
+    if (S_Luminance(hdrPixel) > threshold) {
+        outBrightColor = vec4(hdrPixel * intensity, 0.0);
     } else {
         outBrightColor = vec4(0.0);
     }
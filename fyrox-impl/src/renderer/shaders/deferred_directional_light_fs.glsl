@@ -6,7 +6,6 @@ uniform sampler2D materialTexture;
 uniform vec3 lightDirection;
 uniform vec4 lightColor;
 uniform mat4 invViewProj;
-uniform vec3 cameraPosition;
 uniform float lightIntensity;
 uniform mat4 viewMatrix;
 
@@ -35,9 +34,12 @@ float CsmGetShadow(in sampler2D sampler, in vec3 fragmentPosition, in mat4 light
 
 void main()
 {
-    vec3 material = texture(materialTexture, texCoord).rgb;
+    vec4 material = texture(materialTexture, texCoord);
+    // Alpha channel of the material texture tells whether the fragment receives shadows or not.
+    bool receiveShadows = material.a > 0.0;
 
-    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 screenPosition = vec3(texCoord, texture(depthTexture, texCoord).r);
+    vec3 fragmentPosition = S_UnProject(screenPosition, invViewProj);
     vec4 diffuseColor = texture(colorTexture, texCoord);
 
     TPBRContext ctx;
@@ -47,19 +49,21 @@ void main()
     ctx.lightColor = lightColor.rgb;
     ctx.metallic = material.x;
     ctx.roughness = material.y;
-    ctx.viewVector = normalize(cameraPosition - fragmentPosition);
+    ctx.viewVector = S_ViewVector(screenPosition, fragmentPosition, invViewProj);
 
     vec3 lighting = S_PBR_CalculateLight(ctx);
 
     float fragmentZViewSpace = abs((viewMatrix * vec4(fragmentPosition, 1.0)).z);
 
     float shadow = 1.0;
-    if (fragmentZViewSpace <= cascadeDistances[0]) {
-        shadow = CsmGetShadow(shadowCascade0, fragmentPosition, lightViewProjMatrices[0]);
-    } else if (fragmentZViewSpace <= cascadeDistances[1]) {
-        shadow = CsmGetShadow(shadowCascade1, fragmentPosition, lightViewProjMatrices[1]);
-    } else if (fragmentZViewSpace <= cascadeDistances[2]) {
-        shadow = CsmGetShadow(shadowCascade2, fragmentPosition, lightViewProjMatrices[2]);
+    if (receiveShadows) {
+        if (fragmentZViewSpace <= cascadeDistances[0]) {
+            shadow = CsmGetShadow(shadowCascade0, fragmentPosition, lightViewProjMatrices[0]);
+        } else if (fragmentZViewSpace <= cascadeDistances[1]) {
+            shadow = CsmGetShadow(shadowCascade1, fragmentPosition, lightViewProjMatrices[1]);
+        } else if (fragmentZViewSpace <= cascadeDistances[2]) {
+            shadow = CsmGetShadow(shadowCascade2, fragmentPosition, lightViewProjMatrices[2]);
+        }
     }
 
     FragColor = shadow * vec4(lightIntensity * lighting, diffuseColor.a);
@@ -8,11 +8,11 @@ uniform vec3 lightPos;
 uniform float lightRadius;
 uniform vec4 lightColor;
 uniform mat4 invViewProj;
-uniform vec3 cameraPosition;
 uniform bool softShadows;
 uniform bool shadowsEnabled;
 uniform float shadowBias;
 uniform float lightIntensity;
+uniform bool inverseSquareAttenuation;
 uniform float shadowAlpha;
 
 in vec2 texCoord;
@@ -20,9 +20,12 @@ out vec4 FragColor;
 
 void main()
 {
-    vec3 material = texture(materialTexture, texCoord).rgb;
+    vec4 material = texture(materialTexture, texCoord);
+    // Alpha channel of the material texture tells whether the fragment receives shadows or not.
+    bool receiveShadows = material.a > 0.0;
 
-    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 screenPosition = vec3(texCoord, texture(depthTexture, texCoord).r);
+    vec3 fragmentPosition = S_UnProject(screenPosition, invViewProj);
     vec3 fragmentToLight = lightPos - fragmentPosition;
     float distance = length(fragmentToLight);
 
@@ -35,14 +38,14 @@ void main()
     ctx.lightColor = lightColor.rgb;
     ctx.metallic = material.x;
     ctx.roughness = material.y;
-    ctx.viewVector = normalize(cameraPosition - fragmentPosition);
+    ctx.viewVector = S_ViewVector(screenPosition, fragmentPosition, invViewProj);
 
     vec3 lighting = S_PBR_CalculateLight(ctx);
 
-    float distanceAttenuation = S_LightDistanceAttenuation(distance, lightRadius);
+    float distanceAttenuation = S_LightAttenuation(inverseSquareAttenuation, distance, lightRadius);
 
     float shadow = S_PointShadow(
-        shadowsEnabled, softShadows, distance, shadowBias, ctx.fragmentToLight, pointShadowTexture);
+        shadowsEnabled && receiveShadows, softShadows, distance, shadowBias, ctx.fragmentToLight, pointShadowTexture);
     float finalShadow = mix(1.0, shadow, shadowAlpha);
 
     FragColor = vec4(lightIntensity * distanceAttenuation * finalShadow * lighting, diffuseColor.a);
@@ -13,13 +13,13 @@ uniform vec3 lightDirection;
 uniform float halfHotspotConeAngleCos;
 uniform float halfConeAngleCos;
 uniform mat4 invViewProj;
-uniform vec3 cameraPosition;
 uniform bool shadowsEnabled;
 uniform bool softShadows;
 uniform float shadowMapInvSize;
 uniform float shadowBias;
 uniform bool cookieEnabled;
 uniform float lightIntensity;
+uniform bool inverseSquareAttenuation;
 uniform float shadowAlpha;
 
 in vec2 texCoord;
@@ -27,9 +27,12 @@ out vec4 FragColor;
 
 void main()
 {
-    vec3 material = texture(materialTexture, texCoord).rgb;
+    vec4 material = texture(materialTexture, texCoord);
+    // Alpha channel of the material texture tells whether the fragment receives shadows or not.
+    bool receiveShadows = material.a > 0.0;
 
-    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 screenPosition = vec3(texCoord, texture(depthTexture, texCoord).r);
+    vec3 fragmentPosition = S_UnProject(screenPosition, invViewProj);
     vec3 fragmentToLight = lightPos - fragmentPosition;
     float distance = length(fragmentToLight);
     vec4 diffuseColor = texture(colorTexture, texCoord);
@@ -41,17 +44,17 @@ void main()
     ctx.lightColor = lightColor.rgb;
     ctx.metallic = material.x;
     ctx.roughness = material.y;
-    ctx.viewVector = normalize(cameraPosition - fragmentPosition);
+    ctx.viewVector = S_ViewVector(screenPosition, fragmentPosition, invViewProj);
 
     vec3 lighting = S_PBR_CalculateLight(ctx);
 
-    float distanceAttenuation = S_LightDistanceAttenuation(distance, lightRadius);
+    float distanceAttenuation = S_LightAttenuation(inverseSquareAttenuation, distance, lightRadius);
 
     float spotAngleCos = dot(lightDirection, ctx.fragmentToLight);
     float coneFactor = smoothstep(halfConeAngleCos, halfHotspotConeAngleCos, spotAngleCos);
 
     float shadow = S_SpotShadowFactor(
-        shadowsEnabled, softShadows, shadowBias, fragmentPosition,
+        shadowsEnabled && receiveShadows, softShadows, shadowBias, fragmentPosition,
             lightViewProjMatrix, shadowMapInvSize, spotShadowTexture);
     float finalShadow = mix(1.0, shadow, shadowAlpha);
 
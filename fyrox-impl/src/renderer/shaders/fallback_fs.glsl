@@ -0,0 +1,7 @@
+// Bright magenta makes objects with broken shaders easy to spot.
+out vec4 FragColor;
+
+void main()
+{
+    FragColor = vec4(1.0, 0.0, 1.0, 1.0);
+}
@@ -0,0 +1,8 @@
+layout(location = 0) in vec3 vertexPosition;
+
+uniform mat4 fyrox_worldViewProjection;
+
+void main()
+{
+    gl_Position = fyrox_worldViewProjection * vec4(vertexPosition, 1.0);
+}
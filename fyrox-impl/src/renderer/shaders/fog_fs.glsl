@@ -0,0 +1,36 @@
+uniform sampler2D depthSampler;
+uniform mat4 inverseProjectionMatrix;
+uniform vec4 fogColor;
+// 0 - linear, 1 - exponential.
+uniform int fogMode;
+uniform float fogStart;
+uniform float fogEnd;
+uniform float fogDensity;
+// Depth value of empty pixels, it is either 1.0 or 0.0 (when reversed depth is used).
+uniform float farDepth;
+
+in vec2 texCoord;
+
+out vec4 FragColor;
+
+void main()
+{
+    float depth = texture(depthSampler, texCoord).r;
+
+    // There's no geometry at the far plane (it could be covered by a skybox), so leave it as is.
+    if (depth == farDepth) {
+        discard;
+    }
+
+    vec3 viewPosition = S_UnProject(vec3(texCoord, depth), inverseProjectionMatrix);
+    float distance = max(length(viewPosition) - fogStart, 0.0);
+
+    float fogFactor;
+    if (fogMode == 0) {
+        fogFactor = distance / max(fogEnd - fogStart, 0.0001);
+    } else {
+        fogFactor = 1.0 - exp(-fogDensity * distance);
+    }
+
+    FragColor = vec4(fogColor.rgb, clamp(fogFactor, 0.0, 1.0) * fogColor.a);
+}
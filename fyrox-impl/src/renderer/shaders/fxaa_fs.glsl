@@ -8,7 +8,6 @@
 uniform sampler2D screenTexture;
 uniform vec2 inverseScreenSize;
 
-in vec2 texCoord;
 out vec4 fragColor;
 
 // Settings for FXAA.
@@ -25,16 +24,20 @@ float rgb2luma(vec3 rgb){
 // Performs FXAA post-process anti-aliasing as described in the Nvidia FXAA white paper and the associated shader code.
 void main()
 {
-    vec4 colorCenter = texture(screenTexture, texCoord);
+    // The frame could be rendered into a part of the texture (see camera viewport), so texture
+    // coordinates are derived from the fragment position instead of the quad.
+    vec2 screenUv = gl_FragCoord.xy * inverseScreenSize;
+
+    vec4 colorCenter = texture(screenTexture, screenUv);
 
     // Luma at the current fragment
     float lumaCenter = rgb2luma(colorCenter.rgb);
 
     // Luma at the four direct neighbours of the current fragment.
-    float lumaDown = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(0, -1)).rgb);
-    float lumaUp = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(0, 1)).rgb);
-    float lumaLeft = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(-1, 0)).rgb);
-    float lumaRight = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(1, 0)).rgb);
+    float lumaDown = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(0, -1)).rgb);
+    float lumaUp = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(0, 1)).rgb);
+    float lumaLeft = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(-1, 0)).rgb);
+    float lumaRight = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(1, 0)).rgb);
 
     // Find the maximum and minimum luma around the current fragment.
     float lumaMin = min(lumaCenter, min(min(lumaDown, lumaUp), min(lumaLeft, lumaRight)));
@@ -50,10 +53,10 @@ void main()
     }
 
     // Query the 4 remaining corners lumas.
-    float lumaDownLeft    = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(-1, -1)).rgb);
-    float lumaUpRight    = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(1, 1)).rgb);
-    float lumaUpLeft    = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(-1, 1)).rgb);
-    float lumaDownRight = rgb2luma(textureLodOffset(screenTexture, texCoord, 0.0, ivec2(1, -1)).rgb);
+    float lumaDownLeft    = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(-1, -1)).rgb);
+    float lumaUpRight    = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(1, 1)).rgb);
+    float lumaUpLeft    = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(-1, 1)).rgb);
+    float lumaDownRight = rgb2luma(textureLodOffset(screenTexture, screenUv, 0.0, ivec2(1, -1)).rgb);
 
     // Combine the four edges lumas (using intermediary variables for future computations with the same values).
     float lumaDownUp = lumaDown + lumaUp;
@@ -99,7 +102,7 @@ void main()
     }
 
     // Shift UV in the correct direction by half a pixel.
-    vec2 currentUv = texCoord;
+    vec2 currentUv = screenUv;
     if (isHorizontal){
         currentUv.y += stepLength * 0.5;
     } else {
@@ -165,8 +168,8 @@ void main()
     }
 
     // Compute the distances to each side edge of the edge (!).
-    float distance1 = isHorizontal ? (texCoord.x - uv1.x) : (texCoord.y - uv1.y);
-    float distance2 = isHorizontal ? (uv2.x - texCoord.x) : (uv2.y - texCoord.y);
+    float distance1 = isHorizontal ? (screenUv.x - uv1.x) : (screenUv.y - uv1.y);
+    float distance2 = isHorizontal ? (uv2.x - screenUv.x) : (uv2.y - screenUv.y);
 
     // In which direction is the side of the edge closer ?
     bool isDirection1 = distance1 < distance2;
@@ -204,7 +207,7 @@ void main()
     finalOffset = max(finalOffset, subPixelOffsetFinal);
 
     // Compute the final UV coordinates.
-    vec2 finalUv = texCoord;
+    vec2 finalUv = screenUv;
     if (isHorizontal){
         finalUv.y += finalOffset * stepLength;
     } else {
@@ -0,0 +1,36 @@
+uniform sampler2D diffuseTexture;
+uniform sampler2D depthTexture;
+uniform mat4 inverseProjectionMatrix;
+uniform mat4 inverseViewProjectionMatrix;
+uniform float zFar;
+// Depth value of empty pixels, it is either 1.0 or 0.0 (when reversed depth is used).
+uniform float farDepth;
+// 0 - albedo, 1 - normal, 2 - depth, 3 - position.
+uniform int mode;
+
+out vec4 FragColor;
+
+in vec2 texCoord;
+
+void main()
+{
+    if (mode == 0 || mode == 1) {
+        // Normals are already packed into [0; 1] range in the GBuffer.
+        FragColor = vec4(texture(diffuseTexture, texCoord).rgb, 1.0);
+    } else {
+        float depth = texture(depthTexture, texCoord).r;
+
+        if (mode == 2) {
+            // Raw depth is highly non-linear, show linear distance instead.
+            vec3 viewPosition = S_UnProject(vec3(texCoord, depth), inverseProjectionMatrix);
+            FragColor = vec4(vec3(clamp(-viewPosition.z / zFar, 0.0, 1.0)), 1.0);
+        } else {
+            // Show a repeating pattern with one meter period, otherwise positions of most of the
+            // scene will be clamped to white.
+            vec3 worldPosition = S_UnProject(vec3(texCoord, depth), inverseViewProjectionMatrix);
+            FragColor = depth == farDepth
+                ? vec4(0.0, 0.0, 0.0, 1.0)
+                : vec4(fract(worldPosition), 1.0);
+        }
+    }
+}
@@ -3,11 +3,22 @@ uniform sampler2D lumSampler;
 uniform sampler2D bloomSampler;
 uniform sampler3D colorMapSampler;
 uniform bool useColorGrading;
+uniform float colorGradingIntensity;
 uniform float keyValue;
 uniform float minLuminance;
 uniform float maxLuminance;
+uniform float minExposure;
+uniform float maxExposure;
 uniform bool autoExposure;
 uniform float fixedExposure;
+// In EV stops.
+uniform float exposureCompensation;
+// 0 - Exponential, 1 - Reinhard, 2 - ACES Filmic, 3 - None.
+uniform int toneMapping;
+uniform float gamma;
+uniform float brightness;
+uniform float contrast;
+uniform float saturation;
 
 in vec2 texCoord;
 
@@ -22,25 +33,54 @@ vec3 ColorGrading(vec3 color) {
     return texture(colorMapSampler, scale * color + offset).rgb;
 }
 
+vec3 SimpleColorGrading(vec3 color) {
+    color = (color - vec3(0.5)) * contrast + vec3(0.5) + vec3(brightness);
+    color = mix(vec3(S_Luminance(color)), color, saturation);
+    return pow(max(color, vec3(0.0)), vec3(1.0 / gamma));
+}
+
+vec3 ToneMapping(vec3 color) {
+    if (toneMapping == 1) {
+        return color / (vec3(1.0) + color);
+    } else if (toneMapping == 2) {
+        // Narkowicz 2015, "ACES Filmic Tone Mapping Curve".
+        const float a = 2.51;
+        const float b = 0.03;
+        const float c = 2.43;
+        const float d = 0.59;
+        const float e = 0.14;
+        return clamp((color * (a * color + b)) / (color * (c * color + d) + e), 0.0, 1.0);
+    } else if (toneMapping == 3) {
+        return clamp(color, 0.0, 1.0);
+    } else {
+        return vec3(1.0) - exp(-color);
+    }
+}
+
 void main() {
     vec4 hdrColor = texture(hdrSampler, texCoord);
 
-    hdrColor += texture(bloomSampler, texCoord);
+    hdrColor.rgb += texture(bloomSampler, texCoord).rgb;
 
     float luminance = texture(lumSampler, vec2(0.5, 0.5)).r;
 
     float exposure;
     if (autoExposure) {
-        exposure = keyValue / clamp(luminance, minLuminance, maxLuminance);
+        exposure = clamp(keyValue / clamp(luminance, minLuminance, maxLuminance), minExposure, maxExposure);
     } else {
         exposure = fixedExposure;
     }
+    exposure *= exp2(exposureCompensation);
+
+    vec4 ldrColor = vec4(ToneMapping(hdrColor.rgb * exposure), hdrColor.a);
 
-    vec4 ldrColor = vec4(vec3(1.0) - exp(-hdrColor.rgb * exposure), hdrColor.a);
+    vec3 srgbColor = SimpleColorGrading(S_LinearToSRGB(ldrColor).rgb);
 
     if (useColorGrading) {
-        outLdrColor = vec4(ColorGrading(S_LinearToSRGB(ldrColor).rgb), ldrColor.a);
+        vec3 clampedColor = clamp(srgbColor, 0.0, 1.0);
+        vec3 gradedColor = mix(clampedColor, ColorGrading(clampedColor), colorGradingIntensity);
+        outLdrColor = vec4(gradedColor, ldrColor.a);
     } else {
-        outLdrColor = S_LinearToSRGB(ldrColor);
+        outLdrColor = vec4(srgbColor, ldrColor.a);
     }
 }
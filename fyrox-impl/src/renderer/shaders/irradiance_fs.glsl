@@ -0,0 +1,49 @@
+uniform samplerCube environmentMap;
+// Index of the cube map face being rendered: +X, -X, +Y, -Y, +Z, -Z.
+uniform int faceIndex;
+
+in vec2 texCoord;
+
+out vec4 FragColor;
+
+// Converts texture coordinates of a cube map face into a direction, see "Cube Map Texture
+// Selection" of OpenGL specification.
+vec3 FaceDirection(vec2 uv) {
+    if (faceIndex == 0) {
+        return vec3(1.0, -uv.y, -uv.x);
+    } else if (faceIndex == 1) {
+        return vec3(-1.0, -uv.y, uv.x);
+    } else if (faceIndex == 2) {
+        return vec3(uv.x, 1.0, uv.y);
+    } else if (faceIndex == 3) {
+        return vec3(uv.x, -1.0, -uv.y);
+    } else if (faceIndex == 4) {
+        return vec3(uv.x, -uv.y, 1.0);
+    } else {
+        return vec3(-uv.x, -uv.y, -1.0);
+    }
+}
+
+void main()
+{
+    vec3 normal = normalize(FaceDirection(texCoord * 2.0 - 1.0));
+    vec3 up = abs(normal.y) < 0.999 ? vec3(0.0, 1.0, 0.0) : vec3(0.0, 0.0, 1.0);
+    vec3 right = normalize(cross(up, normal));
+    up = cross(normal, right);
+
+    // Cosine-weighted integration of the environment over the hemisphere around the normal.
+    const float sampleDelta = 0.05;
+    vec3 irradiance = vec3(0.0);
+    float sampleCount = 0.0;
+    for (float phi = 0.0; phi < 2.0 * PI; phi += sampleDelta) {
+        for (float theta = 0.0; theta < 0.5 * PI; theta += sampleDelta) {
+            vec3 tangentSample = vec3(sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            vec3 sampleDirection = tangentSample.x * right + tangentSample.y * up + tangentSample.z * normal;
+            vec3 radiance = S_SRGBToLinear(texture(environmentMap, sampleDirection)).rgb;
+            irradiance += radiance * cos(theta) * sin(theta);
+            sampleCount += 1.0;
+        }
+    }
+
+    FragColor = vec4(PI * irradiance / sampleCount, 1.0);
+}
@@ -0,0 +1,14 @@
+uniform uint objectId;
+
+out vec4 FragColor;
+
+void main()
+{
+    // Pack 32-bit id into RGBA8 color, it will be unpacked on CPU side.
+    FragColor = vec4(
+        float(objectId & 255u),
+        float((objectId >> 8u) & 255u),
+        float((objectId >> 16u) & 255u),
+        float((objectId >> 24u) & 255u)
+    ) / 255.0;
+}
\ No newline at end of file
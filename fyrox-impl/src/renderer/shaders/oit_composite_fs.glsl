@@ -0,0 +1,22 @@
+uniform sampler2D accumulationTexture;
+uniform sampler2D weightTexture;
+
+in vec2 texCoord;
+
+out vec4 FragColor;
+
+void main()
+{
+    vec4 accumulation = texture(accumulationTexture, texCoord);
+
+    // Alpha channel contains product of transparencies of every fragment.
+    float revealage = accumulation.a;
+    if (revealage >= 1.0) {
+        // Nothing was rendered in the pixel.
+        discard;
+    }
+
+    float weight = texture(weightTexture, texCoord).r;
+
+    FragColor = vec4(accumulation.rgb / max(weight, 0.00001), 1.0 - revealage);
+}
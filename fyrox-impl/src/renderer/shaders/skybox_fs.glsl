@@ -1,4 +1,5 @@
 uniform samplerCube cubemapTexture;
+uniform float intensity;
 
 out vec4 FragColor;
 
@@ -6,5 +7,6 @@ in vec3 texCoord;
 
 void main()
 {
-    FragColor = S_SRGBToLinear(texture(cubemapTexture, texCoord));
+    vec4 color = S_SRGBToLinear(texture(cubemapTexture, texCoord));
+    FragColor = vec4(intensity * color.rgb, color.a);
 }
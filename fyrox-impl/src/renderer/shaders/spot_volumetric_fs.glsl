@@ -3,6 +3,7 @@ uniform sampler2D depthSampler;
 uniform vec3 lightPosition;
 uniform vec3 lightDirection;
 uniform float coneAngleCos;
+uniform float hotspotConeAngleCos;
 uniform mat4 invProj;
 uniform vec3 lightColor;
 uniform vec3 scatterFactor;
@@ -54,7 +55,13 @@ void main()
             minDepth = max(minDepth, 0.0);
             maxDepth = clamp(maxDepth, 0.0, fragmentDepth);
 
-            scatter = scatterFactor * S_InScatter(viewDirection * minDepth, viewDirection, lightPosition, maxDepth - minDepth);
+            // Soft cone edge: the middle of the segment inside the cone is used to approximate
+            // how close the ray passes to the axis of the cone.
+            vec3 middlePoint = viewDirection * (0.5 * (minDepth + maxDepth));
+            float middleAngleCos = dot(normalize(middlePoint - lightPosition), lightDirection);
+            float coneFactor = smoothstep(coneAngleCos, hotspotConeAngleCos, middleAngleCos);
+
+            scatter = coneFactor * scatterFactor * S_InScatter(viewDirection * minDepth, viewDirection, lightPosition, maxDepth - minDepth);
         }
     }
 
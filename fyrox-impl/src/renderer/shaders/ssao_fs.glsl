@@ -5,6 +5,8 @@ uniform sampler2D normalSampler;
 uniform sampler2D noiseSampler;
 
 uniform float radius;
+uniform float bias;
+uniform int sampleCount;
 uniform mat4 inverseProjectionMatrix;
 uniform mat4 projectionMatrix;
 uniform vec3 kernel[KERNEL_SIZE];
@@ -29,8 +31,14 @@ void main() {
     vec3 bitangent = normalize(cross(viewSpaceNormal, tangent));
     mat3 TBN = mat3(tangent, bitangent, viewSpaceNormal);
 
+    // Kernel samples are sorted by their distance from the center, so take them with a stride
+    // to cover the whole hemisphere when sample count is less than kernel size.
+    int stride = max(KERNEL_SIZE / max(sampleCount, 1), 1);
+    int takenSamples = 0;
+
     float occlusion = 0.0;
-    for (int i = 0; i < KERNEL_SIZE; ++i) {
+    for (int i = 0; i < KERNEL_SIZE; i += stride) {
+        ++takenSamples;
         vec3 samplePoint = fragPos.xyz + TBN * kernel[i] * radius;
 
         vec4 offset = projectionMatrix * vec4(samplePoint, 1.0);
@@ -40,8 +48,8 @@ void main() {
         vec3 position = GetViewSpacePosition(offset.xy);
 
         float rangeCheck = smoothstep(0.0, 1.0, radius / abs(fragPos.z - position.z));
-        occlusion += rangeCheck * ((position.z > samplePoint.z + 0.04) ? 1.0 : 0.0);
+        occlusion += rangeCheck * ((position.z > samplePoint.z + bias) ? 1.0 : 0.0);
     }
 
-    finalOcclusion = 1.0 - occlusion / float(KERNEL_SIZE);
+    finalOcclusion = 1.0 - occlusion / float(takenSamples);
 }
\ No newline at end of file
@@ -3,6 +3,7 @@
 uniform sampler2D diffuseTexture;
 
 uniform bool isFont;
+uniform bool isSdfFont;
 uniform vec4 solidColor;
 uniform float opacity;
 
@@ -76,7 +77,18 @@ void main()
 
     if (isFont)
     {
-        fragColor.a *= diffuseColor.r;
+        if (isSdfFont)
+        {
+            // Reconstruct the edge from the distance field, the width of the transition is one
+            // screen pixel at any scale.
+            float distance = diffuseColor.r;
+            float width = fwidth(distance);
+            fragColor.a *= smoothstep(0.5 - width, 0.5 + width, distance);
+        }
+        else
+        {
+            fragColor.a *= diffuseColor.r;
+        }
     }
     else
     {
@@ -0,0 +1,18 @@
+uniform sampler2D depthTexture;
+uniform vec4 wireColor;
+// Depth value of empty pixels, it is either 1.0 or 0.0 (when reversed depth is used).
+uniform float farDepth;
+
+out vec4 FragColor;
+
+in vec2 texCoord;
+
+void main()
+{
+    // Only edges of the geometry were written in the depth buffer.
+    if (texture(depthTexture, texCoord).r == farDepth) {
+        discard;
+    }
+
+    FragColor = wireColor;
+}
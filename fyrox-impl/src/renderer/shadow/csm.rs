@@ -3,6 +3,7 @@ use crate::{
         algebra::{Matrix4, Point3, Vector2, Vector3},
         math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Rect},
     },
+    material::CullMode,
     renderer::{
         apply_material,
         bundle::{ObserverInfo, RenderDataBundleStorage},
@@ -17,7 +18,8 @@ use crate::{
             state::{ColorMask, PipelineState},
         },
         storage::MatrixStorageCache,
-        MaterialContext, RenderPassStatistics, ShadowMapPrecision, DIRECTIONAL_SHADOW_PASS_NAME,
+        MaterialContext, RenderFlags, RenderPassStatistics, ShadowMapPrecision,
+        DIRECTIONAL_SHADOW_PASS_NAME,
     },
     scene::{
         camera::Camera,
@@ -88,7 +90,8 @@ impl Cascade {
 }
 
 pub struct CsmRenderer {
-    cascades: [Cascade; CSM_NUM_CASCADES],
+    // At least one and no more than `CSM_NUM_CASCADES` cascades.
+    cascades: Vec<Cascade>,
     size: usize,
     precision: ShadowMapPrecision,
 }
@@ -107,6 +110,7 @@ pub(crate) struct CsmRenderContext<'a, 'c> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub render_flags: RenderFlags,
 }
 
 impl CsmRenderer {
@@ -114,15 +118,14 @@ impl CsmRenderer {
         state: &PipelineState,
         size: usize,
         precision: ShadowMapPrecision,
+        cascade_count: usize,
     ) -> Result<Self, FrameworkError> {
         Ok(Self {
             precision,
             size,
-            cascades: [
-                Cascade::new(state, size, precision)?,
-                Cascade::new(state, size, precision)?,
-                Cascade::new(state, size, precision)?,
-            ],
+            cascades: (0..cascade_count.clamp(1, CSM_NUM_CASCADES))
+                .map(|_| Cascade::new(state, size, precision))
+                .collect::<Result<Vec<_>, _>>()?,
         })
     }
 
@@ -134,6 +137,10 @@ impl CsmRenderer {
         self.size
     }
 
+    pub fn cascade_count(&self) -> usize {
+        self.cascades.len()
+    }
+
     pub fn cascades(&self) -> &[Cascade] {
         &self.cascades
     }
@@ -158,6 +165,7 @@ impl CsmRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            render_flags,
         } = ctx;
 
         let light_direction = -light
@@ -185,9 +193,15 @@ impl CsmRenderer {
             ],
         };
 
-        for i in 0..CSM_NUM_CASCADES {
+        let cascade_count = self.cascades.len();
+        for i in 0..cascade_count {
             let z_near = z_values[i];
-            let mut z_far = z_values[i + 1];
+            // The last cascade covers the rest of the frustum, if some cascades are disabled.
+            let mut z_far = if i + 1 == cascade_count {
+                z_values[CSM_NUM_CASCADES]
+            } else {
+                z_values[i + 1]
+            };
 
             if z_far.eq(&z_near) {
                 z_far += 10.0 * f32::EPSILON;
@@ -257,8 +271,13 @@ impl CsmRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix: cascade_projection_matrix,
+                    frustum_culling: true,
+                    particle_transparency: Default::default(),
+                    render_flags,
+                    lod_hysteresis: false,
                 },
                 DIRECTIONAL_SHADOW_PASS_NAME.clone(),
+                None,
             );
 
             for bundle in bundle_storage.bundles.iter() {
@@ -296,7 +315,9 @@ impl CsmRenderer {
                         viewport,
                         &render_pass.program,
                         &DrawParameters {
-                            cull_face: Some(CullFace::Back),
+                            cull_face: material
+                                .cull_mode()
+                                .map_or(Some(CullFace::Back), CullMode::cull_face),
                             color_write: ColorMask::all(false),
                             depth_write: true,
                             stencil_test: None,
@@ -316,6 +337,7 @@ impl CsmRenderer {
                                 wvp_matrix: &(light_view_projection * instance.world_transform),
                                 bone_matrices: &instance.bone_matrices,
                                 use_skeletal_animation: bundle.is_skinned,
+                                instance_matrices: &[],
                                 camera_position: &camera.global_position(),
                                 camera_up_vector: &camera_up,
                                 camera_side_vector: &camera_side,
@@ -326,11 +348,13 @@ impl CsmRenderer {
                                 blend_shapes_weights: &instance.blend_shapes_weights,
                                 normal_dummy: &normal_dummy,
                                 white_dummy: &white_dummy,
+                                diffuse_dummy: &white_dummy,
                                 black_dummy: &black_dummy,
                                 volume_dummy: &volume_dummy,
                                 persistent_identifier: instance.persistent_identifier,
                                 light_data: None,            // TODO
                                 ambient_light: Color::WHITE, // TODO
+                                soft_particle_scale: 1.0,
                                 scene_depth: None,
                                 z_far,
                             });
@@ -20,7 +20,7 @@ use crate::{
         },
         shadow::cascade_size,
         storage::MatrixStorageCache,
-        GeometryCache, MaterialContext, RenderPassStatistics, ShadowMapPrecision,
+        GeometryCache, MaterialContext, RenderFlags, RenderPassStatistics, ShadowMapPrecision,
         POINT_SHADOW_PASS_NAME,
     },
     scene::graph::Graph,
@@ -55,6 +55,7 @@ pub(crate) struct PointShadowMapRenderContext<'a> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub render_flags: RenderFlags,
 }
 
 impl PointShadowMapRenderer {
@@ -208,6 +209,7 @@ impl PointShadowMapRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            render_flags,
         } = args;
 
         let framebuffer = &mut self.cascades[cascade];
@@ -249,8 +251,13 @@ impl PointShadowMapRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix: light_projection_matrix,
+                    frustum_culling: true,
+                    particle_transparency: Default::default(),
+                    render_flags,
+                    lod_hysteresis: false,
                 },
                 POINT_SHADOW_PASS_NAME.clone(),
+                None,
             );
 
             for bundle in bundle_storage.bundles.iter() {
@@ -283,7 +290,7 @@ impl PointShadowMapRenderer {
                         state,
                         viewport,
                         &render_pass.program,
-                        &render_pass.draw_params,
+                        &material.draw_parameters(&render_pass.draw_params),
                         instance.element_range,
                         |mut program_binding| {
                             apply_material(MaterialContext {
@@ -297,6 +304,7 @@ impl PointShadowMapRenderer {
                                     * instance.world_transform),
                                 bone_matrices: &instance.bone_matrices,
                                 use_skeletal_animation: bundle.is_skinned,
+                                instance_matrices: &[],
                                 camera_position: &Default::default(),
                                 camera_up_vector: &camera_up,
                                 camera_side_vector: &camera_side,
@@ -307,11 +315,13 @@ impl PointShadowMapRenderer {
                                 blend_shapes_weights: &instance.blend_shapes_weights,
                                 normal_dummy: &normal_dummy,
                                 white_dummy: &white_dummy,
+                                diffuse_dummy: &white_dummy,
                                 black_dummy: &black_dummy,
                                 volume_dummy: &volume_dummy,
                                 persistent_identifier: instance.persistent_identifier,
                                 light_data: None,            // TODO
                                 ambient_light: Color::WHITE, // TODO
+                                soft_particle_scale: 1.0,
                                 scene_depth: None,
                                 z_far,
                             });
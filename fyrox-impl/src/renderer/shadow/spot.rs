@@ -5,6 +5,7 @@ use crate::{
         math::Rect,
         scope_profile,
     },
+    material::CullMode,
     renderer::{
         apply_material,
         bundle::{ObserverInfo, RenderDataBundleStorage},
@@ -20,7 +21,7 @@ use crate::{
         },
         shadow::cascade_size,
         storage::MatrixStorageCache,
-        GeometryCache, MaterialContext, RenderPassStatistics, ShadowMapPrecision,
+        GeometryCache, MaterialContext, RenderFlags, RenderPassStatistics, ShadowMapPrecision,
         SPOT_SHADOW_PASS_NAME,
     },
     scene::graph::Graph,
@@ -134,6 +135,7 @@ impl SpotShadowMapRenderer {
         black_dummy: Rc<RefCell<GpuTexture>>,
         volume_dummy: Rc<RefCell<GpuTexture>>,
         matrix_storage: &mut MatrixStorageCache,
+        render_flags: RenderFlags,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
@@ -155,8 +157,13 @@ impl SpotShadowMapRenderer {
                 z_far,
                 view_matrix: light_view_matrix,
                 projection_matrix: light_projection_matrix,
+                frustum_culling: true,
+                particle_transparency: Default::default(),
+                render_flags,
+                lod_hysteresis: false,
             },
             SPOT_SHADOW_PASS_NAME.clone(),
+            None,
         );
 
         let inv_view = light_view_matrix.try_inverse().unwrap();
@@ -194,7 +201,9 @@ impl SpotShadowMapRenderer {
                     viewport,
                     &render_pass.program,
                     &DrawParameters {
-                        cull_face: Some(CullFace::Back),
+                        cull_face: material
+                            .cull_mode()
+                            .map_or(Some(CullFace::Back), CullMode::cull_face),
                         color_write: ColorMask::all(false),
                         depth_write: true,
                         stencil_test: None,
@@ -214,6 +223,7 @@ impl SpotShadowMapRenderer {
                             wvp_matrix: &(light_view_projection * instance.world_transform),
                             bone_matrices: &instance.bone_matrices,
                             use_skeletal_animation: bundle.is_skinned,
+                            instance_matrices: &[],
                             camera_position: &Default::default(),
                             camera_up_vector: &camera_up,
                             camera_side_vector: &camera_side,
@@ -224,11 +234,13 @@ impl SpotShadowMapRenderer {
                             blend_shapes_weights: &instance.blend_shapes_weights,
                             normal_dummy: &normal_dummy,
                             white_dummy: &white_dummy,
+                            diffuse_dummy: &white_dummy,
                             black_dummy: &black_dummy,
                             volume_dummy: &volume_dummy,
                             persistent_identifier: instance.persistent_identifier,
                             light_data: None,            // TODO
                             ambient_light: Color::WHITE, // TODO
+                            soft_particle_scale: 1.0,
                             scene_depth: None,
                             z_far,
                         });
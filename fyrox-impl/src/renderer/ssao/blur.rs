@@ -89,6 +89,18 @@ impl Blur {
         })
     }
 
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<(), FrameworkError> {
+        self.framebuffer.resize(state, width, height)?;
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
     pub fn result(&self) -> Rc<RefCell<GpuTexture>> {
         self.framebuffer.color_attachments()[0].texture.clone()
     }
@@ -42,6 +42,8 @@ struct Shader {
     normal_sampler: UniformLocation,
     noise_sampler: UniformLocation,
     radius: UniformLocation,
+    bias: UniformLocation,
+    sample_count: UniformLocation,
     kernel: UniformLocation,
     projection_matrix: UniformLocation,
     noise_scale: UniformLocation,
@@ -64,6 +66,8 @@ impl Shader {
                 .uniform_location(state, &ImmutableString::new("noiseSampler"))?,
             kernel: program.uniform_location(state, &ImmutableString::new("kernel"))?,
             radius: program.uniform_location(state, &ImmutableString::new("radius"))?,
+            bias: program.uniform_location(state, &ImmutableString::new("bias"))?,
+            sample_count: program.uniform_location(state, &ImmutableString::new("sampleCount"))?,
             projection_matrix: program
                 .uniform_location(state, &ImmutableString::new("projectionMatrix"))?,
             inv_proj_matrix: program
@@ -87,6 +91,8 @@ pub struct ScreenSpaceAmbientOcclusionRenderer {
     noise: Rc<RefCell<GpuTexture>>,
     kernel: [Vector3<f32>; KERNEL_SIZE],
     radius: f32,
+    bias: f32,
+    sample_count: usize,
 }
 
 impl ScreenSpaceAmbientOcclusionRenderer {
@@ -184,13 +190,40 @@ impl ScreenSpaceAmbientOcclusionRenderer {
                 texture
             })),
             radius: 0.5,
+            bias: 0.04,
+            sample_count: KERNEL_SIZE,
         })
     }
 
+    pub fn resize(
+        &mut self,
+        state: &PipelineState,
+        frame_width: usize,
+        frame_height: usize,
+    ) -> Result<(), FrameworkError> {
+        let width = (frame_width / 2).max(1);
+        let height = (frame_height / 2).max(1);
+        self.framebuffer.resize(state, width, height)?;
+        self.blur.resize(state, width, height)?;
+        self.width = width as i32;
+        self.height = height as i32;
+        Ok(())
+    }
+
     pub fn set_radius(&mut self, radius: f32) {
         self.radius = radius.abs();
     }
 
+    /// Sets a depth bias, that is used to prevent self-occlusion artifacts on flat surfaces.
+    pub fn set_bias(&mut self, bias: f32) {
+        self.bias = bias.abs();
+    }
+
+    /// Sets an amount of samples per pixel. It is clamped to `[1; 32]` range.
+    pub fn set_sample_count(&mut self, sample_count: usize) {
+        self.sample_count = sample_count.clamp(1, KERNEL_SIZE);
+    }
+
     fn raw_ao_map(&self) -> Rc<RefCell<GpuTexture>> {
         self.framebuffer.color_attachments()[0].texture.clone()
     }
@@ -241,6 +274,8 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             self.height as f32 / NOISE_SIZE as f32,
         );
         let radius = self.radius;
+        let bias = self.bias;
+        let sample_count = self.sample_count as i32;
         stats += self.framebuffer.draw(
             &self.quad,
             state,
@@ -264,6 +299,8 @@ impl ScreenSpaceAmbientOcclusionRenderer {
                     .set_vector3_slice(&shader.kernel, kernel)
                     .set_vector2(&shader.noise_scale, &noise_scale)
                     .set_f32(&shader.radius, radius)
+                    .set_f32(&shader.bias, bias)
+                    .set_i32(&shader.sample_count, sample_count)
                     .set_matrix4(&shader.world_view_proj_matrix, &frame_matrix)
                     .set_matrix4(&shader.projection_matrix, &projection_matrix)
                     .set_matrix4(
@@ -274,7 +311,7 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             },
         )?;
 
-        self.blur.render(state, self.raw_ao_map())?;
+        stats += self.blur.render(state, self.raw_ao_map())?;
 
         Ok(stats)
     }
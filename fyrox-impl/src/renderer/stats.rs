@@ -83,6 +83,9 @@ pub struct LightingStatistics {
     pub spot_shadow_maps_rendered: usize,
     /// How many directional lights were rendered.
     pub directional_lights_rendered: usize,
+    /// Total amount of light evaluations per screen tile, that is sum of affected tiles of every
+    /// rendered light. See [`crate::renderer::QualitySettings::use_light_grid`] for more info.
+    pub light_tiles_shaded: usize,
 }
 
 impl AddAssign for LightingStatistics {
@@ -93,6 +96,7 @@ impl AddAssign for LightingStatistics {
         self.spot_shadow_maps_rendered += rhs.spot_shadow_maps_rendered;
         self.directional_lights_rendered += rhs.directional_lights_rendered;
         self.csm_rendered += rhs.csm_rendered;
+        self.light_tiles_shaded += rhs.light_tiles_shaded;
     }
 }
 
@@ -106,13 +110,15 @@ impl Display for LightingStatistics {
             \tDirectional Lights: {}\n\
             \tPoint Shadow Maps: {}\n\
             \tSpot Shadow Maps: {}\n\
-            \tSpot Shadow Maps: {}\n",
+            \tSpot Shadow Maps: {}\n\
+            \tShaded Light Tiles: {}\n",
             self.point_lights_rendered,
             self.spot_lights_rendered,
             self.directional_lights_rendered,
             self.point_shadow_maps_rendered,
             self.spot_shadow_maps_rendered,
-            self.csm_rendered
+            self.csm_rendered,
+            self.light_tiles_shaded
         )
     }
 }
@@ -126,6 +132,10 @@ pub struct SceneStatistics {
     pub lighting: LightingStatistics,
     /// Shows how many draw calls was made and how many triangles were rendered.
     pub geometry: RenderPassStatistics,
+    /// Shows how many scene nodes were culled by frustum culling.
+    pub culling: CullingStatistics,
+    /// Total amount of alive particles of all particle systems of the scene.
+    pub particles: usize,
 }
 
 impl Display for SceneStatistics {
@@ -134,12 +144,20 @@ impl Display for SceneStatistics {
             f,
             "{}\n\
             {}\n\
-            {}\n",
-            self.geometry, self.lighting, self.pipeline
+            {}\n\
+            {}\n\
+            Particles: {}\n",
+            self.geometry, self.lighting, self.culling, self.pipeline, self.particles
         )
     }
 }
 
+impl AddAssign<CullingStatistics> for SceneStatistics {
+    fn add_assign(&mut self, rhs: CullingStatistics) {
+        self.culling += rhs;
+    }
+}
+
 impl AddAssign<DrawCallStatistics> for SceneStatistics {
     fn add_assign(&mut self, rhs: DrawCallStatistics) {
         self.geometry += rhs;
@@ -164,6 +182,47 @@ impl AddAssign<LightingStatistics> for SceneStatistics {
     }
 }
 
+/// A group of render passes, that is measured by GPU timers (see
+/// [`super::Renderer::set_gpu_timers_enabled`]). Use it as an index of [`Statistics::pass_times`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GpuPass {
+    /// Filling of the G-Buffer (opaque geometry and decals).
+    GBuffer = 0,
+    /// Deferred lighting, including shadow maps, SSAO and skybox.
+    Lighting = 1,
+    /// Forward rendering of transparent geometry, particle systems and sprites.
+    Forward = 2,
+    /// Bloom, tone mapping and anti-aliasing.
+    PostProcessing = 3,
+    /// User interface.
+    Ui = 4,
+}
+
+impl GpuPass {
+    /// Total amount of the measured pass groups.
+    pub const COUNT: usize = 5;
+
+    /// All the pass groups in order of their indices.
+    pub const ALL: [GpuPass; Self::COUNT] = [
+        GpuPass::GBuffer,
+        GpuPass::Lighting,
+        GpuPass::Forward,
+        GpuPass::PostProcessing,
+        GpuPass::Ui,
+    ];
+
+    /// Returns a human-readable name of the pass group.
+    pub fn name(self) -> &'static str {
+        match self {
+            GpuPass::GBuffer => "G-Buffer",
+            GpuPass::Lighting => "Lighting",
+            GpuPass::Forward => "Forward",
+            GpuPass::PostProcessing => "Post Processing",
+            GpuPass::Ui => "UI",
+        }
+    }
+}
+
 /// Renderer statistics for one frame, also includes current frames per second
 /// amount.
 #[derive(Debug, Copy, Clone)]
@@ -174,14 +233,44 @@ pub struct Statistics {
     pub lighting: LightingStatistics,
     /// Shows how many draw calls was made and how many triangles were rendered.
     pub geometry: RenderPassStatistics,
+    /// Shows how many scene nodes were culled by frustum culling.
+    pub culling: CullingStatistics,
+    /// Total amount of alive particles of all particle systems of all rendered scenes.
+    pub particles: usize,
     /// Real time consumed to render frame. Time given in **seconds**.
     pub pure_frame_time: f32,
+    /// Exponential moving average of [`Self::pure_frame_time`], it changes smoothly and does not
+    /// jump from frame to frame, which makes it suitable for on-screen overlays. Time given in
+    /// **seconds**.
+    pub avg_frame_time: f32,
     /// Total time renderer took to process single frame, usually includes
     /// time renderer spend to wait to buffers swap (can include vsync).
     /// Time given in **seconds**.
     pub capped_frame_time: f32,
+    /// Time between the starts of two last consecutive frames, it includes the time that was spent outside
+    /// of the renderer (game logic, physics, etc.). Time given in **seconds**.
+    pub frame_delta: f32,
     /// Total amount of frames been rendered in one second.
     pub frames_per_second: usize,
+    /// Estimated amount of GPU memory (in bytes) freed by [`super::Renderer::evict_unused_textures`]
+    /// over the whole lifetime of the renderer.
+    pub evicted_texture_memory: usize,
+    /// Estimated amount of GPU memory (in bytes) occupied by all textures, including render targets.
+    pub texture_memory: usize,
+    /// Estimated amount of GPU memory (in bytes) occupied by all vertex and index buffers.
+    pub geometry_memory: usize,
+    /// Amount of unique geometry buffers of surfaces resident in GPU memory. Surfaces, that use the
+    /// same surface data resource, share a single geometry buffer.
+    pub geometry_buffers: usize,
+    /// `true` if the renderer is paused (see [`super::Renderer::set_paused`]). Frames are still
+    /// presented while paused, so the frame rate stays valid, but the scenes are not re-rendered
+    /// and geometry, lighting and culling statistics contain only the cost of the presentation.
+    pub paused: bool,
+    /// Time (in **seconds**) spent by GPU on every group of passes (see [`GpuPass`]) of all scenes.
+    /// The values are measured only if GPU timers are enabled (see
+    /// [`super::Renderer::set_gpu_timers_enabled`]), otherwise they are zero. GPU works
+    /// asynchronously, so the values are a few frames late.
+    pub pass_times: [f32; GpuPass::COUNT],
     pub(super) frame_counter: usize,
     pub(super) frame_start_time: instant::Instant,
     pub(super) last_fps_commit_time: instant::Instant,
@@ -192,6 +281,8 @@ impl std::ops::AddAssign<SceneStatistics> for Statistics {
         self.pipeline += rhs.pipeline;
         self.lighting += rhs.lighting;
         self.geometry += rhs.geometry;
+        self.culling += rhs.culling;
+        self.particles += rhs.particles;
     }
 }
 
@@ -199,22 +290,83 @@ impl Display for Statistics {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "FPS: {}\n\
+            "FPS: {}{}\n\
             Pure Frame Time: {:.2} ms\n\
+            Average Frame Time: {:.2} ms\n\
             Capped Frame Time: {:.2} ms\n\
+            Frame Delta: {:.2} ms\n\
+            Texture Memory: {:.2} MiB\n\
+            Geometry Memory: {:.2} MiB\n\
+            Geometry Buffers: {}\n\
+            Particles: {}\n\
+            {}\n\
             {}\n\
             {}\n\
             {}\n",
             self.frames_per_second,
+            if self.paused { " (Paused)" } else { "" },
             self.pure_frame_time * 1000.0,
+            self.avg_frame_time * 1000.0,
             self.capped_frame_time * 1000.0,
+            self.frame_delta * 1000.0,
+            self.texture_memory as f32 / (1024.0 * 1024.0),
+            self.geometry_memory as f32 / (1024.0 * 1024.0),
+            self.geometry_buffers,
+            self.particles,
             self.geometry,
             self.lighting,
+            self.culling,
             self.pipeline
+        )?;
+        if self.pass_times.iter().any(|time| *time > 0.0) {
+            write!(f, "GPU pass times:")?;
+            for pass in GpuPass::ALL {
+                write!(
+                    f,
+                    "\n\t{}: {:.2} ms",
+                    pass.name(),
+                    self.pass_times[pass as usize] * 1000.0
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Frustum culling statistics. Only nodes that are visible, enabled, have frustum culling enabled and
+/// could produce render data (meshes, sprites, etc.) are counted.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CullingStatistics {
+    /// Amount of nodes that are (at least partially) inside the frustum of an observer.
+    pub passed_nodes: usize,
+    /// Amount of nodes that are fully outside the frustum of an observer and were skipped.
+    pub culled_nodes: usize,
+    /// Amount of particles, that were not rendered because of the particle budget (see
+    /// [`super::Renderer::set_particle_budget`]).
+    pub culled_particles: usize,
+}
+
+impl Display for CullingStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Frustum Culling:\n\
+            \tPassed Nodes: {}\n\
+            \tCulled Nodes: {}\n\
+            \tCulled Particles: {}",
+            self.passed_nodes, self.culled_nodes, self.culled_particles
         )
     }
 }
 
+impl AddAssign for CullingStatistics {
+    fn add_assign(&mut self, rhs: Self) {
+        self.passed_nodes += rhs.passed_nodes;
+        self.culled_nodes += rhs.culled_nodes;
+        self.culled_particles += rhs.culled_particles;
+    }
+}
+
 /// GPU statistics for single frame.
 #[derive(Debug, Copy, Clone, Default)]
 pub struct RenderPassStatistics {
@@ -12,6 +12,7 @@ use crate::{
     gui::{
         brush::Brush,
         draw::{CommandTexture, DrawingContext},
+        font::FontRenderMode,
     },
     renderer::{
         framework::{
@@ -42,6 +43,7 @@ struct UiShader {
     wvp_matrix: UniformLocation,
     diffuse_texture: UniformLocation,
     is_font: UniformLocation,
+    is_sdf_font: UniformLocation,
     solid_color: UniformLocation,
     brush_type: UniformLocation,
     gradient_point_count: UniformLocation,
@@ -66,6 +68,7 @@ impl UiShader {
             diffuse_texture: program
                 .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
             is_font: program.uniform_location(state, &ImmutableString::new("isFont"))?,
+            is_sdf_font: program.uniform_location(state, &ImmutableString::new("isSdfFont"))?,
             solid_color: program.uniform_location(state, &ImmutableString::new("solidColor"))?,
             brush_type: program.uniform_location(state, &ImmutableString::new("brushType"))?,
             gradient_point_count: program
@@ -101,9 +104,11 @@ pub struct UiRenderContext<'a, 'b, 'c> {
     pub viewport: Rect<i32>,
     /// Frame buffer to where render the user interface.
     pub frame_buffer: &'b mut FrameBuffer,
-    /// Width of the frame buffer to where render the user interface.
+    /// Width of the user interface in its own units. If it is different from the width of the
+    /// viewport, the user interface will be scaled to fit the viewport.
     pub frame_width: f32,
-    /// Height of the frame buffer to where render the user interface.
+    /// Height of the user interface in its own units. If it is different from the height of the
+    /// viewport, the user interface will be scaled to fit the viewport.
     pub frame_height: f32,
     /// Drawing context of a user interface.
     pub drawing_context: &'c DrawingContext,
@@ -192,19 +197,29 @@ impl UiRenderer {
         geometry_buffer.set_triangles(drawing_context.get_triangles());
 
         let ortho = Matrix4::new_orthographic(0.0, frame_width, frame_height, 0.0, -1.0, 1.0);
-        let resolution = Vector2::new(frame_width, frame_height);
+        let resolution = Vector2::new(viewport.w() as f32, viewport.h() as f32);
+        // Scale from user interface units to pixels of the viewport. Commands are stored in user
+        // interface units, while scissor test and fragment coordinates are in pixels.
+        let scale = Vector2::new(resolution.x / frame_width, resolution.y / frame_height);
 
         state.set_scissor_test(true);
 
         for cmd in drawing_context.get_commands() {
             let mut diffuse_texture = &white_dummy;
             let mut is_font_texture = false;
+            let mut is_sdf_font = false;
 
             let mut clip_bounds = cmd.clip_bounds;
-            clip_bounds.position.x = clip_bounds.position.x.floor();
-            clip_bounds.position.y = clip_bounds.position.y.floor();
-            clip_bounds.size.x = clip_bounds.size.x.ceil();
-            clip_bounds.size.y = clip_bounds.size.y.ceil();
+            clip_bounds.position.x = (clip_bounds.position.x * scale.x).floor();
+            clip_bounds.position.y = (clip_bounds.position.y * scale.y).floor();
+            clip_bounds.size.x = (clip_bounds.size.x * scale.x).ceil();
+            clip_bounds.size.y = (clip_bounds.size.y * scale.y).ceil();
+
+            // Command is completely clipped away (for example by nested clip rectangles of the
+            // drawing context), there's no need to draw it at all.
+            if clip_bounds.size.x <= 0.0 || clip_bounds.size.y <= 0.0 {
+                continue;
+            }
 
             state.set_scissor_box(
                 clip_bounds.position.x as i32,
@@ -269,6 +284,7 @@ impl UiRenderer {
                     height,
                 } => {
                     if let Some(font) = font.state().data() {
+                        is_sdf_font = font.render_mode() == FontRenderMode::Sdf;
                         let page_size = font.page_size() as u32;
                         if let Some(page) = font
                             .atlases
@@ -318,7 +334,8 @@ impl UiRenderer {
 
             let mut raw_stops = [0.0; 16];
             let mut raw_colors = [Vector4::default(); 16];
-            let bounds_max = cmd.bounds.right_bottom_corner();
+            let bounds_min = cmd.bounds.position.component_mul(&scale);
+            let bounds_max = cmd.bounds.right_bottom_corner().component_mul(&scale);
 
             let (gradient_origin, gradient_end) = match cmd.brush {
                 Brush::Solid(_) => (Vector2::default(), Vector2::default()),
@@ -355,9 +372,10 @@ impl UiRenderer {
                         .set_texture(&shader.diffuse_texture, diffuse_texture)
                         .set_matrix4(&shader.wvp_matrix, &ortho)
                         .set_vector2(&shader.resolution, &resolution)
-                        .set_vector2(&shader.bounds_min, &cmd.bounds.position)
+                        .set_vector2(&shader.bounds_min, &bounds_min)
                         .set_vector2(&shader.bounds_max, &bounds_max)
                         .set_bool(&shader.is_font, is_font_texture)
+                        .set_bool(&shader.is_sdf_font, is_sdf_font)
                         .set_i32(
                             &shader.brush_type,
                             match cmd.brush {
@@ -12,7 +12,7 @@ use crate::{
         texture::{Texture, TextureError, TextureImportOptions, TextureResource},
     },
 };
-use gltf::{buffer::View, image, Document};
+use gltf::{buffer::View, image, material::AlphaMode, Document};
 use lazy_static::lazy_static;
 
 use super::uri;
@@ -167,6 +167,7 @@ async fn import_material(
             tex.texture().index(),
             SamplerFallback::Normal,
         )?;
+        set_material_scalar(&mut result, "normalScale", tex.scale())?;
     }
     if let Some(tex) = pbr.metallic_roughness_texture() {
         set_texture(
@@ -203,6 +204,17 @@ async fn import_material(
     set_material_vector3(&mut result, "emissionStrength", mat.emissive_factor())?;
     set_material_scalar(&mut result, "metallicFactor", pbr.metallic_factor())?;
     set_material_scalar(&mut result, "roughnessFactor", pbr.roughness_factor())?;
+    match mat.alpha_mode() {
+        // Alpha is ignored by opaque materials.
+        AlphaMode::Opaque => set_material_scalar(&mut result, "alphaTestThreshold", 0.0)?,
+        AlphaMode::Mask => set_material_scalar(
+            &mut result,
+            "alphaTestThreshold",
+            mat.alpha_cutoff().unwrap_or(0.5),
+        )?,
+        // There's no blending in the shader, keep the default threshold.
+        AlphaMode::Blend => (),
+    }
     Ok(Resource::new_ok(ResourceKind::Embedded, result))
 }
 
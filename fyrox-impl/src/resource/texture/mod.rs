@@ -32,7 +32,7 @@ use crate::{
         TypeUuidProvider,
     },
 };
-use ddsfile::{Caps2, D3DFormat};
+use ddsfile::{Caps2, D3DFormat, DxgiFormat};
 use fast_image_resize as fr;
 use fast_image_resize::ResizeOptions;
 use fxhash::FxHasher;
@@ -256,6 +256,8 @@ pub struct Texture {
     t_wrap_mode: TextureWrapMode,
     mip_count: u32,
     anisotropy: f32,
+    color_space: TextureColorSpace,
+    mip_map_mode: MipMapMode,
     modifications_counter: u64,
     is_render_target: bool,
     #[doc(hidden)]
@@ -347,6 +349,8 @@ impl Visit for Texture {
         self.t_wrap_mode.visit("TWrapMode", &mut region)?;
         self.mip_count.visit("MipCount", &mut region)?;
         self.kind.visit("Kind", &mut region)?;
+        let _ = self.color_space.visit("ColorSpace", &mut region);
+        let _ = self.mip_map_mode.visit("MipMapMode", &mut region);
         let mut bytes_view = PodVecView::from_pod_vec(&mut self.bytes);
         let _ = bytes_view.visit("Data", &mut region);
 
@@ -372,6 +376,8 @@ impl Default for Texture {
             t_wrap_mode: TextureWrapMode::Repeat,
             mip_count: 1,
             anisotropy: 16.0,
+            color_space: Default::default(),
+            mip_map_mode: Default::default(),
             modifications_counter: 0,
             is_render_target: false,
             cache_index: Default::default(),
@@ -403,6 +409,49 @@ pub enum MipFilter {
 
 uuid_provider!(MipFilter = "8fa17c0e-6889-4540-b396-97db4dc952aa");
 
+/// Defines where mip levels of a texture come from.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum MipMapMode {
+    /// The texture has only the base level. Minification filters, that use mip mapping, will sample the
+    /// base level only. Could be useful for pixel-art textures with nearest filtering.
+    None,
+    /// Mip levels are generated at import using [`MipFilter`], if minification filter of the texture uses
+    /// mip mapping. Textures with just one level of data (procedural textures, for example) have their mip
+    /// levels generated by GPU. This is default mode.
+    #[default]
+    Generate,
+    /// Mip levels are taken from the texture data as is and nothing is generated. The value defines the max
+    /// amount of mip levels to use. Could be useful for DDS textures with precomputed mip levels.
+    Provided(u32),
+}
+
+uuid_provider!(MipMapMode = "3c7f5a90-51e4-4b0e-9f3d-2a8e6c1b7d45");
+
+impl MipMapMode {
+    /// Returns the amount of mip levels, that should be used for a texture with `mip_count` levels of data.
+    pub fn mip_count(self, mip_count: u32) -> u32 {
+        match self {
+            MipMapMode::None => 1,
+            MipMapMode::Generate => mip_count,
+            MipMapMode::Provided(levels) => mip_count.min(levels.max(1)),
+        }
+    }
+}
+
 impl MipFilter {
     fn into_filter_type(self) -> fr::FilterType {
         match self {
@@ -430,6 +479,7 @@ impl MipFilter {
 ///     t_wrap_mode: ClampToEdge,
 ///     anisotropy: 8.0,
 ///     compression: NoCompression,
+///     mip_map_mode: Generate,
 /// )
 /// ```
 #[derive(Clone, Deserialize, Serialize, Debug, Reflect)]
@@ -450,6 +500,10 @@ pub struct TextureImportOptions {
     pub(crate) mip_filter: MipFilter,
     #[serde(default)]
     pub(crate) flip_green_channel: bool,
+    #[serde(default)]
+    pub(crate) color_space: TextureColorSpace,
+    #[serde(default)]
+    pub(crate) mip_map_mode: MipMapMode,
 }
 
 impl Default for TextureImportOptions {
@@ -463,6 +517,8 @@ impl Default for TextureImportOptions {
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
             flip_green_channel: false,
+            color_space: Default::default(),
+            mip_map_mode: Default::default(),
         }
     }
 }
@@ -551,6 +607,28 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Sets desired color space of texture data. See [`TextureColorSpace`] docs for more info.
+    pub fn with_color_space(mut self, color_space: TextureColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Sets desired color space of texture data. See [`TextureColorSpace`] docs for more info.
+    pub fn set_color_space(&mut self, color_space: TextureColorSpace) {
+        self.color_space = color_space;
+    }
+
+    /// Sets desired mip map mode. See [`MipMapMode`] docs for more info.
+    pub fn with_mip_map_mode(mut self, mip_map_mode: MipMapMode) -> Self {
+        self.mip_map_mode = mip_map_mode;
+        self
+    }
+
+    /// Sets desired mip map mode. See [`MipMapMode`] docs for more info.
+    pub fn set_mip_map_mode(&mut self, mip_map_mode: MipMapMode) {
+        self.mip_map_mode = mip_map_mode;
+    }
 }
 
 lazy_static! {
@@ -625,6 +703,8 @@ impl TextureResourceExtension for TextureResource {
                 t_wrap_mode: TextureWrapMode::Repeat,
                 mip_count: 1,
                 anisotropy: 1.0,
+                color_space: TextureColorSpace::Linear,
+                mip_map_mode: MipMapMode::None,
                 modifications_counter: 0,
                 is_render_target: true,
                 cache_index: Default::default(),
@@ -821,6 +901,46 @@ impl Default for TextureWrapMode {
     }
 }
 
+/// Color space of texture data. It defines whether the GPU should convert texture data from sRGB to linear
+/// color space when the texture is sampled in a shader.
+///
+/// # Notes
+///
+/// Built-in shaders support both color spaces for diffuse textures, custom shaders could use the
+/// `fyrox_diffuseTextureSrgb` built-in uniform to do the same. Data textures (normal maps, roughness maps,
+/// etc.) must always be [`TextureColorSpace::Linear`].
+/// The conversion is done only for textures with `RGB8` and `RGBA8` pixel kinds, other kinds are always
+/// treated as linear.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialOrd,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    Reflect,
+    VariantNames,
+    EnumString,
+    AsRefStr,
+    Visit,
+    Eq,
+)]
+#[repr(u32)]
+pub enum TextureColorSpace {
+    /// Texture data is passed to shaders as is.
+    #[default]
+    Linear = 0,
+
+    /// Texture data is in sRGB color space and will be converted into linear color space by the GPU
+    /// when sampled.
+    Srgb = 1,
+}
+
+uuid_provider!(TextureColorSpace = "0b4d1d2e-8a5c-4e6b-9b1f-6f2d7c3a9e41");
+
 /// Texture kind defines pixel format of texture.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect)]
 #[repr(u32)]
@@ -1309,57 +1429,91 @@ impl Texture {
         // DDS is special. It can contain various kinds of textures as well as textures with
         // various pixel formats.
         //
-        // TODO: Add support for DXGI formats.
+        // Compressed formats are passed as-is (including the whole mip chain), so they will stay
+        // compressed in video memory.
         if let Ok(dds) = ddsfile::Dds::read(&mut Cursor::new(data)) {
-            let d3dformat = dds
-                .get_d3d_format()
-                .ok_or(TextureError::UnsupportedFormat)?;
             let mip_count = dds.get_num_mipmap_levels();
+            let d3dformat = dds.get_d3d_format();
+            let dxgi_format = dds.get_dxgi_format();
             let mut bytes = dds.data;
 
             // Try to use as much formats as possible.
-            let pixel_kind = match d3dformat {
-                D3DFormat::DXT1 => TexturePixelKind::DXT1RGBA,
-                D3DFormat::DXT3 => TexturePixelKind::DXT3RGBA,
-                D3DFormat::DXT5 => TexturePixelKind::DXT5RGBA,
-                D3DFormat::L8 | D3DFormat::A8 => TexturePixelKind::R8,
-                D3DFormat::L16 => TexturePixelKind::R16,
-                D3DFormat::R8G8B8 => TexturePixelKind::RGB8,
-                D3DFormat::A8L8 => TexturePixelKind::RG8,
-                D3DFormat::A8R8G8B8 => {
-                    // // ARGB8 -> RGBA8
-                    // assert_eq!(bytes.len() % 4, 0);
-                    // for chunk in bytes.chunks_exact_mut(4) {
-                    //     let a = chunk[0];
-                    //     let r = chunk[1];
-                    //     let g = chunk[2];
-                    //     let b = chunk[3];
-                    //     chunk[0] = r;
-                    //     chunk[1] = g;
-                    //     chunk[2] = b;
-                    //     chunk[3] = a;
-                    // }
-                    TexturePixelKind::RGBA8
+            let pixel_kind = if let Some(d3dformat) = d3dformat {
+                match d3dformat {
+                    D3DFormat::DXT1 => TexturePixelKind::DXT1RGBA,
+                    D3DFormat::DXT3 => TexturePixelKind::DXT3RGBA,
+                    D3DFormat::DXT5 => TexturePixelKind::DXT5RGBA,
+                    D3DFormat::L8 | D3DFormat::A8 => TexturePixelKind::R8,
+                    D3DFormat::L16 => TexturePixelKind::R16,
+                    D3DFormat::R8G8B8 => TexturePixelKind::RGB8,
+                    D3DFormat::A8L8 => TexturePixelKind::RG8,
+                    D3DFormat::A8R8G8B8 => {
+                        // // ARGB8 -> RGBA8
+                        // assert_eq!(bytes.len() % 4, 0);
+                        // for chunk in bytes.chunks_exact_mut(4) {
+                        //     let a = chunk[0];
+                        //     let r = chunk[1];
+                        //     let g = chunk[2];
+                        //     let b = chunk[3];
+                        //     chunk[0] = r;
+                        //     chunk[1] = g;
+                        //     chunk[2] = b;
+                        //     chunk[3] = a;
+                        // }
+                        TexturePixelKind::RGBA8
+                    }
+                    D3DFormat::G16R16 => {
+                        // GR16 -> RG16
+                        assert_eq!(bytes.len() % 4, 0);
+                        for chunk in bytes.chunks_exact_mut(4) {
+                            // Red Hi + Lo bytes
+                            let gh = chunk[0];
+                            let gl = chunk[1];
+                            // Green Hi + Lo bytes
+                            let rh = chunk[2];
+                            let rl = chunk[3];
+                            // Swap
+                            chunk[0] = rh;
+                            chunk[1] = rl;
+                            chunk[2] = gh;
+                            chunk[3] = gl;
+                        }
+                        TexturePixelKind::RG16
+                    }
+                    _ => return Err(TextureError::UnsupportedFormat),
                 }
-                D3DFormat::G16R16 => {
-                    // GR16 -> RG16
-                    assert_eq!(bytes.len() % 4, 0);
-                    for chunk in bytes.chunks_exact_mut(4) {
-                        // Red Hi + Lo bytes
-                        let gh = chunk[0];
-                        let gl = chunk[1];
-                        // Green Hi + Lo bytes
-                        let rh = chunk[2];
-                        let rl = chunk[3];
-                        // Swap
-                        chunk[0] = rh;
-                        chunk[1] = rl;
-                        chunk[2] = gh;
-                        chunk[3] = gl;
+            } else if let Some(dxgi_format) = dxgi_format {
+                // Newer DDS files (with DX10 header) use DXGI formats, that are stored in
+                // a separate header.
+                match dxgi_format {
+                    DxgiFormat::BC1_UNorm | DxgiFormat::BC1_UNorm_sRGB => {
+                        TexturePixelKind::DXT1RGBA
+                    }
+                    DxgiFormat::BC2_UNorm | DxgiFormat::BC2_UNorm_sRGB => {
+                        TexturePixelKind::DXT3RGBA
+                    }
+                    DxgiFormat::BC3_UNorm | DxgiFormat::BC3_UNorm_sRGB => {
+                        TexturePixelKind::DXT5RGBA
+                    }
+                    DxgiFormat::BC4_UNorm => TexturePixelKind::R8RGTC,
+                    DxgiFormat::BC5_UNorm => TexturePixelKind::RG8RGTC,
+                    DxgiFormat::R8_UNorm => TexturePixelKind::R8,
+                    DxgiFormat::R8G8_UNorm => TexturePixelKind::RG8,
+                    DxgiFormat::R8G8B8A8_UNorm | DxgiFormat::R8G8B8A8_UNorm_sRGB => {
+                        TexturePixelKind::RGBA8
                     }
-                    TexturePixelKind::RG16
+                    DxgiFormat::B8G8R8A8_UNorm | DxgiFormat::B8G8R8A8_UNorm_sRGB => {
+                        TexturePixelKind::BGRA8
+                    }
+                    DxgiFormat::R16_UNorm => TexturePixelKind::R16,
+                    DxgiFormat::R16G16_UNorm => TexturePixelKind::RG16,
+                    DxgiFormat::R16G16B16A16_UNorm => TexturePixelKind::RGBA16,
+                    DxgiFormat::R32G32B32_Float => TexturePixelKind::RGB32F,
+                    DxgiFormat::R32G32B32A32_Float => TexturePixelKind::RGBA32F,
+                    _ => return Err(TextureError::UnsupportedFormat),
                 }
-                _ => return Err(TextureError::UnsupportedFormat),
+            } else {
+                return Err(TextureError::UnsupportedFormat);
             };
 
             Ok(Self {
@@ -1370,6 +1524,8 @@ impl Texture {
                 s_wrap_mode: import_options.s_wrap_mode,
                 t_wrap_mode: import_options.t_wrap_mode,
                 anisotropy: import_options.anisotropy,
+                color_space: import_options.color_space,
+                mip_map_mode: import_options.mip_map_mode,
                 mip_count,
                 bytes: bytes.into(),
                 kind: if dds.header.caps2 & Caps2::CUBEMAP == Caps2::CUBEMAP {
@@ -1435,7 +1591,9 @@ impl Texture {
                 width as usize * height as usize * src_pixel_kind.size_in_bytes().unwrap_or(4),
             );
 
-            if import_options.minification_filter.is_using_mip_mapping() {
+            if import_options.mip_map_mode == MipMapMode::Generate
+                && import_options.minification_filter.is_using_mip_mapping()
+            {
                 let src_pixel_type = convert_pixel_type_enum(src_pixel_kind);
                 let mut level_width = width;
                 let mut level_height = height;
@@ -1521,6 +1679,8 @@ impl Texture {
                 s_wrap_mode: import_options.s_wrap_mode,
                 t_wrap_mode: import_options.t_wrap_mode,
                 anisotropy: import_options.anisotropy,
+                color_space: import_options.color_space,
+                mip_map_mode: import_options.mip_map_mode,
                 is_render_target: false,
                 cache_index: Default::default(),
             })
@@ -1657,6 +1817,14 @@ impl Texture {
         None
     }
 
+    /// Returns data of the first `count` mip levels of the texture.
+    pub fn mip_chain_data(&self, count: usize) -> &[u8] {
+        let size = (0..count)
+            .map(|mip| bytes_in_mip_level(self.kind, self.pixel_kind, mip) as usize)
+            .sum::<usize>();
+        &self.bytes[..size.min(self.bytes.len())]
+    }
+
     /// Returns data of the given mip level.
     pub fn mip_level_data(&self, mip: usize) -> &[u8] {
         let mip_begin = mip
@@ -1704,6 +1872,34 @@ impl Texture {
         self.anisotropy
     }
 
+    /// Sets new color space of texture data. See [`TextureColorSpace`] docs for more info. Changing the
+    /// color space causes re-upload of the texture to GPU.
+    pub fn set_color_space(&mut self, color_space: TextureColorSpace) {
+        if self.color_space != color_space {
+            self.color_space = color_space;
+            self.modifications_counter += 1;
+        }
+    }
+
+    /// Returns current color space of texture data.
+    pub fn color_space(&self) -> TextureColorSpace {
+        self.color_space
+    }
+
+    /// Sets new mip map mode of the texture. See [`MipMapMode`] docs for more info. Changing the mode
+    /// causes the texture to be re-uploaded to GPU.
+    pub fn set_mip_map_mode(&mut self, mip_map_mode: MipMapMode) {
+        if self.mip_map_mode != mip_map_mode {
+            self.mip_map_mode = mip_map_mode;
+            self.modifications_counter += 1;
+        }
+    }
+
+    /// Returns current mip map mode of the texture.
+    pub fn mip_map_mode(&self) -> MipMapMode {
+        self.mip_map_mode
+    }
+
     /// Returns a special reference holder that provides mutable access to content of the
     /// texture and automatically calculates hash of the data in its destructor.
     pub fn modify(&mut self) -> TextureDataRefMut<'_> {
@@ -23,7 +23,7 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::{
     any::Any,
-    cell::Cell,
+    cell::{Cell, RefCell},
     ops::{Deref, DerefMut},
     sync::mpsc::Sender,
 };
@@ -112,6 +112,60 @@ impl LevelOfDetail {
 pub struct LodGroup {
     /// Set of cascades.
     pub levels: Vec<LevelOfDetail>,
+    /// Width of a band (in normalized distance) around the boundaries of the level of a visible
+    /// object, within which the object remains visible. It prevents flickering when an object stays
+    /// near the boundary of two levels. Hysteresis is applied only for the main camera of a scene
+    /// (see [`LodGroup::is_object_visible`]). Default is `0.0` - no hysteresis.
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub hysteresis: f32,
+    // Objects, that were visible last time for the observer with hysteresis.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    visible_objects: RefCell<Vec<Handle<Node>>>,
+}
+
+impl LodGroup {
+    /// Checks whether the given object of the level with the given index should be visible at the
+    /// given normalized distance from the observer to the object. If `use_hysteresis` is set, an
+    /// object, that was visible last time, remains visible while the distance is within the
+    /// hysteresis band around the range of its level, and the result is remembered for the next
+    /// check. Hysteresis state is not tracked per observer, so only one observer (the main camera of
+    /// a scene) should use it.
+    pub fn is_object_visible(
+        &self,
+        level_index: usize,
+        object: Handle<Node>,
+        normalized_distance: f32,
+        use_hysteresis: bool,
+    ) -> bool {
+        let Some(level) = self.levels.get(level_index) else {
+            return false;
+        };
+
+        if !use_hysteresis {
+            return normalized_distance >= level.begin && normalized_distance <= level.end;
+        }
+
+        let mut visible_objects = self.visible_objects.borrow_mut();
+        let position = visible_objects.iter().position(|o| *o == object);
+        let (begin, end) = if position.is_some() {
+            (level.begin - self.hysteresis, level.end + self.hysteresis)
+        } else {
+            (level.begin, level.end)
+        };
+        let visible = normalized_distance >= begin && normalized_distance <= end;
+
+        match (visible, position) {
+            (true, None) => visible_objects.push(object),
+            (false, Some(position)) => {
+                visible_objects.swap_remove(position);
+            }
+            _ => (),
+        }
+
+        visible
+    }
 }
 
 /// Mobility defines a group for scene node which has direct impact on performance
@@ -746,7 +800,10 @@ impl Base {
         *self.cast_shadows
     }
 
-    /// Sets whether the mesh should cast shadows or not.
+    /// Sets whether the node should cast shadows or not. Shadow casters are collected from the point
+    /// of view of each light (not the camera), so nodes outside of the camera frustum still cast
+    /// shadows into the view. The flag affects only shadow passes, the node itself is rendered as
+    /// usual.
     #[inline]
     pub fn set_cast_shadows(&mut self, cast_shadows: bool) -> bool {
         self.cast_shadows.set_value_and_mark_modified(cast_shadows)
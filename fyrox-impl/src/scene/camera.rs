@@ -310,6 +310,27 @@ impl Default for Exposure {
     }
 }
 
+/// Tone mapping operator defines how high dynamic range colors of a frame will be mapped into low
+/// dynamic range. All operators take exposure into account.
+#[derive(
+    Visit, Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, AsRefStr, EnumString, VariantNames,
+)]
+pub enum ToneMapping {
+    /// Exponential tone mapping, uses `1.0 - exp(-color * exposure)` formula. This is default option.
+    #[default]
+    Exponential,
+    /// Classic Reinhard tone mapping, uses `color / (1.0 + color)` formula.
+    Reinhard,
+    /// Filmic curve approximation of the Academy Color Encoding System (ACES). Gives high contrast
+    /// images with a slight desaturation of very bright colors.
+    AcesFilmic,
+    /// Passthrough, no tone mapping is applied; colors are only multiplied by exposure and then clamped
+    /// in `[0; 1]` range.
+    None,
+}
+
+uuid_provider!(ToneMapping = "a6c2f4c6-3c39-4b4e-9d0a-0e2b9f8c5e71");
+
 /// Camera allows you to see world from specific point in world. You must have at least one camera in
 /// your scene to see anything.
 ///
@@ -329,6 +350,12 @@ impl Default for Exposure {
 /// Fyrox supports multiple cameras per scene, it means that you can create split screen games, make
 /// picture-in-picture insertions in your main camera view and any other combinations you need.
 ///
+/// Only enabled cameras (see [`Camera::set_enabled`]) are rendered, disabled cameras are skipped
+/// entirely. Each enabled camera renders the scene into its own viewport (see [`Camera::set_viewport`]),
+/// which covers the entire frame by default. Cameras are rendered in the order of their handles in the
+/// scene graph (see [`Graph::linear_iter`]), so if viewports overlap, a camera with larger handle index
+/// will be drawn on top.
+///
 /// ## Performance
 ///
 /// Each camera forces engine to re-render same scene one more time, which may cause almost double load
@@ -349,12 +376,24 @@ pub struct Camera {
     #[reflect(setter = "set_skybox")]
     sky_box: InheritableVariable<Option<SkyBox>>,
 
+    #[reflect(setter = "set_skybox_intensity", min_value = 0.0, step = 0.1)]
+    #[visit(optional)]
+    skybox_intensity: InheritableVariable<f32>,
+
     #[reflect(setter = "set_environment")]
     environment: InheritableVariable<Option<TextureResource>>,
 
     #[reflect(setter = "set_exposure")]
     exposure: InheritableVariable<Exposure>,
 
+    #[reflect(setter = "set_exposure_compensation", step = 0.1)]
+    #[visit(optional)]
+    exposure_compensation: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_tone_mapping")]
+    #[visit(optional)]
+    tone_mapping: InheritableVariable<ToneMapping>,
+
     #[reflect(setter = "set_color_grading_lut")]
     color_grading_lut: InheritableVariable<Option<ColorGradingLut>>,
 
@@ -476,6 +515,13 @@ impl Camera {
         self.projection_matrix * self.view_matrix
     }
 
+    /// Returns inverse view-projection matrix. It could be used to transform points from normalized
+    /// device coordinates to world space.
+    #[inline]
+    pub fn inv_view_projection_matrix(&self) -> Option<Matrix4<f32>> {
+        self.view_projection_matrix().try_inverse()
+    }
+
     /// Returns current projection matrix.
     #[inline]
     pub fn projection_matrix(&self) -> Matrix4<f32> {
@@ -547,6 +593,19 @@ impl Camera {
         self.sky_box.as_ref()
     }
 
+    /// Sets new brightness multiplier of the skybox. Skybox textures are in low dynamic range, so
+    /// the multiplier could be used to match brightness of the sky with the lighting of the scene,
+    /// that is then processed by exposure and tone mapping. Default is 1.0.
+    pub fn set_skybox_intensity(&mut self, intensity: f32) -> f32 {
+        self.skybox_intensity
+            .set_value_and_mark_modified(intensity.max(0.0))
+    }
+
+    /// Returns current brightness multiplier of the skybox.
+    pub fn skybox_intensity(&self) -> f32 {
+        *self.skybox_intensity
+    }
+
     /// Replaces the skybox.
     pub fn replace_skybox(&mut self, new: Option<SkyBox>) -> Option<SkyBox> {
         std::mem::replace(self.sky_box.get_value_mut_and_mark_modified(), new)
@@ -575,17 +634,20 @@ impl Camera {
         (*self.environment).clone()
     }
 
-    /// Creates picking ray from given screen coordinates.
+    /// Creates picking ray from given screen coordinates. Screen coordinates are given in pixels
+    /// relative to the left upper corner of the frame, the viewport of the camera is taken into
+    /// account, so the method works correctly for split screen. The ray starts at the near clipping
+    /// plane and ends at the far clipping plane, it works for both perspective and orthographic
+    /// projections.
     pub fn make_ray(&self, screen_coord: Vector2<f32>, screen_size: Vector2<f32>) -> Ray {
         let viewport = self.viewport_pixels(screen_size);
-        let nx = screen_coord.x / (viewport.w() as f32) * 2.0 - 1.0;
+        let nx = (screen_coord.x - viewport.x() as f32) / (viewport.w() as f32) * 2.0 - 1.0;
         // Invert y here because OpenGL has origin at left bottom corner,
         // but window coordinates starts from left *upper* corner.
-        let ny = (viewport.h() as f32 - screen_coord.y) / (viewport.h() as f32) * 2.0 - 1.0;
-        let inv_view_proj = self
-            .view_projection_matrix()
-            .try_inverse()
-            .unwrap_or_default();
+        let ny = (screen_size.y - screen_coord.y - viewport.y() as f32) / (viewport.h() as f32)
+            * 2.0
+            - 1.0;
+        let inv_view_proj = self.inv_view_projection_matrix().unwrap_or_default();
         let near = inv_view_proj * Vector4::new(nx, ny, -1.0, 1.0);
         let far = inv_view_proj * Vector4::new(nx, ny, 1.0, 1.0);
         let begin = near.xyz().scale(1.0 / near.w);
@@ -667,8 +729,7 @@ impl Camera {
             let k = (1.0 / proj.w) * 0.5;
             Some(Vector2::new(
                 viewport.x() as f32 + viewport.w() as f32 * (proj.x * k + 0.5),
-                viewport.h() as f32
-                    - (viewport.y() as f32 + viewport.h() as f32 * (proj.y * k + 0.5)),
+                screen_size.y - (viewport.y() as f32 + viewport.h() as f32 * (proj.y * k + 0.5)),
             ))
         } else {
             None
@@ -713,6 +774,29 @@ impl Camera {
     pub fn exposure(&self) -> Exposure {
         *self.exposure
     }
+
+    /// Sets new exposure compensation in EV stops. The exposure (either automatic or manual) is
+    /// multiplied by `2 ^ compensation`, so `1.0` makes the frame twice as bright and `-1.0` twice
+    /// as dark. Default is `0.0`, which means no compensation.
+    pub fn set_exposure_compensation(&mut self, compensation: f32) -> f32 {
+        self.exposure_compensation
+            .set_value_and_mark_modified(compensation)
+    }
+
+    /// Returns current exposure compensation in EV stops.
+    pub fn exposure_compensation(&self) -> f32 {
+        *self.exposure_compensation
+    }
+
+    /// Sets new tone mapping operator. See [`ToneMapping`] docs for more info.
+    pub fn set_tone_mapping(&mut self, tone_mapping: ToneMapping) -> ToneMapping {
+        self.tone_mapping.set_value_and_mark_modified(tone_mapping)
+    }
+
+    /// Returns current tone mapping operator.
+    pub fn tone_mapping(&self) -> ToneMapping {
+        *self.tone_mapping
+    }
 }
 
 impl NodeTrait for Camera {
@@ -1035,8 +1119,11 @@ pub struct CameraBuilder {
     viewport: Rect<f32>,
     enabled: bool,
     skybox: SkyBoxKind,
+    skybox_intensity: f32,
     environment: Option<TextureResource>,
     exposure: Exposure,
+    exposure_compensation: f32,
+    tone_mapping: ToneMapping,
     color_grading_lut: Option<ColorGradingLut>,
     color_grading_enabled: bool,
     projection: Projection,
@@ -1053,8 +1140,11 @@ impl CameraBuilder {
             z_far: 2048.0,
             viewport: Rect::new(0.0, 0.0, 1.0, 1.0),
             skybox: SkyBoxKind::Builtin,
+            skybox_intensity: 1.0,
             environment: None,
             exposure: Exposure::Manual(std::f32::consts::E),
+            exposure_compensation: 0.0,
+            tone_mapping: Default::default(),
             color_grading_lut: None,
             color_grading_enabled: false,
             projection: Projection::default(),
@@ -1103,6 +1193,12 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets desired brightness multiplier of the skybox.
+    pub fn with_skybox_intensity(mut self, intensity: f32) -> Self {
+        self.skybox_intensity = intensity;
+        self
+    }
+
     /// Sets desired environment map.
     pub fn with_environment(mut self, environment: TextureResource) -> Self {
         self.environment = Some(environment);
@@ -1127,6 +1223,18 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets desired exposure compensation in EV stops.
+    pub fn with_exposure_compensation(mut self, compensation: f32) -> Self {
+        self.exposure_compensation = compensation;
+        self
+    }
+
+    /// Sets desired tone mapping operator.
+    pub fn with_tone_mapping(mut self, tone_mapping: ToneMapping) -> Self {
+        self.tone_mapping = tone_mapping;
+        self
+    }
+
     /// Sets desired projection mode.
     pub fn with_projection(mut self, projection: Projection) -> Self {
         self.projection = projection;
@@ -1149,8 +1257,11 @@ impl CameraBuilder {
                 SkyBoxKind::None => None,
                 SkyBoxKind::Specific(skybox) => Some(skybox),
             }),
+            skybox_intensity: self.skybox_intensity.into(),
             environment: self.environment.into(),
             exposure: self.exposure.into(),
+            exposure_compensation: self.exposure_compensation.into(),
+            tone_mapping: self.tone_mapping.into(),
             color_grading_lut: self.color_grading_lut.into(),
             color_grading_enabled: self.color_grading_enabled.into(),
         }
@@ -48,6 +48,7 @@ use crate::{
         mesh::Mesh,
         navmesh,
         node::{container::NodeContainer, Node, NodeTrait, SyncContext, UpdateContext},
+        particle_system::force_field::{self, ActiveForceField},
         pivot::Pivot,
         sound::context::SoundContext,
         transform::TransformBuilder,
@@ -925,6 +926,7 @@ impl Graph {
         frame_size: Vector2<f32>,
         dt: f32,
         delete_dead_nodes: bool,
+        force_fields: &[ActiveForceField],
     ) {
         if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
             node.transform_modified.set(false);
@@ -939,6 +941,7 @@ impl Graph {
                     physics: &mut self.physics,
                     physics2d: &mut self.physics2d,
                     sound_context: &mut self.sound_context,
+                    force_fields,
                 });
 
                 if delete_dead_nodes {
@@ -996,9 +999,19 @@ impl Graph {
         self.performance_statistics.sound_update_time =
             self.sound_context.state().full_render_duration();
 
+        // Force fields are collected once per frame, every particle system then picks the ones
+        // that are close enough to its particles.
+        let force_fields = force_field::collect_force_fields(&self.pool);
+
         if let Some(overrides) = switches.node_overrides.as_ref() {
             for handle in overrides {
-                self.update_node(*handle, frame_size, dt, switches.delete_dead_nodes);
+                self.update_node(
+                    *handle,
+                    frame_size,
+                    dt,
+                    switches.delete_dead_nodes,
+                    &force_fields,
+                );
             }
         } else {
             for i in 0..self.pool.get_capacity() {
@@ -1007,6 +1020,7 @@ impl Graph {
                     frame_size,
                     dt,
                     switches.delete_dead_nodes,
+                    &force_fields,
                 );
             }
         }
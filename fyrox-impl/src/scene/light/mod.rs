@@ -21,12 +21,14 @@ use crate::{
         algebra::Vector3,
         color::Color,
         reflect::prelude::*,
+        uuid_provider,
         variable::InheritableVariable,
         visitor::{Visit, VisitResult, Visitor},
     },
     scene::base::{Base, BaseBuilder},
 };
 use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub mod directional;
 pub mod point;
@@ -44,6 +46,25 @@ pub const DEFAULT_SCATTER_G: f32 = 0.03;
 /// significant value and you'll clearly see light volume with such settings.
 pub const DEFAULT_SCATTER_B: f32 = 0.03;
 
+/// Defines how intensity of point and spot lights decreases with the distance to the light source.
+/// Directional lights are not affected by attenuation.
+#[derive(
+    Visit, Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, AsRefStr, EnumString, VariantNames,
+)]
+pub enum LightAttenuation {
+    /// Simple range-based attenuation, uses `1.0 - (distance / radius)^2` formula. This is default
+    /// option, it keeps appearance of scenes that were made before physically-based attenuation
+    /// was added.
+    #[default]
+    Legacy,
+    /// Physically-based inverse-square attenuation, uses `1.0 / distance^2` formula smoothly faded
+    /// to zero at the radius of the light. Intensity of the light defines its brightness at the
+    /// distance of one unit, so such lights usually need higher intensity than legacy ones.
+    InverseSquare,
+}
+
+uuid_provider!(LightAttenuation = "5c8a2f3e-7b51-4d0e-a6c9-1e4f8b2d73a0");
+
 /// Light scene node. It contains common properties of light such as color,
 /// scattering factor (per color channel) and other useful properties. Exact
 /// behavior defined by specific light kind.
@@ -67,6 +88,10 @@ pub struct BaseLight {
     #[reflect(min_value = 0.0, step = 0.1)]
     #[reflect(setter = "set_intensity")]
     intensity: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_attenuation")]
+    #[visit(optional)]
+    attenuation: InheritableVariable<LightAttenuation>,
 }
 
 impl Deref for BaseLight {
@@ -96,6 +121,7 @@ impl Default for BaseLight {
             )),
             scatter_enabled: InheritableVariable::new_modified(true),
             intensity: InheritableVariable::new_modified(1.0),
+            attenuation: InheritableVariable::new_modified(Default::default()),
         }
     }
 }
@@ -158,6 +184,16 @@ impl BaseLight {
         *self.intensity
     }
 
+    /// Sets new distance attenuation model. See [`LightAttenuation`] docs for more info.
+    pub fn set_attenuation(&mut self, attenuation: LightAttenuation) -> LightAttenuation {
+        self.attenuation.set_value_and_mark_modified(attenuation)
+    }
+
+    /// Returns current distance attenuation model.
+    pub fn attenuation(&self) -> LightAttenuation {
+        *self.attenuation
+    }
+
     /// Returns current scatter factor in linear color space.
     #[inline]
     pub fn scatter_linear(&self) -> Vector3<f32> {
@@ -186,6 +222,7 @@ pub struct BaseLightBuilder {
     scatter_factor: Vector3<f32>,
     scatter_enabled: bool,
     intensity: f32,
+    attenuation: LightAttenuation,
 }
 
 impl BaseLightBuilder {
@@ -201,6 +238,7 @@ impl BaseLightBuilder {
             scatter_factor: Vector3::new(DEFAULT_SCATTER_R, DEFAULT_SCATTER_G, DEFAULT_SCATTER_B),
             scatter_enabled: true,
             intensity: 1.0,
+            attenuation: Default::default(),
         }
     }
 
@@ -234,6 +272,12 @@ impl BaseLightBuilder {
         self
     }
 
+    /// Sets desired distance attenuation model.
+    pub fn with_attenuation(mut self, attenuation: LightAttenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
     /// Creates new instance of base light.
     pub fn build(self) -> BaseLight {
         BaseLight {
@@ -243,6 +287,7 @@ impl BaseLightBuilder {
             scatter: self.scatter_factor.into(),
             scatter_enabled: self.scatter_enabled.into(),
             intensity: self.intensity.into(),
+            attenuation: self.attenuation.into(),
         }
     }
 }
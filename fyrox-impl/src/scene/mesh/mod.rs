@@ -21,7 +21,7 @@ use crate::{
         bundle::{
             PersistentIdentifier, RenderContext, RenderDataBundleStorageTrait, SurfaceInstanceData,
         },
-        framework::geometry_buffer::ElementRange,
+        framework::{geometry_buffer::ElementRange, state::BlendFunc},
     },
     scene::{
         base::{Base, BaseBuilder},
@@ -102,6 +102,17 @@ fn transform_vertex(mut vertex: VertexViewMut, world: &Matrix4<f32>) {
 }
 
 /// Batching mode defines how the mesh data will be grouped before rendering.
+///
+/// # Choosing a mode
+///
+/// Scenes with thousands of identical props (rocks, grass, debris, etc.) could waste a lot of draw calls,
+/// if every prop is drawn separately. Meshes that share the same surface data and material are always grouped
+/// in the same render bundle, batching modes allow you to reduce the amount of draw calls even further:
+///
+/// - Use [`BatchingMode::Static`] on a parent node of props that never move.
+/// - Use [`BatchingMode::Dynamic`] on each of movable props, that share the same surface data and material.
+/// - Use [`Mesh::set_instanced`] on each of movable props with high vertex count, that share the same surface
+///   data and material. Such props will be drawn in a single instanced draw call.
 #[derive(
     Default,
     Copy,
@@ -163,6 +174,9 @@ impl BatchContainer {
                 storage: self,
                 graph: ctx.graph,
                 render_pass_name: ctx.render_pass_name,
+                particle_transparency: ctx.particle_transparency,
+                particle_budget: ctx.particle_budget,
+                culling_statistics: Default::default(),
             });
         }
     }
@@ -186,6 +200,7 @@ impl RenderDataBundleStorageTrait for BatchContainer {
         _decal_layer_index: u8,
         _sort_index: u64,
         _is_skinned: bool,
+        _blend_func: Option<BlendFunc>,
         _node_handle: Handle<Node>,
         func: &mut dyn FnMut(VertexBufferRefMut, TriangleBufferRefMut),
     ) {
@@ -323,6 +338,15 @@ pub struct Mesh {
     )]
     batching_mode: InheritableVariable<BatchingMode>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_instanced",
+        description = "Enable or disable instanced rendering. Meshes, that share the same surface \
+    data and material, will be drawn in a single draw call. Does not work with meshes, that have \
+    skin or blend shapes, and with meshes that use batching."
+    )]
+    instanced: InheritableVariable<bool>,
+
     #[visit(optional)]
     blend_shapes: InheritableVariable<Vec<BlendShape>>,
 
@@ -354,6 +378,7 @@ impl Default for Mesh {
             render_path: InheritableVariable::new_modified(RenderPath::Deferred),
             decal_layer_index: InheritableVariable::new_modified(0),
             batching_mode: Default::default(),
+            instanced: Default::default(),
             blend_shapes: Default::default(),
             batch_container: Default::default(),
         }
@@ -521,6 +546,22 @@ impl Mesh {
     pub fn batching_mode(&self) -> BatchingMode {
         *self.batching_mode
     }
+
+    /// Enable or disable instanced rendering. When enabled, every surface of the mesh will be drawn in
+    /// a single instanced draw call together with surfaces of other instanced meshes, that share the
+    /// same surface data and material. Does not work with meshes, that have skin or blend shapes, and
+    /// with meshes that use batching. Such meshes will be drawn in a separate draw call. The shader
+    /// of the material must support instancing (the standard shaders do), see "Built-in properties"
+    /// section of [`crate::material::shader`] docs for more info. Otherwise the surfaces are drawn
+    /// one by one.
+    pub fn set_instanced(&mut self, instanced: bool) -> bool {
+        self.instanced.set_value_and_mark_modified(instanced)
+    }
+
+    /// Returns `true` if the instanced rendering is enabled, `false` otherwise.
+    pub fn is_instanced(&self) -> bool {
+        *self.instanced
+    }
 }
 
 fn extend_aabb_from_vertex_buffer(
@@ -602,10 +643,7 @@ impl NodeTrait for Mesh {
     fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
         if !self.global_visibility()
             || !self.is_globally_enabled()
-            || (self.frustum_culling()
-                && !ctx
-                    .frustum
-                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+            || !ctx.is_inside_frustum(self.frustum_culling(), &self.world_bounding_box())
         {
             return RdcControlFlow::Continue;
         }
@@ -640,6 +678,7 @@ impl NodeTrait for Mesh {
                             index,
                         ),
                         node_handle: self.self_handle,
+                        instanced: false,
                     },
                 );
             }
@@ -706,6 +745,7 @@ impl NodeTrait for Mesh {
                                     index,
                                 ),
                                 node_handle: self.self_handle,
+                                instanced: *self.instanced,
                             },
                         );
                     }
@@ -722,6 +762,7 @@ impl NodeTrait for Mesh {
                             self.decal_layer_index(),
                             0,
                             false,
+                            None,
                             self.self_handle,
                             &mut move |mut vertex_buffer, mut triangle_buffer| {
                                 let start_vertex_index = vertex_buffer.vertex_count();
@@ -811,6 +852,7 @@ pub struct MeshBuilder {
     decal_layer_index: u8,
     blend_shapes: Vec<BlendShape>,
     batching_mode: BatchingMode,
+    instanced: bool,
 }
 
 impl MeshBuilder {
@@ -823,6 +865,7 @@ impl MeshBuilder {
             decal_layer_index: 0,
             blend_shapes: Default::default(),
             batching_mode: BatchingMode::None,
+            instanced: false,
         }
     }
 
@@ -858,6 +901,13 @@ impl MeshBuilder {
         self
     }
 
+    /// Sets whether the mesh should be drawn using instanced rendering. See [`Mesh::set_instanced`] docs
+    /// for more info.
+    pub fn with_instanced(mut self, instanced: bool) -> Self {
+        self.instanced = instanced;
+        self
+    }
+
     /// Creates new mesh.
     pub fn build_node(self) -> Node {
         Node::new(Mesh {
@@ -870,6 +920,7 @@ impl MeshBuilder {
             decal_layer_index: self.decal_layer_index.into(),
             world_bounding_box: Default::default(),
             batching_mode: self.batching_mode.into(),
+            instanced: self.instanced.into(),
             batch_container: Default::default(),
         })
     }
@@ -263,12 +263,19 @@ impl SurfaceData {
                 transform.transform_point(&Point3::from(position)).coords,
             )?;
             let normal = view.read_3_f32(VertexAttributeUsage::Normal)?;
+            // Non-uniform scaling changes the length of the vectors, so they must be normalized.
             view.write_3_f32(
                 VertexAttributeUsage::Normal,
-                normal_matrix.transform_vector(&normal),
+                normal_matrix
+                    .transform_vector(&normal)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or(normal),
             )?;
             let tangent = view.read_4_f32(VertexAttributeUsage::Tangent)?;
-            let new_tangent = normal_matrix.transform_vector(&tangent.xyz());
+            let new_tangent = normal_matrix
+                .transform_vector(&tangent.xyz())
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(|| tangent.xyz());
             // Keep sign (W).
             view.write_4_f32(
                 VertexAttributeUsage::Tangent,
@@ -411,10 +418,12 @@ impl SurfaceData {
 
         let triangles = vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])];
 
-        Self::new(
+        let mut data = Self::new(
             VertexBuffer::new(vertices.len(), vertices).unwrap(),
             TriangleBuffer::new(triangles),
-        )
+        );
+        data.calculate_tangents().unwrap();
+        data
     }
 
     /// Creates a degenerated quad which collapsed in a point. This is very special method for sprite renderer - shader will
@@ -497,6 +506,7 @@ impl SurfaceData {
     }
 
     /// Calculates per-face normals. This method is fast, but have very poor quality, and surface will look facet.
+    /// See [`Self::calculate_smooth_normals`] for smooth normals.
     pub fn calculate_normals(&mut self) -> Result<(), VertexFetchError> {
         let mut vertex_buffer_mut = self.vertex_buffer.modify();
         for triangle in self.geometry_buffer.iter() {
@@ -536,6 +546,56 @@ impl SurfaceData {
         Ok(())
     }
 
+    /// Calculates per-vertex normals from positions of the vertices. Normal of a vertex is an average of
+    /// the normals of the triangles sharing the vertex, weighted by the areas of the triangles. Vertices
+    /// are not welded, so a surface with unshared vertices (for example, a cube with 24 vertices) will
+    /// look facet. Tangents depend on normals, use [`Self::calculate_tangents`] after this method.
+    pub fn calculate_smooth_normals(&mut self) -> Result<(), VertexFetchError> {
+        let mut normals = vec![Vector3::default(); self.vertex_buffer.vertex_count() as usize];
+
+        for triangle in self.geometry_buffer.iter() {
+            let ia = triangle[0] as usize;
+            let ib = triangle[1] as usize;
+            let ic = triangle[2] as usize;
+
+            let a = self
+                .vertex_buffer
+                .get(ia)
+                .unwrap()
+                .read_3_f32(VertexAttributeUsage::Position)?;
+            let b = self
+                .vertex_buffer
+                .get(ib)
+                .unwrap()
+                .read_3_f32(VertexAttributeUsage::Position)?;
+            let c = self
+                .vertex_buffer
+                .get(ic)
+                .unwrap()
+                .read_3_f32(VertexAttributeUsage::Position)?;
+
+            // Length of the cross product is twice the area of the triangle, so there is no need to
+            // normalize it.
+            let normal = (b - a).cross(&(c - a));
+
+            normals[ia] += normal;
+            normals[ib] += normal;
+            normals[ic] += normal;
+        }
+
+        let mut vertex_buffer_mut = self.vertex_buffer.modify();
+        for (mut view, normal) in vertex_buffer_mut.iter_mut().zip(normals) {
+            view.write_3_f32(
+                VertexAttributeUsage::Normal,
+                normal
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::z),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Creates sphere of specified radius with given slices and stacks. The larger the `slices` and `stacks`, the smoother the sphere will be.
     /// Typical values are [16..32]. The sphere is then transformed by the given transformation matrix, which could be [`Matrix4::identity`]
     /// to not modify the sphere at all.
@@ -547,6 +607,9 @@ impl SurfaceData {
         let d_tc_y = 1.0 / stacks as f32;
         let d_tc_x = 1.0 / slices as f32;
 
+        // The sphere is centered at the origin, so normal of a vertex is its normalized position.
+        let normal = |v: Vector3<f32>| v.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::z);
+
         for i in 0..stacks {
             for j in 0..slices {
                 let nj = j + 1;
@@ -572,9 +635,9 @@ impl SurfaceData {
                     let v2 = Vector3::new(k4 * k5, k4 * k6, k7);
                     let t2 = Vector2::new(d_tc_x * nj as f32, d_tc_y * ni as f32);
 
-                    builder.insert(StaticVertex::from_pos_uv_normal(v0, t0, v0));
-                    builder.insert(StaticVertex::from_pos_uv_normal(v1, t1, v1));
-                    builder.insert(StaticVertex::from_pos_uv_normal(v2, t2, v2));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v0, t0, normal(v0)));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v1, t1, normal(v1)));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v2, t2, normal(v2)));
                 }
 
                 if i != 0 {
@@ -587,9 +650,9 @@ impl SurfaceData {
                     let v2 = Vector3::new(k0 * k1, k0 * k2, k3);
                     let t2 = Vector2::new(d_tc_x * j as f32, d_tc_y * i as f32);
 
-                    builder.insert(StaticVertex::from_pos_uv_normal(v0, t0, v0));
-                    builder.insert(StaticVertex::from_pos_uv_normal(v1, t1, v1));
-                    builder.insert(StaticVertex::from_pos_uv_normal(v2, t2, v2));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v0, t0, normal(v0)));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v1, t1, normal(v1)));
+                    builder.insert(StaticVertex::from_pos_uv_normal(v2, t2, normal(v2)));
                 }
             }
         }
@@ -652,8 +715,14 @@ impl SurfaceData {
             let tip = Vector3::new(0.0, h, 0.0);
             let v_curr = Vector3::new(x0, 0.0, z0);
             let v_next = Vector3::new(x1, 0.0, z1);
-            let n_next = (tip - v_next).cross(&(v_next - v_curr));
-            let n_curr = (tip - v_curr).cross(&(v_next - v_curr));
+            let n_next = (tip - v_next)
+                .cross(&(v_next - v_curr))
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            let n_curr = (tip - v_curr)
+                .cross(&(v_next - v_curr))
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
 
             builder.insert(StaticVertex::from_pos_uv_normal(
                 tip,
@@ -813,33 +882,33 @@ impl SurfaceData {
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x0, 0.0, z0),
                 Vector2::new(t_side_curr, 0.0),
-                Vector3::new(x0, 0.0, z0),
+                Vector3::new(nx0, 0.0, ny0),
             ));
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x0, h, z0),
                 Vector2::new(t_side_curr, 1.0),
-                Vector3::new(x0, 0.0, z0),
+                Vector3::new(nx0, 0.0, ny0),
             ));
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x1, 0.0, z1),
                 Vector2::new(t_side_next, 0.0),
-                Vector3::new(x1, 0.0, z1),
+                Vector3::new(nx1, 0.0, ny1),
             ));
 
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x1, 0.0, z1),
                 Vector2::new(t_side_next, 0.0),
-                Vector3::new(x1, 0.0, z1),
+                Vector3::new(nx1, 0.0, ny1),
             ));
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x0, h, z0),
                 Vector2::new(t_side_curr, 1.0),
-                Vector3::new(x0, 0.0, z0),
+                Vector3::new(nx0, 0.0, ny0),
             ));
             builder.insert(StaticVertex::from_pos_uv_normal(
                 Vector3::new(x1, h, z1),
                 Vector2::new(t_side_next, 1.0),
-                Vector3::new(x1, 0.0, z1),
+                Vector3::new(nx1, 0.0, ny1),
             ));
         }
 
@@ -1505,3 +1574,86 @@ lazy_static! {
         SurfaceData::make_torus(1.0, 0.25,32, 32,  &Matrix4::identity()),
     );
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::TriangleDefinition,
+        },
+        scene::mesh::{
+            buffer::{TriangleBuffer, VertexAttributeUsage, VertexBuffer, VertexReadTrait},
+            surface::SurfaceData,
+            vertex::StaticVertex,
+        },
+    };
+
+    // Two right triangles of the same area, that share an edge and form a right angle. The first one
+    // lies in oXY plane, the second one - in oXZ plane.
+    fn make_corner() -> SurfaceData {
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ]
+        .into_iter()
+        .map(|position| StaticVertex::from_pos_uv(position, Vector2::default()))
+        .collect::<Vec<_>>();
+
+        SurfaceData::new(
+            VertexBuffer::new(vertices.len(), vertices).unwrap(),
+            TriangleBuffer::new(vec![
+                TriangleDefinition([0, 1, 2]),
+                TriangleDefinition([0, 3, 1]),
+            ]),
+        )
+    }
+
+    fn normal(data: &SurfaceData, index: usize) -> Vector3<f32> {
+        data.vertex_buffer
+            .get(index)
+            .unwrap()
+            .read_3_f32(VertexAttributeUsage::Normal)
+            .unwrap()
+    }
+
+    fn assert_normal_eq(actual: Vector3<f32>, expected: Vector3<f32>) {
+        assert!(
+            (actual - expected).norm() < 1.0e-5,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_flat_normals() {
+        let mut data = make_corner();
+        data.calculate_normals().unwrap();
+
+        assert_normal_eq(normal(&data, 2), Vector3::z());
+        assert_normal_eq(normal(&data, 3), Vector3::y());
+
+        // Shared vertices are not averaged, they keep normal of one of the faces.
+        for shared in [0, 1] {
+            let normal = normal(&data, shared);
+            assert!(
+                (normal - Vector3::z()).norm() < 1.0e-5 || (normal - Vector3::y()).norm() < 1.0e-5,
+                "unexpected normal {normal:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_smooth_normals() {
+        let mut data = make_corner();
+        data.calculate_smooth_normals().unwrap();
+
+        assert_normal_eq(normal(&data, 2), Vector3::z());
+        assert_normal_eq(normal(&data, 3), Vector3::y());
+
+        let averaged = Vector3::new(0.0, 1.0, 1.0).normalize();
+        assert_normal_eq(normal(&data, 0), averaged);
+        assert_normal_eq(normal(&data, 1), averaged);
+    }
+}
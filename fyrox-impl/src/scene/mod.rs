@@ -37,6 +37,7 @@ use crate::{
         log::{Log, MessageKind},
         pool::{Handle, Pool, Ticket},
         reflect::prelude::*,
+        type_traits::prelude::*,
         visitor::{Visit, VisitError, VisitResult, Visitor},
     },
     engine::SerializationContext,
@@ -64,6 +65,7 @@ use std::{
     path::PathBuf,
     sync::Arc,
 };
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 /// A container for navigational meshes.
 #[derive(Default, Clone, Debug, Visit)]
@@ -142,6 +144,70 @@ impl IndexMut<Handle<Navmesh>> for NavMeshContainer {
     }
 }
 
+/// Defines how fog density changes with the distance from the camera.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "0f4bf5a2-4d0e-4a43-9a57-8d2b7ad4c6e1")]
+#[repr(u32)]
+pub enum FogMode {
+    /// Fog density grows linearly from zero at [`FogSettings::start`] distance to one at [`FogSettings::end`]
+    /// distance.
+    #[default]
+    Linear = 0,
+    /// Fog density grows exponentially with the distance from [`FogSettings::start`], the speed of growth
+    /// is defined by [`FogSettings::density`].
+    Exponential = 1,
+}
+
+/// Fog settings of a scene. Fog is applied to the opaque (deferred) geometry of a scene, it blends the
+/// lit color of every pixel with the color of the fog using the distance from the camera to the pixel.
+/// Forward-rendered geometry (particles, sprites, etc.) is drawn on top of the fogged scene.
+#[derive(Debug, Copy, Clone, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "7c2dd1a8-29de-4b8b-84a4-1b0c5c8f0e3d")]
+pub struct FogSettings {
+    /// Color of the fog.
+    pub color: Color,
+    /// Fog density function.
+    pub mode: FogMode,
+    /// Distance from the camera at which the fog starts.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub start: f32,
+    /// Distance from the camera at which the fog fully covers objects. It is used only in [`FogMode::Linear`]
+    /// mode.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub end: f32,
+    /// Density of the fog. It is used only in [`FogMode::Exponential`] mode.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub density: f32,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::opaque(180, 180, 190),
+            mode: Default::default(),
+            start: 10.0,
+            end: 100.0,
+            density: 0.05,
+        }
+    }
+}
+
 /// Rendering options of a scene. It allows you to specify a render target to render the scene to, change its clear color, etc.
 #[derive(Debug, Visit, Reflect, PartialEq)]
 pub struct SceneRenderingOptions {
@@ -157,11 +223,34 @@ pub struct SceneRenderingOptions {
     pub clear_color: Option<Color>,
 
     /// Defines how polygons of the scene will be rasterized. By default it set to [`PolygonFillMode::Fill`],
-    /// [`PolygonFillMode::Line`] could be used to render the scene in wireframe mode.
+    /// [`PolygonFillMode::Line`] could be used to render the scene in wireframe mode. In wireframe mode
+    /// the deferred lighting is skipped and the edges are drawn with a flat white color. Forward-rendered geometry (sprites,
+    /// particles, etc.) and the UI are always rendered filled.
     pub polygon_rasterization_mode: PolygonFillMode,
 
-    /// Color of the ambient lighting.
+    /// Color of the ambient lighting. Every scene has its own ambient lighting, so multiple scenes with
+    /// different ambient levels could be rendered (and composited) in one frame. The color is applied to
+    /// every camera of the scene, both in deferred and forward rendering paths.
     pub ambient_lighting_color: Color,
+
+    /// Defines the order in which scenes are rendered. Scenes are rendered in ascending order of this
+    /// value, scenes with the same value are rendered in the order they were added to the scene container.
+    /// It could be used to render a HUD scene on top of the others, regardless of the order of creation.
+    /// The user interface is always drawn on top of every scene. Default value is 0.
+    #[visit(optional)]
+    pub render_order: i32,
+
+    /// Optional fog settings of the scene. Default is [`None`], which means that there's no fog.
+    #[visit(optional)]
+    pub fog: Option<FogSettings>,
+
+    /// Whether to use the skybox of a camera as a source of ambient lighting or not. When enabled,
+    /// the skybox is convolved into a small irradiance cube map, that is sampled using the normal
+    /// of every pixel and tinted by [`Self::ambient_lighting_color`]. Cameras without a skybox use
+    /// the flat ambient color. Forward rendering path always uses the flat ambient color. Default
+    /// is `false`.
+    #[visit(optional)]
+    pub image_based_lighting: bool,
 }
 
 impl Default for SceneRenderingOptions {
@@ -171,6 +260,9 @@ impl Default for SceneRenderingOptions {
             clear_color: None,
             polygon_rasterization_mode: Default::default(),
             ambient_lighting_color: Color::opaque(100, 100, 100),
+            render_order: 0,
+            fog: None,
+            image_based_lighting: false,
         }
     }
 }
@@ -182,6 +274,9 @@ impl Clone for SceneRenderingOptions {
             clear_color: self.clear_color,
             polygon_rasterization_mode: self.polygon_rasterization_mode,
             ambient_lighting_color: self.ambient_lighting_color,
+            render_order: self.render_order,
+            fog: self.fog,
+            image_based_lighting: self.image_based_lighting,
         }
     }
 }
@@ -17,7 +17,7 @@ use crate::{
         mesh::Mesh,
         navmesh::NavigationalMesh,
         node::{Node, NodeTrait},
-        particle_system::ParticleSystem,
+        particle_system::{force_field::ForceField, ParticleSystem},
         pivot::Pivot,
         ragdoll::Ragdoll,
         sound::{listener::Listener, Sound},
@@ -56,6 +56,7 @@ impl NodeConstructorContainer {
         container.add::<SpotLight>();
         container.add::<Mesh>();
         container.add::<ParticleSystem>();
+        container.add::<ForceField>();
         container.add::<Sound>();
         container.add::<Listener>();
         container.add::<Camera>();
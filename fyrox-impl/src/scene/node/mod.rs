@@ -32,7 +32,7 @@ use crate::{
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
         mesh::Mesh,
         navmesh::NavigationalMesh,
-        particle_system::ParticleSystem,
+        particle_system::{force_field::ActiveForceField, ParticleSystem},
         pivot::Pivot,
         ragdoll::Ragdoll,
         sound::{context::SoundContext, listener::Listener, Sound},
@@ -111,6 +111,8 @@ pub struct UpdateContext<'a> {
     pub physics2d: &'a mut dim2::physics::PhysicsWorld,
     /// A mutable reference to sound context.
     pub sound_context: &'a mut SoundContext,
+    /// World-space snapshots of force fields of the graph, collected once per frame.
+    pub(crate) force_fields: &'a [ActiveForceField],
 }
 
 /// Implements [`NodeTrait::query_component_ref`] and [`NodeTrait::query_component_mut`] in a much
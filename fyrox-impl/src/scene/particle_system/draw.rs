@@ -18,6 +18,7 @@ pub struct Vertex {
     pub size: f32,
     pub rotation: f32,
     pub color: Color,
+    pub atlas_tex_coord: Vector2<f32>,
 }
 
 impl VertexTrait for Vertex {
@@ -63,6 +64,14 @@ impl VertexTrait for Vertex {
                 shader_location: 4,
                 normalized: true,
             },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::TexCoord1,
+                data_type: VertexAttributeDataType::F32,
+                size: 2,
+                divisor: 0,
+                shader_location: 5,
+                normalized: false,
+            },
         ]
     }
 }
@@ -2,12 +2,43 @@
 
 use crate::{
     core::{
-        algebra::Vector3, color::Color, numeric_range::RangeExt, reflect::prelude::*,
-        visitor::prelude::*,
+        algebra::Vector3, color::Color, math::curve::Curve, numeric_range::RangeExt,
+        reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*,
     },
     scene::particle_system::{Particle, ParticleSystemRng},
 };
 use std::ops::Range;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Emission mode defines when an emitter spawns new particles.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "9d9e03a6-72e7-4c8c-ad7a-2a81fc8f033d")]
+#[repr(u32)]
+pub enum EmissionMode {
+    /// Particles are spawned continuously using the spawn rate of the emitter. Bursts are
+    /// spawned in addition to them.
+    #[default]
+    Continuous = 0,
+    /// Continuous emission is disabled, particles are spawned only by bursts (see
+    /// [`BaseEmitter::burst`]). Suitable for explosions, impacts and other one-shot effects.
+    BurstOnly = 1,
+}
 
 /// See module docs.
 #[derive(Debug, Visit, PartialEq, Reflect)]
@@ -18,6 +49,10 @@ pub struct BaseEmitter {
     /// spawns nothing if `max_particles` < 0
     #[visit(rename = "SpawnRate")]
     particle_spawn_rate: u32,
+    /// Optional curve, that defines particle spawn rate (in particles per second) over the time (in
+    /// seconds) passed since the start of the emitter. Constant spawn rate is used if there is no curve.
+    #[visit(optional)] // Backward compatibility
+    spawn_rate_curve: Option<Curve>,
     /// Maximum amount of particles emitter can emit. Unlimited if < 0
     #[visit(optional)] // Backward compatibility
     max_particles: Option<u32>,
@@ -45,10 +80,19 @@ pub struct BaseEmitter {
     time: f32,
     #[visit(skip)]
     #[reflect(hidden)]
+    pub(crate) elapsed_time: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
     pub(crate) particles_to_spawn: u32,
     resurrect_particles: bool,
     #[reflect(hidden)]
     pub(crate) spawned_particles: u64,
+    #[visit(optional)] // Backward compatibility
+    emission_mode: EmissionMode,
+    // Amount of particles, that will be spawned on next update regardless of the spawn rate.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pending_burst: u32,
 }
 
 /// Emitter builder allows you to construct emitter in declarative manner.
@@ -56,6 +100,7 @@ pub struct BaseEmitter {
 pub struct BaseEmitterBuilder {
     position: Option<Vector3<f32>>,
     particle_spawn_rate: Option<u32>,
+    spawn_rate_curve: Option<Curve>,
     max_particles: Option<u32>,
     lifetime: Range<f32>,
     size: Range<f32>,
@@ -66,6 +111,7 @@ pub struct BaseEmitterBuilder {
     rotation_speed: Range<f32>,
     rotation: Range<f32>,
     resurrect_particles: bool,
+    emission_mode: EmissionMode,
 }
 
 impl Default for BaseEmitterBuilder {
@@ -80,6 +126,7 @@ impl BaseEmitterBuilder {
         Self {
             position: None,
             particle_spawn_rate: None,
+            spawn_rate_curve: None,
             max_particles: None,
             lifetime: 5.0..10.0,
             size: 0.125..0.250,
@@ -90,6 +137,7 @@ impl BaseEmitterBuilder {
             rotation_speed: -0.02..0.02,
             rotation: -std::f32::consts::PI..std::f32::consts::PI,
             resurrect_particles: true,
+            emission_mode: Default::default(),
         }
     }
 
@@ -105,6 +153,13 @@ impl BaseEmitterBuilder {
         self
     }
 
+    /// Sets desired curve, that defines particle spawn rate (in particles per second) over time (in
+    /// seconds). It overrides constant spawn rate.
+    pub fn with_spawn_rate_curve(mut self, curve: Curve) -> Self {
+        self.spawn_rate_curve = Some(curve);
+        self
+    }
+
     /// Sets desired max amount of particles.
     pub fn with_max_particles(mut self, value: u32) -> Self {
         self.max_particles = Some(value);
@@ -165,11 +220,18 @@ impl BaseEmitterBuilder {
         self
     }
 
+    /// Sets desired emission mode of the emitter.
+    pub fn with_emission_mode(mut self, emission_mode: EmissionMode) -> Self {
+        self.emission_mode = emission_mode;
+        self
+    }
+
     /// Creates new instance of emitter.
     pub fn build(self) -> BaseEmitter {
         BaseEmitter {
             position: self.position.unwrap_or_default(),
             particle_spawn_rate: self.particle_spawn_rate.unwrap_or(25),
+            spawn_rate_curve: self.spawn_rate_curve,
             max_particles: self.max_particles,
             lifetime: self.lifetime,
             size: self.size,
@@ -181,9 +243,12 @@ impl BaseEmitterBuilder {
             rotation: self.rotation,
             alive_particles: 0,
             time: 0.0,
+            elapsed_time: 0.0,
             particles_to_spawn: 0,
             resurrect_particles: self.resurrect_particles,
             spawned_particles: 0,
+            emission_mode: self.emission_mode,
+            pending_burst: 0,
         }
     }
 }
@@ -192,17 +257,27 @@ impl BaseEmitter {
     /// Updates emitter and emits required amount of particles each call. There is no
     /// need to call it manually, it will be automatically called by scene update call.
     pub fn tick(&mut self, dt: f32) {
-        self.time += dt;
-        let time_amount_per_particle = 1.0 / self.particle_spawn_rate as f32;
-        self.particles_to_spawn = (self.time / time_amount_per_particle) as u32;
-        self.time -= time_amount_per_particle * self.particles_to_spawn as f32;
+        self.elapsed_time += dt;
+        let spawn_rate = match self.emission_mode {
+            EmissionMode::Continuous => self.current_spawn_rate(),
+            EmissionMode::BurstOnly => 0.0,
+        };
+        if spawn_rate > 0.0 {
+            self.time += dt;
+            let time_amount_per_particle = 1.0 / spawn_rate;
+            self.particles_to_spawn = (self.time / time_amount_per_particle) as u32;
+            self.time -= time_amount_per_particle * self.particles_to_spawn as f32;
+        } else {
+            self.time = 0.0;
+            self.particles_to_spawn = 0;
+        }
+        self.particles_to_spawn = self
+            .particles_to_spawn
+            .saturating_add(std::mem::take(&mut self.pending_burst));
         if let Some(max_particles) = self.max_particles {
-            let alive_particles = self.alive_particles;
-            if alive_particles < max_particles
-                && alive_particles + self.particles_to_spawn > max_particles
-            {
-                self.particles_to_spawn = max_particles.saturating_sub(alive_particles);
-            }
+            self.particles_to_spawn = self
+                .particles_to_spawn
+                .min(max_particles.saturating_sub(self.alive_particles));
             if !self.resurrect_particles && self.spawned_particles >= u64::from(max_particles) {
                 self.particles_to_spawn = 0;
             }
@@ -249,6 +324,57 @@ impl BaseEmitter {
         self.particle_spawn_rate
     }
 
+    /// Sets new curve, that defines particle spawn rate (in particles per second) over the time (in seconds)
+    /// passed since the start of the emitter. `None` means that constant spawn rate will be used.
+    pub fn set_spawn_rate_curve(&mut self, curve: Option<Curve>) -> &mut Self {
+        self.spawn_rate_curve = curve;
+        self
+    }
+
+    /// Returns current spawn rate curve, if any.
+    pub fn spawn_rate_curve(&self) -> Option<&Curve> {
+        self.spawn_rate_curve.as_ref()
+    }
+
+    /// Returns actual spawn rate in particles per second. It is either the value of the spawn rate curve
+    /// at the current time or the constant spawn rate, if there is no curve.
+    pub fn current_spawn_rate(&self) -> f32 {
+        match self.spawn_rate_curve {
+            Some(ref curve) => curve.value_at(self.elapsed_time).max(0.0),
+            None => self.particle_spawn_rate as f32,
+        }
+    }
+
+    /// Requests the emitter to spawn the given amount of particles on next update, regardless of
+    /// its spawn rate and emission mode. The burst is still limited by the maximum amount of
+    /// particles of the emitter and the particle system.
+    pub fn burst(&mut self, count: u32) -> &mut Self {
+        self.pending_burst = self.pending_burst.saturating_add(count);
+        self
+    }
+
+    /// Brings the emitter to its initial state: resets spawn time accumulator, emission counters
+    /// and cancels pending bursts.
+    pub(crate) fn reset(&mut self) {
+        self.alive_particles = 0;
+        self.spawned_particles = 0;
+        self.elapsed_time = 0.0;
+        self.time = 0.0;
+        self.particles_to_spawn = 0;
+        self.pending_burst = 0;
+    }
+
+    /// Sets new emission mode of the emitter. See [`EmissionMode`] docs for more info.
+    pub fn set_emission_mode(&mut self, emission_mode: EmissionMode) -> &mut Self {
+        self.emission_mode = emission_mode;
+        self
+    }
+
+    /// Returns current emission mode of the emitter.
+    pub fn emission_mode(&self) -> EmissionMode {
+        self.emission_mode
+    }
+
     /// Sets maximum amount of particles.
     pub fn set_max_particles(&mut self, max: Option<u32>) -> &mut Self {
         self.max_particles = max;
@@ -384,6 +510,7 @@ impl Clone for BaseEmitter {
         Self {
             position: self.position,
             particle_spawn_rate: self.particle_spawn_rate,
+            spawn_rate_curve: self.spawn_rate_curve.clone(),
             max_particles: self.max_particles,
             lifetime: self.lifetime.clone(),
             size: self.size.clone(),
@@ -395,9 +522,12 @@ impl Clone for BaseEmitter {
             rotation: self.rotation.clone(),
             alive_particles: self.alive_particles,
             time: self.time,
+            elapsed_time: self.elapsed_time,
             particles_to_spawn: 0,
             resurrect_particles: self.resurrect_particles,
             spawned_particles: self.spawned_particles,
+            emission_mode: self.emission_mode,
+            pending_burst: self.pending_burst,
         }
     }
 }
@@ -407,6 +537,7 @@ impl Default for BaseEmitter {
         Self {
             position: Vector3::default(),
             particle_spawn_rate: 100,
+            spawn_rate_curve: None,
             max_particles: None,
             lifetime: 5.0..10.0,
             size: 0.125..0.250,
@@ -418,9 +549,12 @@ impl Default for BaseEmitter {
             rotation: -std::f32::consts::PI..std::f32::consts::PI,
             alive_particles: 0,
             time: 0.0,
+            elapsed_time: 0.0,
             particles_to_spawn: 0,
             resurrect_particles: true,
             spawned_particles: 0,
+            emission_mode: Default::default(),
+            pending_burst: 0,
         }
     }
 }
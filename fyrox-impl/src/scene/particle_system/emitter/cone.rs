@@ -0,0 +1,162 @@
+//! Cone emitter emits particles within a cone and pushes them outward along its axis.
+
+use crate::{
+    core::{algebra::Vector3, numeric_range::RangeExt, reflect::prelude::*, visitor::prelude::*},
+    scene::particle_system::{
+        emitter::{
+            base::{BaseEmitter, BaseEmitterBuilder},
+            Emit, Emitter,
+        },
+        particle::Particle,
+        ParticleSystemRng,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Cone emitter spawns particles inside a (truncated) cone, which axis is directed along positive Y
+/// axis. The cone starts at the position of the emitter with a disk of [`Self::radius`] and expands
+/// up to [`Self::height`] with [`Self::half_angle`]. Every particle is directed outward along the cone
+/// axis - the closer the particle to the side of the cone, the closer its direction to the half angle.
+/// Speed of particles is defined by the length of a velocity vector that was generated by the base
+/// emitter, so use base emitter's velocity ranges to control it. Such emitters are ideal for muzzle
+/// flashes, jets, fountains, etc.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct ConeEmitter {
+    emitter: BaseEmitter,
+    #[reflect(min_value = 0.0, max_value = 1.57, step = 0.01)]
+    half_angle: f32,
+    #[reflect(min_value = 0.0, step = 0.1)]
+    radius: f32,
+    #[reflect(min_value = 0.0, step = 0.1)]
+    height: f32,
+}
+
+impl Default for ConeEmitter {
+    fn default() -> Self {
+        Self {
+            emitter: Default::default(),
+            half_angle: std::f32::consts::FRAC_PI_8,
+            radius: 0.1,
+            height: 0.1,
+        }
+    }
+}
+
+impl Deref for ConeEmitter {
+    type Target = BaseEmitter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.emitter
+    }
+}
+
+impl DerefMut for ConeEmitter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.emitter
+    }
+}
+
+impl Emit for ConeEmitter {
+    fn emit(&self, particle: &mut Particle, rng: &mut ParticleSystemRng) {
+        self.emitter.emit(particle, rng);
+
+        // Pick a slice of the cone first and then pick a point on it using disk point picking.
+        let y = (0.0..self.height).random(rng);
+        let slice_radius = self.radius + y * self.half_angle.tan();
+        let scale: f32 = (0.0..1.0).random(rng);
+        let theta = (0.0..2.0 * std::f32::consts::PI).random(rng);
+        let radius = scale.sqrt() * slice_radius;
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        particle.position =
+            self.position() + Vector3::new(radius * cos_theta, y, radius * sin_theta);
+
+        // Deviate the direction from the axis proportionally to the distance from the axis.
+        let angle = scale.sqrt() * self.half_angle;
+        let (sin_angle, cos_angle) = angle.sin_cos();
+        let direction = Vector3::new(sin_angle * cos_theta, cos_angle, sin_angle * sin_theta);
+        particle.velocity = direction.scale(particle.velocity.norm());
+    }
+}
+
+impl ConeEmitter {
+    /// Returns half angle (in radians) of the cone emitter.
+    pub fn half_angle(&self) -> f32 {
+        self.half_angle
+    }
+
+    /// Sets half angle (in radians) of the cone emitter. The value will be clamped to `[0; pi/2)` range.
+    pub fn set_half_angle(&mut self, half_angle: f32) {
+        self.half_angle = half_angle.clamp(0.0, std::f32::consts::FRAC_PI_2 - f32::EPSILON);
+    }
+
+    /// Returns radius of the base of the cone emitter.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Sets radius of the base of the cone emitter.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius.max(0.0);
+    }
+
+    /// Returns height of the cone emitter.
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Sets height of the cone emitter.
+    pub fn set_height(&mut self, height: f32) {
+        self.height = height.max(0.0);
+    }
+}
+
+/// Cone emitter builder allows you to construct cone emitter in declarative manner.
+/// This is typical implementation of Builder pattern.
+pub struct ConeEmitterBuilder {
+    base: BaseEmitterBuilder,
+    half_angle: f32,
+    radius: f32,
+    height: f32,
+}
+
+impl ConeEmitterBuilder {
+    /// Creates new cone emitter builder.
+    pub fn new(base: BaseEmitterBuilder) -> Self {
+        Self {
+            base,
+            half_angle: std::f32::consts::FRAC_PI_8,
+            radius: 0.1,
+            height: 0.1,
+        }
+    }
+
+    /// Sets desired half angle (in radians) of the emitter.
+    pub fn with_half_angle(mut self, half_angle: f32) -> Self {
+        self.half_angle = half_angle;
+        self
+    }
+
+    /// Sets desired radius of the base of the emitter.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired height of the emitter.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Creates new cone emitter with given parameters.
+    pub fn build(self) -> Emitter {
+        Emitter::Cone(ConeEmitter {
+            emitter: self.base.build(),
+            half_angle: self
+                .half_angle
+                .clamp(0.0, std::f32::consts::FRAC_PI_2 - f32::EPSILON),
+            radius: self.radius.max(0.0),
+            height: self.height.max(0.0),
+        })
+    }
+}
@@ -1,11 +1,16 @@
 //! Emitter is an enum over all possible emitter types, they all must
 //! use BaseEmitter which contains base functionality.
+//!
+//! Initial parameters of particles (lifetime, size, velocity, rotation) are defined by ranges in
+//! [`BaseEmitter`] and sampled per particle using PRNG of the particle system
+//! ([`ParticleSystemRng`]). The PRNG is seeded, so the same seed produces the same effect every
+//! time the particle system is rewound.
 
 use crate::{
     core::{reflect::prelude::*, visitor::prelude::*},
     scene::particle_system::{
         emitter::{
-            base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
+            base::BaseEmitter, cone::ConeEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
             sphere::SphereEmitter,
         },
         Particle, ParticleSystemRng,
@@ -16,6 +21,7 @@ use std::ops::{Deref, DerefMut};
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub mod base;
+pub mod cone;
 pub mod cuboid;
 pub mod cylinder;
 pub mod sphere;
@@ -35,6 +41,8 @@ pub enum Emitter {
     Sphere(SphereEmitter),
     /// Cylinder emitter.
     Cylinder(CylinderEmitter),
+    /// See ConeEmitter docs.
+    Cone(ConeEmitter),
 }
 
 uuid_provider!(Emitter = "4cad87ed-6b2c-411d-8c05-86dc26e463b2");
@@ -46,6 +54,7 @@ impl Emitter {
             1 => Ok(Self::Cuboid(Default::default())),
             2 => Ok(Self::Sphere(Default::default())),
             3 => Ok(Self::Cylinder(Default::default())),
+            4 => Ok(Self::Cone(Default::default())),
             _ => Err(format!("Invalid emitter id {}!", id)),
         }
     }
@@ -56,6 +65,7 @@ impl Emitter {
             Self::Cuboid(_) => 1,
             Self::Sphere(_) => 2,
             Self::Cylinder(_) => 3,
+            Self::Cone(_) => 4,
         }
     }
 }
@@ -66,6 +76,7 @@ macro_rules! static_dispatch {
             Emitter::Cuboid(v) => v.$func($($args),*),
             Emitter::Sphere(v) => v.$func($($args),*),
             Emitter::Cylinder(v) => v.$func($($args),*),
+            Emitter::Cone(v) => v.$func($($args),*),
         }
     };
 }
@@ -94,6 +105,7 @@ impl Clone for Emitter {
             Self::Cuboid(box_emitter) => Self::Cuboid(box_emitter.clone()),
             Self::Sphere(sphere_emitter) => Self::Sphere(sphere_emitter.clone()),
             Self::Cylinder(cylinder) => Self::Cylinder(cylinder.clone()),
+            Self::Cone(cone) => Self::Cone(cone.clone()),
         }
     }
 }
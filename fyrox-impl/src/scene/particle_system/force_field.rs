@@ -0,0 +1,273 @@
+//! Force field is a node, that applies additional acceleration to particles of particle systems
+//! within its radius. See [`ForceField`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::{Graph, NodePool},
+        node::{Node, NodeTrait},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Defines how a force field affects particles.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "85e702b6-1715-42aa-9ac3-7ddfb6abf4c5")]
+#[repr(u32)]
+pub enum ForceFieldKind {
+    /// Particles are pulled to the center of the field (point gravity). Negative strength pushes
+    /// particles away from the center.
+    #[default]
+    Attractor = 0,
+    /// Particles are rotated around the up axis (local Y axis) of the field. Negative strength
+    /// changes the direction of rotation.
+    Vortex = 1,
+}
+
+/// Force field applies additional acceleration to every particle within its radius. The acceleration
+/// linearly fades from full strength at the center of the field to zero at its boundary. Only
+/// particle systems, that are explicitly marked as affected by force fields (see
+/// [`super::ParticleSystem::set_affected_by_force_fields`]) are affected, so force fields do not
+/// add any overhead to other particle systems.
+#[derive(Clone, Reflect, Debug, Visit)]
+pub struct ForceField {
+    base: Base,
+
+    #[reflect(setter = "set_kind")]
+    kind: InheritableVariable<ForceFieldKind>,
+
+    #[reflect(setter = "set_strength")]
+    strength: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_radius", min_value = 0.0, step = 0.1)]
+    radius: InheritableVariable<f32>,
+}
+
+impl Default for ForceField {
+    fn default() -> Self {
+        ForceFieldBuilder::new(BaseBuilder::new()).build_force_field()
+    }
+}
+
+impl Deref for ForceField {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ForceField {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for ForceField {
+    fn type_uuid() -> Uuid {
+        uuid!("c07ac23d-d96d-4d3a-ae1a-cb781f8a8b39")
+    }
+}
+
+impl ForceField {
+    /// Sets new kind of the field. See [`ForceFieldKind`] docs for more info.
+    pub fn set_kind(&mut self, kind: ForceFieldKind) -> ForceFieldKind {
+        self.kind.set_value_and_mark_modified(kind)
+    }
+
+    /// Returns current kind of the field.
+    pub fn kind(&self) -> ForceFieldKind {
+        *self.kind
+    }
+
+    /// Sets acceleration (in units per second squared) at the center of the field.
+    pub fn set_strength(&mut self, strength: f32) -> f32 {
+        self.strength.set_value_and_mark_modified(strength)
+    }
+
+    /// Returns acceleration at the center of the field.
+    pub fn strength(&self) -> f32 {
+        *self.strength
+    }
+
+    /// Sets radius of the field, particles outside of the radius are not affected. The value will
+    /// be clamped to be non-negative.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.max(0.0))
+    }
+
+    /// Returns radius of the field.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+}
+
+impl NodeTrait for ForceField {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_radius(*self.radius)
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// A snapshot of a force field. Snapshots of every force field of a graph are collected in world
+/// space once per frame and then transformed into simulation space of each particle system.
+pub(crate) struct ActiveForceField {
+    pub kind: ForceFieldKind,
+    pub center: Vector3<f32>,
+    pub axis: Vector3<f32>,
+    pub strength: f32,
+    pub radius: f32,
+}
+
+/// Collects world-space snapshots of every enabled force field, that could affect particles.
+pub(crate) fn collect_force_fields(nodes: &NodePool) -> Vec<ActiveForceField> {
+    nodes
+        .iter()
+        .filter(|node| node.is_globally_enabled())
+        .filter_map(|node| node.cast::<ForceField>())
+        .filter(|force_field| force_field.radius() > 0.0 && force_field.strength() != 0.0)
+        .map(|force_field| ActiveForceField {
+            kind: force_field.kind(),
+            center: force_field.global_position(),
+            axis: force_field
+                .up_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y),
+            strength: force_field.strength(),
+            radius: force_field.radius(),
+        })
+        .collect()
+}
+
+impl ActiveForceField {
+    /// Transforms the snapshot using the given world-to-simulation space transform. Distances and
+    /// accelerations are divided by the `scale` of the simulation space.
+    pub fn transform(&self, transform: &Matrix4<f32>, scale: f32) -> Self {
+        Self {
+            kind: self.kind,
+            center: transform.transform_point(&Point3::from(self.center)).coords,
+            axis: transform
+                .transform_vector(&self.axis)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y),
+            strength: self.strength / scale,
+            radius: self.radius / scale,
+        }
+    }
+
+    /// Returns acceleration of a particle at the given position.
+    pub fn acceleration(&self, position: Vector3<f32>) -> Vector3<f32> {
+        let offset = self.center - position;
+        let sqr_distance = offset.norm_squared();
+        if sqr_distance >= self.radius * self.radius || sqr_distance <= f32::EPSILON {
+            return Vector3::default();
+        }
+
+        let distance = sqr_distance.sqrt();
+        let magnitude = self.strength * (1.0 - distance / self.radius);
+        match self.kind {
+            ForceFieldKind::Attractor => offset.scale(magnitude / distance),
+            ForceFieldKind::Vortex => self
+                .axis
+                .cross(&offset)
+                .try_normalize(f32::EPSILON)
+                .map(|tangent| tangent.scale(magnitude))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Allows you to create force field node in declarative manner.
+pub struct ForceFieldBuilder {
+    base_builder: BaseBuilder,
+    kind: ForceFieldKind,
+    strength: f32,
+    radius: f32,
+}
+
+impl ForceFieldBuilder {
+    /// Creates new force field builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            kind: Default::default(),
+            strength: 1.0,
+            radius: 1.0,
+        }
+    }
+
+    /// Sets desired kind of the field.
+    pub fn with_kind(mut self, kind: ForceFieldKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets desired strength of the field.
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// Sets desired radius of the field.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    fn build_force_field(self) -> ForceField {
+        ForceField {
+            base: self.base_builder.build_base(),
+            kind: self.kind.into(),
+            strength: self.strength.into(),
+            radius: self.radius.max(0.0).into(),
+        }
+    }
+
+    /// Creates new force field node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_force_field())
+    }
+
+    /// Creates new force field node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
@@ -5,21 +5,28 @@ use crate::scene::mesh::buffer::VertexTrait;
 use crate::scene::node::RdcControlFlow;
 use crate::{
     core::{
-        algebra::{Point3, Vector2, Vector3},
+        algebra::{Matrix4, Point3, Vector2, Vector3},
         color_gradient::ColorGradient,
         log::Log,
-        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext, TriangleDefinition},
         pool::Handle,
         reflect::prelude::*,
         sstorage::ImmutableString,
+        type_traits::prelude::*,
         uuid::{uuid, Uuid},
         variable::InheritableVariable,
         visitor::prelude::*,
         TypeUuidProvider,
     },
-    material::{self, Material, MaterialResource, PropertyValue},
+    material::{self, Material, MaterialResource, MaterialResourceExtension, PropertyValue},
     rand::{prelude::StdRng, Error, RngCore, SeedableRng},
-    renderer::{self, bundle::RenderContext},
+    renderer::{
+        self,
+        bundle::RenderContext,
+        framework::state::{BlendFactor, BlendFunc},
+        TransparencyMode,
+    },
+    resource::texture::TextureResource,
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
@@ -28,6 +35,7 @@ use crate::{
         particle_system::{
             draw::Vertex,
             emitter::{Emit, Emitter},
+            force_field::ActiveForceField,
             particle::Particle,
         },
     },
@@ -39,14 +47,17 @@ use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
 };
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub(crate) mod draw;
 pub mod emitter;
+pub mod force_field;
 pub mod particle;
 
 /// Pseudo-random numbers generator for particle systems.
 #[derive(Debug, Clone, Reflect)]
 pub struct ParticleSystemRng {
+    #[reflect(setter = "set_seed")]
     rng_seed: u64,
 
     #[reflect(hidden)]
@@ -74,6 +85,18 @@ impl ParticleSystemRng {
     pub fn reset(&mut self) {
         self.rng = StdRng::seed_from_u64(self.rng_seed);
     }
+
+    /// Sets new seed and resets the state of PRNG. Returns previous seed.
+    pub fn set_seed(&mut self, seed: u64) -> u64 {
+        let prev = std::mem::replace(&mut self.rng_seed, seed);
+        self.reset();
+        prev
+    }
+
+    /// Returns current seed of PRNG.
+    pub fn seed(&self) -> u64 {
+        self.rng_seed
+    }
 }
 
 impl RngCore for ParticleSystemRng {
@@ -113,6 +136,89 @@ impl Visit for ParticleSystemRng {
     }
 }
 
+/// Blending mode defines how the particles of a particle system will be mixed with the pixels behind them.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "bd0e1a4c-7f3e-4c8b-a2a9-5e6b3f1d2c70")]
+#[repr(u32)]
+pub enum ParticleBlendMode {
+    /// Alpha blending (`src * src_alpha + dest * (1 - src_alpha)`). Particle systems with this mode are
+    /// sorted back-to-front relative to the camera, as well as their particles. Suitable for smoke, dust, etc.
+    AlphaBlend = 0,
+    /// Additive blending (`src * src_alpha + dest`). The result does not depend on the drawing order, so
+    /// such particle systems are not sorted. Suitable for fire, sparks, magic effects, etc.
+    Additive = 1,
+    /// Blending function is taken from the render pass of the material's shader. Particle systems with this
+    /// mode are sorted the same way as with [`ParticleBlendMode::AlphaBlend`]. Use this mode for custom
+    /// shaders with its own blending.
+    #[default]
+    Inherit = 2,
+}
+
+impl ParticleBlendMode {
+    /// Returns blending function that corresponds to the blending mode. `None` means that the blending
+    /// function of the material's shader should be used.
+    pub fn blend_func(self) -> Option<BlendFunc> {
+        match self {
+            ParticleBlendMode::AlphaBlend => Some(BlendFunc::new(
+                BlendFactor::SrcAlpha,
+                BlendFactor::OneMinusSrcAlpha,
+            )),
+            ParticleBlendMode::Additive => {
+                Some(BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::One))
+            }
+            ParticleBlendMode::Inherit => None,
+        }
+    }
+}
+
+/// Simulation space defines a coordinate system in which particles of a particle system live.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "6e1b0f5a-3c0d-4b7e-9d8a-2f4c6a1e5b93")]
+#[repr(u32)]
+pub enum SimulationSpace {
+    /// Particles are simulated in local coordinates of the particle system, which means that every
+    /// particle moves together with the particle system.
+    #[default]
+    Local = 0,
+    /// Particles are transformed into world coordinates when spawned and stay where they were born,
+    /// regardless of further movement of the particle system. Suitable for trails of moving objects.
+    /// Keep in mind, that frustum culling is still done using the bounding box of the particle system,
+    /// so disable it if the particles could travel far away from the particle system.
+    World = 1,
+}
+
 /// Particle system used to create visual effects that consists of many small parts,
 /// this can be smoke, fire, dust, sparks, etc. Particle system optimized to operate
 /// on many small parts, so it is much efficient to use particle system instead of
@@ -228,15 +334,50 @@ pub struct ParticleSystem {
     #[reflect(setter = "play")]
     is_playing: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_blend_mode")]
+    blend_mode: InheritableVariable<ParticleBlendMode>,
+
+    #[reflect(setter = "set_frames_h", min_value = 1.0)]
+    frames_h: InheritableVariable<u32>,
+
+    #[reflect(setter = "set_frames_v", min_value = 1.0)]
+    frames_v: InheritableVariable<u32>,
+
+    #[reflect(setter = "set_frames_per_second", min_value = 0.0)]
+    frames_per_second: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_simulation_space")]
+    simulation_space: InheritableVariable<SimulationSpace>,
+
+    #[reflect(setter = "set_max_particles")]
+    max_particles: InheritableVariable<Option<u32>>,
+
+    #[reflect(setter = "set_affected_by_force_fields")]
+    affected_by_force_fields: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     particles: Vec<Particle>,
 
     #[reflect(hidden)]
     free_particles: Vec<u32>,
 
+    #[reflect(hidden)]
+    statistics: ParticleSystemStatistics,
+
     rng: ParticleSystemRng,
 }
 
+/// Runtime counters of a particle system. See [`ParticleSystem::statistics`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ParticleSystemStatistics {
+    /// Amount of particles spawned during last update.
+    pub spawned_this_frame: usize,
+    /// Amount of particles died during last update.
+    pub killed_this_frame: usize,
+    /// Total amount of particles spawned since the last reset of the particle system.
+    pub total_spawned: u64,
+}
+
 impl Visit for ParticleSystem {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
@@ -250,6 +391,15 @@ impl Visit for ParticleSystem {
         self.particles.visit("Particles", &mut region)?;
         self.free_particles.visit("FreeParticles", &mut region)?;
         let _ = self.rng.visit("Rng", &mut region);
+        let _ = self.blend_mode.visit("ParticleBlendMode", &mut region);
+        let _ = self.frames_h.visit("FramesH", &mut region);
+        let _ = self.frames_v.visit("FramesV", &mut region);
+        let _ = self.frames_per_second.visit("FramesPerSecond", &mut region);
+        let _ = self.simulation_space.visit("SimulationSpace", &mut region);
+        let _ = self.max_particles.visit("MaxParticles", &mut region);
+        let _ = self
+            .affected_by_force_fields
+            .visit("AffectedByForceFields", &mut region);
 
         // Backward compatibility.
         if region.is_reading() {
@@ -312,12 +462,20 @@ impl ParticleSystem {
         self.acceleration.set_value_and_mark_modified(accel)
     }
 
-    /// Sets new "color curve" that will evaluate color over lifetime.
+    /// Sets new "color curve" that will evaluate color over lifetime. The gradient is sampled using
+    /// normalized age of each particle (`0.0` - just spawned, `1.0` - about to die) and the sampled
+    /// color is used as vertex color of the particle, which is then multiplied with the texture color
+    /// by the standard particle system shader. An empty gradient means white color.
     pub fn set_color_over_lifetime_gradient(&mut self, gradient: ColorGradient) -> ColorGradient {
         self.color_over_lifetime
             .set_value_and_mark_modified(gradient)
     }
 
+    /// Returns current "color curve" that evaluates color over lifetime.
+    pub fn color_over_lifetime_gradient(&self) -> &ColorGradient {
+        &self.color_over_lifetime
+    }
+
     /// Plays or pauses the particle system. Paused particle system remains in "frozen" state
     /// until played again again. You can manually reset state of the system by calling [`Self::clear_particles`].
     pub fn play(&mut self, is_playing: bool) -> bool {
@@ -329,6 +487,150 @@ impl ParticleSystem {
         *self.is_playing
     }
 
+    /// Sets new blending mode of the particle system. See [`ParticleBlendMode`] docs for more info.
+    pub fn set_blend_mode(&mut self, blend_mode: ParticleBlendMode) -> ParticleBlendMode {
+        self.blend_mode.set_value_and_mark_modified(blend_mode)
+    }
+
+    /// Returns current blending mode of the particle system.
+    pub fn blend_mode(&self) -> ParticleBlendMode {
+        *self.blend_mode
+    }
+
+    /// Sets amount of frames in a row of the sprite sheet (texture atlas) of the particle system. Use
+    /// it in conjunction with [`Self::set_frames_v`] and [`Self::set_frames_per_second`] to animate
+    /// particles. The value will be clamped to be at least 1.
+    pub fn set_frames_h(&mut self, frames_h: u32) -> u32 {
+        self.frames_h.set_value_and_mark_modified(frames_h.max(1))
+    }
+
+    /// Returns amount of frames in a row of the sprite sheet of the particle system.
+    pub fn frames_h(&self) -> u32 {
+        *self.frames_h
+    }
+
+    /// Sets amount of frames in a column of the sprite sheet (texture atlas) of the particle system.
+    /// The value will be clamped to be at least 1.
+    pub fn set_frames_v(&mut self, frames_v: u32) -> u32 {
+        self.frames_v.set_value_and_mark_modified(frames_v.max(1))
+    }
+
+    /// Returns amount of frames in a column of the sprite sheet of the particle system.
+    pub fn frames_v(&self) -> u32 {
+        *self.frames_v
+    }
+
+    /// Sets playback speed of the sprite sheet animation. Every particle starts from the first frame
+    /// and switches frames depending on its age, the animation is looped. Zero means that only the
+    /// first frame will be used.
+    pub fn set_frames_per_second(&mut self, frames_per_second: f32) -> f32 {
+        self.frames_per_second
+            .set_value_and_mark_modified(frames_per_second.max(0.0))
+    }
+
+    /// Returns playback speed of the sprite sheet animation.
+    pub fn frames_per_second(&self) -> f32 {
+        *self.frames_per_second
+    }
+
+    /// Sets new simulation space of the particle system. See [`SimulationSpace`] docs for more info.
+    /// Already spawned particles are not affected, use [`Self::clear_particles`] if needed.
+    pub fn set_simulation_space(&mut self, simulation_space: SimulationSpace) -> SimulationSpace {
+        self.simulation_space
+            .set_value_and_mark_modified(simulation_space)
+    }
+
+    /// Returns current simulation space of the particle system.
+    pub fn simulation_space(&self) -> SimulationSpace {
+        *self.simulation_space
+    }
+
+    /// Sets the maximum amount of alive particles of the entire particle system (all emitters
+    /// together). Emitters stop spawning new particles when the limit is reached, until some of
+    /// alive particles die. Memory for the particles is reserved up-front, so the particle system
+    /// will not re-allocate its buffers when it grows up to the limit. `None` means no limit.
+    pub fn set_max_particles(&mut self, max_particles: Option<u32>) -> Option<u32> {
+        self.max_particles
+            .set_value_and_mark_modified(max_particles)
+    }
+
+    /// Returns the maximum amount of alive particles of the particle system.
+    pub fn max_particles(&self) -> Option<u32> {
+        *self.max_particles
+    }
+
+    /// Sets new seed of the pseudo-random numbers generator of the particle system and restarts the
+    /// generator. Every random parameter of particles is sampled using this generator only, so the
+    /// particle system with the same seed and the same sequence of updates will always produce the
+    /// same particles. Returns previous seed. Use [`Self::reset`] to restart the simulation.
+    pub fn set_seed(&mut self, seed: u64) -> u64 {
+        self.rng.set_seed(seed)
+    }
+
+    /// Returns current seed of the pseudo-random numbers generator of the particle system.
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    /// Removes all particles, resets state of every emitter (including pending bursts) and restarts
+    /// the pseudo-random numbers generator from its seed, so the simulation will be repeated exactly
+    /// from the beginning.
+    pub fn reset(&mut self) {
+        self.rng.reset();
+        self.clear_particles();
+        for emitter in self.emitters.get_value_mut_silent().iter_mut() {
+            emitter.reset();
+        }
+    }
+
+    /// Requests every emitter of the particle system to spawn the given amount of particles on next
+    /// update, regardless of their spawn rate. Use [`emitter::base::EmissionMode::BurstOnly`] for
+    /// emitters, that should spawn particles only by bursts (explosions, impacts, etc.). The burst
+    /// respects the maximum amount of particles (see [`Self::set_max_particles`]).
+    pub fn burst(&mut self, count: u32) {
+        for emitter in self.emitters.get_value_mut_silent().iter_mut() {
+            emitter.burst(count);
+        }
+    }
+
+    /// Defines whether the particles are affected by force fields (see [`force_field::ForceField`]) or not.
+    /// Disabled by default, because every force field of the scene must be checked on each update.
+    pub fn set_affected_by_force_fields(&mut self, affected: bool) -> bool {
+        self.affected_by_force_fields
+            .set_value_and_mark_modified(affected)
+    }
+
+    /// Returns `true` if the particles are affected by force fields, `false` - otherwise.
+    pub fn is_affected_by_force_fields(&self) -> bool {
+        *self.affected_by_force_fields
+    }
+
+    /// Returns current amount of alive particles.
+    pub fn alive_particle_count(&self) -> usize {
+        self.particles.len() - self.free_particles.len()
+    }
+
+    /// Returns runtime counters of the particle system. The counters are not serialized and start
+    /// from zero after loading or [`Self::clear_particles`].
+    pub fn statistics(&self) -> ParticleSystemStatistics {
+        self.statistics
+    }
+
+    /// Returns amount of particles spawned during last update.
+    pub fn spawned_this_frame(&self) -> usize {
+        self.statistics.spawned_this_frame
+    }
+
+    /// Returns amount of particles died during last update.
+    pub fn killed_this_frame(&self) -> usize {
+        self.statistics.killed_this_frame
+    }
+
+    /// Returns total amount of particles spawned since the last reset of the particle system.
+    pub fn total_spawned(&self) -> u64 {
+        self.statistics.total_spawned
+    }
+
     /// Replaces the particles in the particle system with pre-generated set. It could be useful
     /// to create procedural particle effects; when particles cannot be pre-made.
     pub fn set_particles(&mut self, particles: Vec<Particle>) {
@@ -345,9 +647,11 @@ impl ParticleSystem {
     pub fn clear_particles(&mut self) {
         self.particles.clear();
         self.free_particles.clear();
+        self.statistics = Default::default();
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
             emitter.alive_particles = 0;
             emitter.spawned_particles = 0;
+            emitter.elapsed_time = 0.0;
         }
     }
 
@@ -366,12 +670,34 @@ impl ParticleSystem {
         &self.material
     }
 
-    fn tick(&mut self, dt: f32) {
+    fn tick(&mut self, dt: f32, force_fields: &[ActiveForceField]) {
+        self.statistics.spawned_this_frame = 0;
+        self.statistics.killed_this_frame = 0;
+
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
             emitter.tick(dt);
         }
 
+        let global_transform = self.global_transform();
+        let simulation_space = *self.simulation_space;
+
+        let mut spawn_budget = u32::MAX as usize;
+        if let Some(max_particles) = *self.max_particles {
+            let max_particles = max_particles as usize;
+            if self.particles.capacity() < max_particles {
+                self.particles
+                    .reserve_exact(max_particles - self.particles.len());
+            }
+            spawn_budget = max_particles.saturating_sub(self.alive_particle_count());
+        }
+
         for (i, emitter) in self.emitters.get_value_mut_silent().iter_mut().enumerate() {
+            let particles_to_spawn = emitter.particles_to_spawn.min(spawn_budget as u32);
+            // Dropped particles must not be counted as spawned.
+            emitter.spawned_particles -= (emitter.particles_to_spawn - particles_to_spawn) as u64;
+            emitter.particles_to_spawn = particles_to_spawn;
+            spawn_budget -= particles_to_spawn as usize;
+            self.statistics.spawned_this_frame += particles_to_spawn as usize;
             for _ in 0..emitter.particles_to_spawn {
                 let mut particle = Particle {
                     emitter_index: i as u32,
@@ -379,6 +705,12 @@ impl ParticleSystem {
                 };
                 emitter.alive_particles += 1;
                 emitter.emit(&mut particle, &mut self.rng);
+                if simulation_space == SimulationSpace::World {
+                    particle.position = global_transform
+                        .transform_point(&Point3::from(particle.position))
+                        .coords;
+                    particle.velocity = global_transform.transform_vector(&particle.velocity);
+                }
                 if let Some(free_index) = self.free_particles.pop() {
                     self.particles[free_index as usize] = particle;
                 } else {
@@ -394,6 +726,7 @@ impl ParticleSystem {
                 particle.lifetime += dt;
                 if particle.lifetime >= particle.initial_lifetime {
                     self.free_particles.push(i as u32);
+                    self.statistics.killed_this_frame += 1;
                     if let Some(emitter) = self
                         .emitters
                         .get_value_mut_and_mark_modified()
@@ -405,6 +738,10 @@ impl ParticleSystem {
                     particle.lifetime = particle.initial_lifetime;
                 } else {
                     particle.velocity += acceleration_offset;
+                    for force_field in force_fields {
+                        particle.velocity +=
+                            force_field.acceleration(particle.position).scale(dt * dt);
+                    }
                     particle.position += particle.velocity;
                     particle.size += particle.size_modifier * dt;
                     if particle.size < 0.0 {
@@ -417,18 +754,56 @@ impl ParticleSystem {
                 }
             }
         }
+
+        self.statistics.total_spawned += self.statistics.spawned_this_frame as u64;
+    }
+
+    // Picks the force fields, that could affect the particles, and transforms them into simulation
+    // space. Fields, that do not reach bounds of alive particles, are rejected.
+    fn collect_force_fields(&self, force_fields: &[ActiveForceField]) -> Vec<ActiveForceField> {
+        let mut bounds = AxisAlignedBoundingBox::default();
+        for particle in self.particles.iter().filter(|particle| particle.alive) {
+            bounds.add_point(particle.position);
+        }
+        if !bounds.is_valid() {
+            return Vec::new();
+        }
+
+        let (to_simulation_space, scale) = match *self.simulation_space {
+            SimulationSpace::Local => {
+                let global_transform = self.global_transform();
+                // Non-uniform scale is approximated by its largest component.
+                let scale = global_transform
+                    .side()
+                    .norm()
+                    .max(global_transform.up().norm())
+                    .max(global_transform.look().norm());
+                (
+                    global_transform.try_inverse().unwrap_or_default(),
+                    if scale > f32::EPSILON { scale } else { 1.0 },
+                )
+            }
+            SimulationSpace::World => (Matrix4::identity(), 1.0),
+        };
+
+        force_fields
+            .iter()
+            .map(|force_field| force_field.transform(&to_simulation_space, scale))
+            .filter(|force_field| {
+                bounds.is_intersects_sphere(force_field.center, force_field.radius)
+            })
+            .collect()
     }
 
     /// Simulates particle system for the given `time` with given time step (`dt`). `dt` is usually `1.0 / 60.0`.
     pub fn rewind(&mut self, dt: f32, time: f32) {
         assert!(dt > 0.0);
 
-        self.rng.reset();
-        self.clear_particles();
+        self.reset();
 
         let mut t = 0.0;
         while t < time {
-            self.tick(dt);
+            self.tick(dt, &[]);
             t += dt;
         }
     }
@@ -440,6 +815,23 @@ impl Default for ParticleSystem {
     }
 }
 
+/// Checks whether the shader of the given material has `OitForward` render pass, which is used to
+/// render alpha-blended particles using order-independent transparency.
+fn supports_order_independent_transparency(material: &MaterialResource) -> bool {
+    let mut material_state = material.state();
+    let Some(material) = material_state.data() else {
+        return false;
+    };
+    let mut shader_state = material.shader().state();
+    shader_state.data().map_or(false, |shader| {
+        shader
+            .definition
+            .passes
+            .iter()
+            .any(|pass| pass.name == "OitForward")
+    })
+}
+
 impl NodeTrait for ParticleSystem {
     crate::impl_query_component!();
 
@@ -460,17 +852,20 @@ impl NodeTrait for ParticleSystem {
         let dt = context.dt;
 
         if *self.is_playing {
-            self.tick(dt);
+            let force_fields = if *self.affected_by_force_fields {
+                self.collect_force_fields(context.force_fields)
+            } else {
+                Vec::new()
+            };
+
+            self.tick(dt, &force_fields);
         }
     }
 
     fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
         if !self.global_visibility()
             || !self.is_globally_enabled()
-            || (self.frustum_culling()
-                && !ctx
-                    .frustum
-                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+            || !ctx.is_inside_frustum(self.frustum_culling(), &self.world_bounding_box())
         {
             return RdcControlFlow::Continue;
         }
@@ -482,7 +877,10 @@ impl NodeTrait for ParticleSystem {
         let mut sorted_particles = Vec::new();
         for (i, particle) in self.particles.iter().enumerate() {
             if particle.alive {
-                let actual_position = particle.position + self.base.global_position();
+                let actual_position = match *self.simulation_space {
+                    SimulationSpace::Local => particle.position + self.base.global_position(),
+                    SimulationSpace::World => particle.position,
+                };
                 particle
                     .sqr_distance_to_camera
                     .set((*ctx.observer_position - actual_position).norm_squared());
@@ -490,24 +888,62 @@ impl NodeTrait for ParticleSystem {
             }
         }
 
-        let particles = &self.particles;
+        if let Some(limit) = ctx
+            .particle_budget
+            .and_then(|budget| budget.limit(self.self_handle))
+        {
+            if limit == 0 {
+                return RdcControlFlow::Continue;
+            } else if limit < sorted_particles.len() {
+                // Keep the particles closest to the observer.
+                sorted_particles.select_nth_unstable_by(limit, |a, b| {
+                    let distance_a = self.particles[*a as usize].sqr_distance_to_camera.get();
+                    let distance_b = self.particles[*b as usize].sqr_distance_to_camera.get();
+                    distance_a.total_cmp(&distance_b)
+                });
+                sorted_particles.truncate(limit);
+            }
+        }
 
-        sorted_particles.sort_by(|a, b| {
-            let particle_a = particles.get(*a as usize).unwrap();
-            let particle_b = particles.get(*b as usize).unwrap();
+        let particles = &self.particles;
 
-            // Reverse ordering because we want to sort back-to-front.
-            if particle_a.sqr_distance_to_camera < particle_b.sqr_distance_to_camera {
-                Ordering::Greater
-            } else if particle_a.sqr_distance_to_camera > particle_b.sqr_distance_to_camera {
-                Ordering::Less
-            } else {
-                Ordering::Equal
-            }
-        });
+        let blend_mode = *self.blend_mode;
+
+        // Additive blending is order-independent, so particles with any other blending must be sorted.
+        // Order-independent transparency does not need sorting as well, but it is used only if the
+        // shader of the material has the respective render pass.
+        let needs_sorting = blend_mode != ParticleBlendMode::Additive
+            && !(ctx.particle_transparency == TransparencyMode::WeightedBlended
+                && supports_order_independent_transparency(&self.material));
+
+        if needs_sorting {
+            sorted_particles.sort_by(|a, b| {
+                let particle_a = particles.get(*a as usize).unwrap();
+                let particle_b = particles.get(*b as usize).unwrap();
+
+                // Reverse ordering because we want to sort back-to-front.
+                if particle_a.sqr_distance_to_camera < particle_b.sqr_distance_to_camera {
+                    Ordering::Greater
+                } else if particle_a.sqr_distance_to_camera > particle_b.sqr_distance_to_camera {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            });
+        }
 
         let global_transform = self.global_transform();
-        let sort_index = ctx.calculate_sorting_index(self.global_position());
+        let simulation_space = *self.simulation_space;
+        let frames_h = (*self.frames_h).max(1);
+        let frames_v = (*self.frames_v).max(1);
+        let frame_count = frames_h * frames_v;
+        let frames_per_second = *self.frames_per_second;
+        let frame_size = Vector2::new(1.0 / frames_h as f32, 1.0 / frames_v as f32);
+        let sort_index = if needs_sorting {
+            ctx.calculate_sorting_index(self.global_position())
+        } else {
+            0
+        };
 
         ctx.storage.push_triangles(
             Vertex::layout(),
@@ -516,14 +952,29 @@ impl NodeTrait for ParticleSystem {
             0,
             sort_index,
             false,
+            blend_mode.blend_func(),
             self.self_handle,
             &mut move |mut vertex_buffer, mut triangle_buffer| {
                 let vertices = sorted_particles.iter().flat_map(move |particle_index| {
                     let particle = self.particles.get(*particle_index as usize).unwrap();
 
-                    let position = global_transform
-                        .transform_point(&Point3::from(particle.position))
-                        .coords;
+                    let position = match simulation_space {
+                        SimulationSpace::Local => {
+                            global_transform
+                                .transform_point(&Point3::from(particle.position))
+                                .coords
+                        }
+                        SimulationSpace::World => particle.position,
+                    };
+
+                    let frame = (particle.lifetime * frames_per_second) as u32 % frame_count;
+                    let frame_origin = Vector2::new(
+                        (frame % frames_h) as f32 * frame_size.x,
+                        (frame / frames_h) as f32 * frame_size.y,
+                    );
+                    let atlas_tex_coord = |u: f32, v: f32| {
+                        frame_origin + Vector2::new(u, v).component_mul(&frame_size)
+                    };
 
                     [
                         Vertex {
@@ -532,6 +983,7 @@ impl NodeTrait for ParticleSystem {
                             size: particle.size,
                             rotation: particle.rotation,
                             color: particle.color,
+                            atlas_tex_coord: atlas_tex_coord(0.0, 0.0),
                         },
                         Vertex {
                             position,
@@ -539,6 +991,7 @@ impl NodeTrait for ParticleSystem {
                             size: particle.size,
                             rotation: particle.rotation,
                             color: particle.color,
+                            atlas_tex_coord: atlas_tex_coord(1.0, 0.0),
                         },
                         Vertex {
                             position,
@@ -546,6 +999,7 @@ impl NodeTrait for ParticleSystem {
                             size: particle.size,
                             rotation: particle.rotation,
                             color: particle.color,
+                            atlas_tex_coord: atlas_tex_coord(1.0, 1.0),
                         },
                         Vertex {
                             position,
@@ -553,6 +1007,7 @@ impl NodeTrait for ParticleSystem {
                             size: particle.size,
                             rotation: particle.rotation,
                             color: particle.color,
+                            atlas_tex_coord: atlas_tex_coord(0.0, 1.0),
                         },
                     ]
                 });
@@ -593,6 +1048,15 @@ pub struct ParticleSystemBuilder {
     color_over_lifetime: ColorGradient,
     is_playing: bool,
     rng: ParticleSystemRng,
+    blend_mode: ParticleBlendMode,
+    frames_h: u32,
+    frames_v: u32,
+    frames_per_second: f32,
+    texture: Option<TextureResource>,
+    soft_boundary_sharpness_factor: Option<f32>,
+    simulation_space: SimulationSpace,
+    max_particles: Option<u32>,
+    affected_by_force_fields: bool,
 }
 
 impl ParticleSystemBuilder {
@@ -610,6 +1074,15 @@ impl ParticleSystemBuilder {
             color_over_lifetime: Default::default(),
             is_playing: true,
             rng: ParticleSystemRng::default(),
+            blend_mode: Default::default(),
+            frames_h: 1,
+            frames_v: 1,
+            frames_per_second: 0.0,
+            texture: None,
+            soft_boundary_sharpness_factor: None,
+            simulation_space: Default::default(),
+            max_particles: None,
+            affected_by_force_fields: false,
         }
     }
 
@@ -625,6 +1098,23 @@ impl ParticleSystemBuilder {
         self
     }
 
+    /// Sets desired diffuse texture for particle system. The texture will be assigned to the `diffuseTexture`
+    /// property of the material of particle system, so make sure that the material uses a shader with such
+    /// property (the standard particle system shader has it). The material is copied, so other users of the
+    /// material are not affected.
+    pub fn with_texture(mut self, texture: TextureResource) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Sets desired sharpness factor of soft boundaries of particles. The value will be assigned to the
+    /// `softBoundarySharpnessFactor` property of the material of particle system. The higher the value,
+    /// the sharper the boundaries of particles that intersect scene geometry.
+    pub fn with_soft_boundary_sharpness_factor(mut self, factor: f32) -> Self {
+        self.soft_boundary_sharpness_factor = Some(factor);
+        self
+    }
+
     /// Sets desired acceleration for particle system.
     pub fn with_acceleration(mut self, acceleration: Vector3<f32>) -> Self {
         self.acceleration = acceleration;
@@ -656,17 +1146,94 @@ impl ParticleSystemBuilder {
         self
     }
 
-    fn build_particle_system(self) -> ParticleSystem {
+    /// Sets desired blending mode of the particle system.
+    pub fn with_blend_mode(mut self, blend_mode: ParticleBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// Sets desired layout of the sprite sheet (texture atlas) of the particle system, `frames_h` is the
+    /// amount of frames in a row, `frames_v` - the amount of frames in a column.
+    pub fn with_sprite_sheet(mut self, frames_h: u32, frames_v: u32) -> Self {
+        self.frames_h = frames_h;
+        self.frames_v = frames_v;
+        self
+    }
+
+    /// Sets desired playback speed of the sprite sheet animation.
+    pub fn with_frames_per_second(mut self, frames_per_second: f32) -> Self {
+        self.frames_per_second = frames_per_second;
+        self
+    }
+
+    /// Sets desired simulation space of the particle system.
+    pub fn with_simulation_space(mut self, simulation_space: SimulationSpace) -> Self {
+        self.simulation_space = simulation_space;
+        self
+    }
+
+    /// Sets the maximum amount of alive particles of the particle system. See
+    /// [`ParticleSystem::set_max_particles`] for more info.
+    pub fn with_max_particles(mut self, max_particles: u32) -> Self {
+        self.max_particles = Some(max_particles);
+        self
+    }
+
+    /// Sets whether the particles are affected by force fields or not.
+    pub fn with_affected_by_force_fields(mut self, affected: bool) -> Self {
+        self.affected_by_force_fields = affected;
+        self
+    }
+
+    /// Creates new instance of particle system, that could be added to a scene graph later on.
+    pub fn build_particle_system(self) -> ParticleSystem {
+        // Material could be shared with other particle systems, so the properties are set on a
+        // copy of it.
+        let material = if self.texture.is_some() || self.soft_boundary_sharpness_factor.is_some() {
+            let material = self.material.deep_copy_as_embedded();
+            let mut material_ref = material.data_ref();
+
+            if let Some(texture) = self.texture {
+                Log::verify(material_ref.set_property(
+                    &ImmutableString::new("diffuseTexture"),
+                    PropertyValue::Sampler {
+                        value: Some(texture),
+                        fallback: Default::default(),
+                    },
+                ));
+            }
+
+            if let Some(factor) = self.soft_boundary_sharpness_factor {
+                Log::verify(material_ref.set_property(
+                    &ImmutableString::new("softBoundarySharpnessFactor"),
+                    PropertyValue::Float(factor),
+                ));
+            }
+
+            drop(material_ref);
+            material
+        } else {
+            self.material
+        };
+
         ParticleSystem {
             base: self.base_builder.build_base(),
             particles: self.particles,
             free_particles: Vec::new(),
+            statistics: Default::default(),
             emitters: self.emitters.into(),
-            material: self.material.into(),
+            material: material.into(),
             acceleration: self.acceleration.into(),
             color_over_lifetime: self.color_over_lifetime.into(),
             is_playing: self.is_playing.into(),
             rng: self.rng,
+            blend_mode: self.blend_mode.into(),
+            frames_h: self.frames_h.max(1).into(),
+            frames_v: self.frames_v.max(1).into(),
+            frames_per_second: self.frames_per_second.max(0.0).into(),
+            simulation_space: self.simulation_space.into(),
+            max_particles: self.max_particles.into(),
+            affected_by_force_fields: self.affected_by_force_fields.into(),
         }
     }
 
@@ -680,3 +1247,47 @@ impl ParticleSystemBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            particle_system::{
+                emitter::{base::BaseEmitterBuilder, sphere::SphereEmitterBuilder},
+                ParticleSystem, ParticleSystemBuilder, ParticleSystemRng,
+            },
+        },
+    };
+
+    fn particle_positions(particle_system: &ParticleSystem) -> Vec<Vector3<f32>> {
+        particle_system
+            .particles()
+            .iter()
+            .map(|particle| particle.position)
+            .collect()
+    }
+
+    #[test]
+    fn test_reset_repeats_simulation() {
+        let mut particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_emitters(vec![SphereEmitterBuilder::new(
+                BaseEmitterBuilder::new().with_spawn_rate(30),
+            )
+            .build()])
+            .with_rng(ParticleSystemRng::new(42))
+            .build_particle_system();
+
+        // Two updates with a time step, that leaves a fraction of a particle in the spawn time
+        // accumulator of the emitter.
+        particle_system.rewind(0.25, 0.5);
+        let first_run = particle_positions(&particle_system);
+        assert!(!first_run.is_empty());
+
+        // Neither the accumulated spawn time nor pending bursts must leak into the next run.
+        particle_system.emitters.get_value_mut_silent()[0].burst(10);
+        particle_system.rewind(0.25, 0.5);
+        assert_eq!(particle_positions(&particle_system), first_run);
+    }
+}
@@ -2105,10 +2105,7 @@ impl NodeTrait for Terrain {
     fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
         if !self.global_visibility()
             || !self.is_globally_enabled()
-            || (self.frustum_culling()
-                && !ctx
-                    .frustum
-                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+            || !ctx.is_inside_frustum(self.frustum_culling(), &self.world_bounding_box())
         {
             return RdcControlFlow::Continue;
         }
@@ -2226,6 +2223,7 @@ impl NodeTrait for Terrain {
                                     node.persistent_index,
                                 ),
                                 node_handle: self.self_handle,
+                                instanced: false,
                             },
                         );
                     } else {
@@ -2249,6 +2247,7 @@ impl NodeTrait for Terrain {
                                             node.persistent_index,
                                         ),
                                         node_handle: self.self_handle,
+                                        instanced: false,
                                     },
                                 );
                             }
@@ -610,10 +610,7 @@ impl NodeTrait for TileMap {
     fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
         if !self.global_visibility()
             || !self.is_globally_enabled()
-            || (self.frustum_culling()
-                && !ctx
-                    .frustum
-                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+            || !ctx.is_inside_frustum(self.frustum_culling(), &self.world_bounding_box())
         {
             return RdcControlFlow::Continue;
         }
@@ -687,6 +684,7 @@ impl NodeTrait for TileMap {
                     0,
                     sort_index,
                     false,
+                    None,
                     self.self_handle,
                     &mut move |mut vertex_buffer, mut triangle_buffer| {
                         let start_vertex_index = vertex_buffer.vertex_count();
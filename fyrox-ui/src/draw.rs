@@ -747,6 +747,7 @@ pub struct DrawingContext {
     command_buffer: Vec<Command>,
     pub transform_stack: TransformStack,
     opacity_stack: Vec<f32>,
+    clip_stack: Vec<Rect<f32>>,
     triangles_to_commit: usize,
 }
 
@@ -758,6 +759,13 @@ fn get_line_thickness_vector(a: Vector2<f32>, b: Vector2<f32>, thickness: f32) -
     }
 }
 
+// Returns the intersection of two rectangles or an empty rectangle, if they do not intersect. Empty
+// rectangle clips everything away.
+fn clip_rect(rect: Rect<f32>, bounds: Rect<f32>) -> Rect<f32> {
+    rect.clip_by(bounds)
+        .unwrap_or_else(|| Rect::new(rect.position.x, rect.position.y, 0.0, 0.0))
+}
+
 impl Default for DrawingContext {
     fn default() -> Self {
         Self::new()
@@ -796,6 +804,7 @@ impl DrawingContext {
             command_buffer: Vec::new(),
             triangles_to_commit: 0,
             opacity_stack: vec![1.0],
+            clip_stack: Vec::new(),
             transform_stack: Default::default(),
         }
     }
@@ -807,6 +816,7 @@ impl DrawingContext {
         self.command_buffer.clear();
         self.opacity_stack.clear();
         self.opacity_stack.push(1.0);
+        self.clip_stack.clear();
         self.triangles_to_commit = 0;
     }
 
@@ -833,6 +843,29 @@ impl DrawingContext {
         self.opacity_stack.pop().unwrap();
     }
 
+    /// Pushes a new screen-space clipping rectangle. Every command that will be committed until the
+    /// matching [`Self::pop_clip_rect`] call will be clipped by it, in addition to the clipping bounds
+    /// passed to [`Self::commit`]. Nested rectangles are intersected with each other, so it is not
+    /// possible to draw outside of an outer rectangle.
+    pub fn push_clip_rect(&mut self, rect: Rect<f32>) {
+        let rect = match self.clip_stack.last() {
+            Some(top) => clip_rect(rect, *top),
+            None => rect,
+        };
+        self.clip_stack.push(rect);
+    }
+
+    /// Removes a clipping rectangle pushed by [`Self::push_clip_rect`].
+    pub fn pop_clip_rect(&mut self) {
+        self.clip_stack.pop().unwrap();
+    }
+
+    /// Returns current clipping rectangle (if any), it is an intersection of all rectangles in the
+    /// clip stack.
+    pub fn clip_rect(&self) -> Option<Rect<f32>> {
+        self.clip_stack.last().cloned()
+    }
+
     pub fn triangle_points(
         &self,
         triangle: &TriangleDefinition,
@@ -887,6 +920,10 @@ impl DrawingContext {
             let bounds = self.bounds_of(triangles.clone());
 
             let opacity = *self.opacity_stack.last().unwrap();
+            let clip_bounds = match self.clip_stack.last() {
+                Some(top) => clip_rect(clip_bounds, *top),
+                None => clip_bounds,
+            };
             self.command_buffer.push(Command {
                 clip_bounds,
                 bounds,
@@ -2,7 +2,7 @@
 
 use crate::{
     core::{reflect::prelude::*, uuid::Uuid, TypeUuidProvider},
-    font::Font,
+    font::{Font, FontRenderMode},
 };
 use fyrox_resource::{
     io::ResourceIo,
@@ -21,12 +21,15 @@ fn default_page_size() -> usize {
 pub struct FontImportOptions {
     #[serde(default = "default_page_size")]
     pub page_size: usize,
+    #[serde(default)]
+    pub render_mode: FontRenderMode,
 }
 
 impl Default for FontImportOptions {
     fn default() -> Self {
         Self {
             page_size: default_page_size(),
+            render_mode: Default::default(),
         }
     }
 }
@@ -57,9 +60,10 @@ impl ResourceLoader for FontLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let font = Font::from_file(&path, import_options.page_size, io)
+            let mut font = Font::from_file(&path, import_options.page_size, io)
                 .await
                 .map_err(LoadError::new)?;
+            font.set_render_mode(import_options.render_mode);
             Ok(LoaderPayload::new(font))
         })
     }
@@ -8,6 +8,7 @@ use fxhash::FxHashMap;
 use fyrox_resource::untyped::UntypedResource;
 use fyrox_resource::{io::ResourceIo, Resource, ResourceData};
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 use std::{
     any::Any,
@@ -17,6 +18,7 @@ use std::{
     ops::Deref,
     path::Path,
 };
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub mod loader;
 
@@ -31,6 +33,78 @@ pub struct FontGlyph {
     pub page_index: usize,
 }
 
+/// Defines how glyphs of a font are stored in its atlases and how they're rendered.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum FontRenderMode {
+    /// Glyphs are stored as coverage bitmaps. This mode gives the best quality when text is rendered
+    /// at its native size, but it looks blurry when scaled.
+    #[default]
+    Bitmap,
+    /// Glyphs are stored as signed distance fields, which allows renderers to reconstruct sharp
+    /// edges at any scale. Every glyph is padded by [`SDF_SPREAD`] pixels.
+    Sdf,
+}
+
+uuid_provider!(FontRenderMode = "c2b6e0d4-8f1a-4e7b-9a35-7d4f1e2c6b89");
+
+/// Max distance (in pixels) stored in distance fields of glyphs in [`FontRenderMode::Sdf`] mode.
+pub const SDF_SPREAD: usize = 4;
+
+// Converts a coverage bitmap into a signed distance field, the result is larger than the source by
+// `spread` pixels from each side. Value of 128 corresponds to the edge of a glyph, values above it
+// are inside the glyph.
+fn make_sdf(raster: &[u8], width: usize, height: usize, spread: usize) -> Vec<u8> {
+    let out_width = width + 2 * spread;
+    let out_height = height + 2 * spread;
+    let spread = spread as isize;
+
+    let is_inside = |x: isize, y: isize| {
+        x >= spread
+            && y >= spread
+            && ((x - spread) as usize) < width
+            && ((y - spread) as usize) < height
+            && raster[(y - spread) as usize * width + (x - spread) as usize] >= 128
+    };
+
+    let mut sdf = vec![0; out_width * out_height];
+    for y in 0..out_height as isize {
+        for x in 0..out_width as isize {
+            let inside = is_inside(x, y);
+
+            // Brute-force search of the closest pixel with the opposite state, glyphs are small so
+            // it is fast enough.
+            let mut min_sqr_distance = (spread * spread) as f32;
+            for dy in -spread..=spread {
+                for dx in -spread..=spread {
+                    if is_inside(x + dx, y + dy) != inside {
+                        min_sqr_distance = min_sqr_distance.min((dx * dx + dy * dy) as f32);
+                    }
+                }
+            }
+
+            let distance = min_sqr_distance.sqrt() / spread as f32;
+            let signed_distance = if inside { distance } else { -distance };
+            sdf[y as usize * out_width + x as usize] =
+                ((0.5 + signed_distance * 0.5).clamp(0.0, 1.0) * 255.0) as u8;
+        }
+    }
+    sdf
+}
+
 /// Page is a storage for rasterized glyphs.
 pub struct Page {
     pub pixels: Vec<u8>,
@@ -65,6 +139,7 @@ impl Atlas {
         unicode: char,
         height: FontHeight,
         page_size: usize,
+        render_mode: FontRenderMode,
     ) -> Option<&FontGlyph> {
         let border = 2;
 
@@ -77,9 +152,18 @@ impl Atlas {
                 // it in the inner font and render/pack it.
 
                 if let Some(char_index) = font.chars().get(&unicode) {
-                    let (metrics, glyph_raster) =
+                    let (mut metrics, mut glyph_raster) =
                         font.rasterize_indexed(char_index.get(), height.0);
 
+                    if render_mode == FontRenderMode::Sdf {
+                        glyph_raster =
+                            make_sdf(&glyph_raster, metrics.width, metrics.height, SDF_SPREAD);
+                        metrics.width += 2 * SDF_SPREAD;
+                        metrics.height += 2 * SDF_SPREAD;
+                        metrics.xmin -= SDF_SPREAD as i32;
+                        metrics.ymin -= SDF_SPREAD as i32;
+                    }
+
                     // Find a page, that is capable to fit the new character or create a new
                     // page and put the character there.
                     let mut placement_info =
@@ -188,6 +272,8 @@ pub struct Font {
     pub atlases: FxHashMap<FontHeight, Atlas>,
     #[visit(skip)]
     pub page_size: usize,
+    #[visit(skip)]
+    pub render_mode: FontRenderMode,
 }
 
 uuid_provider!(Font = "692fec79-103a-483c-bb0b-9fc3a349cb48");
@@ -258,6 +344,7 @@ impl Font {
             inner: Some(fontdue_font),
             atlases: Default::default(),
             page_size,
+            render_mode: Default::default(),
         })
     }
 
@@ -296,6 +383,7 @@ impl Font {
                 unicode,
                 FontHeight(height),
                 self.page_size,
+                self.render_mode,
             )
     }
 
@@ -324,6 +412,21 @@ impl Font {
         self.page_size
     }
 
+    /// Returns current render mode of the font.
+    #[inline]
+    pub fn render_mode(&self) -> FontRenderMode {
+        self.render_mode
+    }
+
+    /// Sets new render mode of the font. All glyphs rendered so far are discarded, so they will be
+    /// re-created in the new mode on demand.
+    pub fn set_render_mode(&mut self, render_mode: FontRenderMode) {
+        if self.render_mode != render_mode {
+            self.render_mode = render_mode;
+            self.atlases.clear();
+        }
+    }
+
     #[inline]
     pub fn glyph_advance(&mut self, unicode: char, height: f32) -> f32 {
         self.glyph(unicode, height)
@@ -334,12 +437,22 @@ impl Font {
 /// Font builder allows you to load fonts in declarative manner.
 pub struct FontBuilder {
     page_size: usize,
+    render_mode: FontRenderMode,
 }
 
 impl FontBuilder {
     /// Creates a default FontBuilder.
     pub fn new() -> Self {
-        Self { page_size: 1024 }
+        Self {
+            page_size: 1024,
+            render_mode: Default::default(),
+        }
+    }
+
+    /// Sets desired render mode of the font.
+    pub fn with_render_mode(mut self, render_mode: FontRenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
     }
 
     /// Creates a new font from the data at the specified path.
@@ -348,11 +461,15 @@ impl FontBuilder {
         path: impl AsRef<Path>,
         io: &dyn ResourceIo,
     ) -> Result<Font, &'static str> {
-        Font::from_file(path, self.page_size, io).await
+        let mut font = Font::from_file(path, self.page_size, io).await?;
+        font.set_render_mode(self.render_mode);
+        Ok(font)
     }
 
     /// Creates a new font from bytes in memory.
     pub fn build_from_memory(self, data: impl Deref<Target = [u8]>) -> Result<Font, &'static str> {
-        Font::from_memory(data, self.page_size)
+        let mut font = Font::from_memory(data, self.page_size)?;
+        font.set_render_mode(self.render_mode);
+        Ok(font)
     }
 }